@@ -1,8 +1,23 @@
+pub mod armor;
 pub mod background;
 pub mod character;
 pub mod classes;
+pub mod conditions;
+pub mod currency;
+pub mod damage;
+pub mod dice;
+pub mod equipment;
+pub mod feats;
+pub mod gear;
 pub mod gender;
+pub mod inventory;
+pub mod monsters;
 pub mod names;
 pub mod races;
+pub mod resources;
+pub mod rng;
+pub mod skills;
+pub mod spellcasting;
 pub mod stats;
 pub mod utils;
+pub mod weapons;
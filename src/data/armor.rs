@@ -0,0 +1,147 @@
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumIter, EnumString};
+
+/// The AC a shield adds.
+pub const SHIELD_BONUS: i32 = 2;
+/// A shield's weight in pounds.
+pub const SHIELD_WEIGHT: f32 = 6.0;
+/// A shield's price in copper pieces.
+pub const SHIELD_PRICE: u32 = 1_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Display)]
+pub enum ArmorCategory {
+    Light,
+    Medium,
+    Heavy,
+}
+
+/// The armor of the Player's Handbook.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, EnumIter, EnumString, Display)]
+#[strum(ascii_case_insensitive, serialize_all = "title_case")]
+pub enum Armor {
+    Padded,
+    Leather,
+    StuddedLeather,
+    Hide,
+    ChainShirt,
+    ScaleMail,
+    Breastplate,
+    HalfPlate,
+    RingMail,
+    ChainMail,
+    Splint,
+    Plate,
+}
+
+impl Armor {
+    pub fn category(&self) -> ArmorCategory {
+        match self {
+            Armor::Padded | Armor::Leather | Armor::StuddedLeather => ArmorCategory::Light,
+            Armor::Hide
+            | Armor::ChainShirt
+            | Armor::ScaleMail
+            | Armor::Breastplate
+            | Armor::HalfPlate => ArmorCategory::Medium,
+            Armor::RingMail | Armor::ChainMail | Armor::Splint | Armor::Plate => {
+                ArmorCategory::Heavy
+            }
+        }
+    }
+
+    fn base_ac(&self) -> i32 {
+        match self {
+            Armor::Padded | Armor::Leather => 11,
+            Armor::StuddedLeather | Armor::Hide => 12,
+            Armor::ChainShirt => 13,
+            Armor::ScaleMail | Armor::Breastplate | Armor::RingMail => 14,
+            Armor::HalfPlate => 15,
+            Armor::ChainMail => 16,
+            Armor::Splint => 17,
+            Armor::Plate => 18,
+        }
+    }
+
+    /// AC while wearing the armor: light armor adds the full Dexterity
+    /// modifier, medium armor up to +2 and heavy armor none.
+    pub fn armor_class(&self, dex_modifier: i32) -> i32 {
+        self.base_ac()
+            + match self.category() {
+                ArmorCategory::Light => dex_modifier,
+                ArmorCategory::Medium => dex_modifier.min(2),
+                ArmorCategory::Heavy => 0,
+            }
+    }
+
+    /// The Strength score needed to avoid losing 10 feet of speed in heavy armor.
+    pub fn strength_requirement(&self) -> Option<u8> {
+        match self {
+            Armor::ChainMail => Some(13),
+            Armor::Splint | Armor::Plate => Some(15),
+            _ => None,
+        }
+    }
+
+    pub fn stealth_disadvantage(&self) -> bool {
+        matches!(
+            self,
+            Armor::Padded
+                | Armor::ScaleMail
+                | Armor::HalfPlate
+                | Armor::RingMail
+                | Armor::ChainMail
+                | Armor::Splint
+                | Armor::Plate
+        )
+    }
+
+    /// Weight in pounds.
+    pub fn weight(&self) -> f32 {
+        match self {
+            Armor::Padded => 8.0,
+            Armor::Leather => 10.0,
+            Armor::Hide => 12.0,
+            Armor::StuddedLeather => 13.0,
+            Armor::ChainShirt | Armor::Breastplate => 20.0,
+            Armor::HalfPlate | Armor::RingMail => 40.0,
+            Armor::ScaleMail => 45.0,
+            Armor::ChainMail => 55.0,
+            Armor::Splint => 60.0,
+            Armor::Plate => 65.0,
+        }
+    }
+
+    /// Price in copper pieces.
+    pub fn price(&self) -> u32 {
+        match self {
+            Armor::Padded => 500,
+            Armor::Leather | Armor::Hide => 1_000,
+            Armor::RingMail => 3_000,
+            Armor::StuddedLeather => 4_500,
+            Armor::ChainShirt | Armor::ScaleMail => 5_000,
+            Armor::ChainMail => 7_500,
+            Armor::Splint => 20_000,
+            Armor::Breastplate => 40_000,
+            Armor::HalfPlate => 75_000,
+            Armor::Plate => 150_000,
+        }
+    }
+
+    /// A short summary, e.g. "Chain Mail (Heavy, AC 16, Str 13, Stealth disadvantage)".
+    pub fn describe(&self) -> String {
+        let mut details = vec![
+            self.category().to_string(),
+            match self.category() {
+                ArmorCategory::Light => format!("AC {} + Dex", self.base_ac()),
+                ArmorCategory::Medium => format!("AC {} + Dex (max 2)", self.base_ac()),
+                ArmorCategory::Heavy => format!("AC {}", self.base_ac()),
+            },
+        ];
+        if let Some(strength) = self.strength_requirement() {
+            details.push(format!("Str {}", strength));
+        }
+        if self.stealth_disadvantage() {
+            details.push(String::from("Stealth disadvantage"));
+        }
+        format!("{} ({})", self, details.join(", "))
+    }
+}
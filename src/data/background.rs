@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use strum_macros::{Display, EnumIter, EnumString};
 
+use super::rng::GameRng;
+use super::skills::Skill;
 use super::utils::{choose_value, Choosable, StringJoin};
 
 #[derive(Debug, PartialEq, Serialize, Deserialize, EnumIter, EnumString, Display)]
@@ -20,11 +22,31 @@ pub enum Background {
     Urchin,
 }
 
+impl Background {
+    pub fn skills(&self) -> [Skill; 2] {
+        match self {
+            Background::Acolyte => [Skill::Insight, Skill::Religion],
+            Background::Charlatan => [Skill::Deception, Skill::SleightOfHand],
+            Background::Criminal => [Skill::Deception, Skill::Stealth],
+            Background::Entertainer => [Skill::Acrobatics, Skill::Performance],
+            Background::FolkHero => [Skill::AnimalHandling, Skill::Survival],
+            Background::GuildArtisan => [Skill::Insight, Skill::Persuasion],
+            Background::Hermit => [Skill::Medicine, Skill::Religion],
+            Background::Noble => [Skill::History, Skill::Persuasion],
+            Background::Outlander => [Skill::Athletics, Skill::Survival],
+            Background::Sailor => [Skill::Athletics, Skill::Perception],
+            Background::Soldier => [Skill::Athletics, Skill::Intimidation],
+            Background::Urchin => [Skill::SleightOfHand, Skill::Stealth],
+        }
+    }
+}
+
 impl Choosable<Background> for Background {
-    fn choose() -> Background {
+    fn choose(rng: &mut GameRng) -> Background {
         choose_value(
             "What is your character's background?",
             &Background::collect_string(), // background_match_string
+            rng,
         )
     }
 }
@@ -1,54 +1,125 @@
-use std::cmp::Ordering;
+use std::collections::BTreeMap;
 
-use rand::{thread_rng, Rng};
 use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
 use term_table::{
     row::Row,
     table_cell::{Alignment, TableCell},
     Table, TableStyle,
 };
 
-use crate::data::utils::{pretty_print, BLUE};
+use crate::data::utils::{format_modifier, pretty_print, BLUE};
 
-use super::{background::Background, classes::Class, gender::Gender, races::Race, stats::Stat};
-
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
-enum Condition {
-    None,
-    Poisoned,
-    //TODO: Continue to fill out
-}
+use super::{
+    armor::{Armor, SHIELD_BONUS},
+    background::Background,
+    classes::{Class, ClassLevel, SorcererSubclass},
+    conditions::{
+        de_conditions, effective_speed, roll_effects, ActiveCondition, Condition, RollEffects,
+        RollKind, SaveAgainst, MAX_EXHAUSTION,
+    },
+    currency::Purse,
+    damage::{DamageDefense, DamageTaken, DamageType, DeathSaveOutcome, DeathSaves, Defense},
+    dice::{Dice, DiceExpression, RollResult},
+    feats::Feat,
+    gender::Gender,
+    inventory::{Encumbrance, Inventory},
+    races::Race,
+    resources::{Recharge, Resource, UNLIMITED},
+    rng::GameRng,
+    skills::{proficiency_bonus, Skill},
+    spellcasting::{ordinal, pact_slots, spell_slots, spellcasting_ability},
+    stats::{Stat, StatMethod},
+    weapons::{Grip, Weapon, WeaponProperty},
+};
 
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
-enum Dice {
-    D4,
-    D6,
-    D8,
-    D10,
-    D12,
-    D20,
-    D100,
-}
+/// The source given to the Unconscious condition of a character dropped to 0 HP.
+const DYING: &str = "0 hit points";
 
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Status {
-    armor_class: i8,
-    conditions: Condition,
+    armor_class: i32,
+    #[serde(deserialize_with = "de_conditions", default)]
+    pub conditions: Vec<ActiveCondition>,
+    #[serde(default)]
+    pub exhaustion: u8,
     blessed: bool,
-    initiative: i8,
-    hit_dice: Dice,
-    current_hp: i8,
-    maximum_hp: i8,
-    speed: u16,
+    initiative: i32,
+    current_hp: i32,
+    maximum_hp: i32,
+    /// Temporary hit points, which are lost first and don't stack.
+    #[serde(default)]
+    pub temporary_hp: i32,
+    /// Resistances, immunities and vulnerabilities from items and other
+    /// effects, added during play.
+    #[serde(default)]
+    pub damage_defenses: Vec<DamageDefense>,
+    /// Killed outright by massive damage, or by failing three death saves.
+    #[serde(default)]
+    pub dead: bool,
+    #[serde(default)]
+    pub death_saves: DeathSaves,
+    pub speed: u16,
+    /// Feet of movement left this turn.
+    #[serde(skip)]
+    pub movement: u16,
+    /// Spell slots expended since the last rest, by spell level (1st to 9th).
+    #[serde(default)]
+    pub spell_slots_used: [u8; 9],
+    #[serde(default)]
+    pub pact_slots_used: u8,
+    /// Uses spent of limited class features since they last recharged.
+    #[serde(default)]
+    pub resources_used: BTreeMap<Resource, u8>,
+    /// Hit dice spent on short rests, by die size, until a long rest recovers them.
+    #[serde(default)]
+    pub hit_dice_used: BTreeMap<Dice, u8>,
+    /// Rounds of Rage left; 0 when not raging.
+    #[serde(default)]
+    pub rage_rounds: u8,
+    /// Under the Mage Armor spell, which makes unarmored AC 13 + Dex.
+    #[serde(default)]
+    pub mage_armor: bool,
 }
 
-fn calculate_ac(stats: &[Stat], class: &Class) -> i8 {
-    let additional_ac = match class {
-        Class::Barbarian(_) => stats[2].get_modifier(),
-        Class::Monk(_) => stats[4].get_modifier(),
-        _ => 0,
+/// The best AC from the armor worn or, without armor, from Unarmored
+/// Defense, Draconic Resilience or Mage Armor, plus any shield. Unarmored
+/// Defense comes from whichever of Barbarian or Monk was taken first (the two
+/// don't stack), and a Monk's only works without a shield.
+fn calculate_ac(
+    stats: &[Stat],
+    classes: &[ClassLevel],
+    armor: Option<Armor>,
+    shield: bool,
+    mage_armor: bool,
+) -> i32 {
+    let dex_modifier = stats[1].get_modifier() as i32;
+    let best = match armor {
+        Some(armor) => armor.armor_class(dex_modifier),
+        None => {
+            let unarmored_defense = classes
+                .iter()
+                .find_map(|c| match c.class {
+                    Class::Barbarian(_) => Some(Some(stats[2].get_modifier())),
+                    Class::Monk(_) => Some(Some(stats[4].get_modifier()).filter(|_| !shield)),
+                    _ => None,
+                })
+                .flatten()
+                .unwrap_or(0) as i32;
+            let draconic = classes
+                .iter()
+                .any(|c| c.class == Class::Sorcerer(Some(SorcererSubclass::Draconic)));
+            let base = match draconic || mage_armor {
+                true => 13,
+                false => 10,
+            };
+            dex_modifier + base.max(10 + unarmored_defense)
+        }
     };
-    10 + stats[1].get_modifier() + additional_ac
+    match shield {
+        true => best + SHIELD_BONUS,
+        false => best,
+    }
 }
 
 fn calculate_speed(race: &Race) -> u16 {
@@ -62,129 +133,410 @@ fn calculate_speed(race: &Race) -> u16 {
     }
 }
 
+/// Dice counts as an expression, e.g. "2d10 + 5d6", or "none".
+fn format_hit_dice(dice: &[(Dice, u8)]) -> String {
+    if dice.is_empty() {
+        return String::from("none");
+    }
+    dice.iter()
+        .map(|(die, count)| die.expression(*count as u16).to_string())
+        .collect::<Vec<String>>()
+        .join(" + ")
+}
+
 impl Status {
     pub fn new(
         stats: &[Stat],
         race: &Race,
-        class: &Class,
-        level: &u8,
+        classes: &[ClassLevel],
         use_average_dice: bool,
+        rng: &mut GameRng,
     ) -> Status {
-        let hit_dice = match class {
-            Class::Artificer(_) => Dice::D8,
-            Class::Barbarian(_) => Dice::D12,
-            Class::Bard(_) => Dice::D8,
-            Class::Cleric(_) => Dice::D8,
-            Class::Druid(_) => Dice::D8,
-            Class::Fighter(_) => Dice::D10,
-            Class::Monk(_) => Dice::D8,
-            Class::Paladin(_) => Dice::D10,
-            Class::Ranger(_) => Dice::D10,
-            Class::Rogue(_) => Dice::D8,
-            Class::Sorcerer(_) => Dice::D6,
-            Class::Warlock(_) => Dice::D8,
-            Class::Wizard(_) => Dice::D6,
-        };
         let status = Status {
-            armor_class: calculate_ac(stats, class),
-            conditions: Condition::None,
+            armor_class: calculate_ac(stats, classes, None, false, false),
+            conditions: vec![],
+            exhaustion: 0,
             blessed: false,
-            initiative: stats[1].get_modifier(),
-            hit_dice,
+            initiative: stats[1].get_modifier() as i32,
             current_hp: 10,
             maximum_hp: 10,
+            temporary_hp: 0,
+            damage_defenses: vec![],
+            dead: false,
+            death_saves: DeathSaves::default(),
             speed: calculate_speed(race),
+            movement: 0,
+            spell_slots_used: [0; 9],
+            pact_slots_used: 0,
+            resources_used: BTreeMap::new(),
+            hit_dice_used: BTreeMap::new(),
+            rage_rounds: 0,
+            mage_armor: false,
         };
-        status.calculate_hp(level, stats[2].get_modifier(), use_average_dice)
+        status.calculate_hp(
+            classes,
+            stats[2].get_modifier(),
+            race.hp_bonus_per_level(),
+            use_average_dice,
+            rng,
+        )
     }
 
-    pub fn calculate_hp(mut self, level: &u8, con_modifier: i8, use_average_dice: bool) -> Self {
-        let hit_dice = &self.hit_dice;
+    /// Adds a condition, replacing any existing instance of the same condition.
+    pub fn add_condition(&mut self, active: ActiveCondition) {
+        self.remove_condition(active.condition);
+        self.conditions.push(active);
+    }
 
-        let hp_first_level: i8 = con_modifier
-            + match hit_dice {
-                Dice::D6 => 6,
-                Dice::D8 => 8,
-                Dice::D10 => 10,
-                Dice::D12 => 12,
-                _ => panic!("Somehow you don't have a d6, d8, d10, or d12 as your Hit Dice"),
-            };
+    pub fn remove_condition(&mut self, condition: Condition) {
+        self.conditions.retain(|c| c.condition != condition);
+    }
 
-        let maximum_hp: i8 = if use_average_dice {
-            let hp_per_level = match hit_dice {
-                Dice::D6 => 4,
-                Dice::D8 => 5,
-                Dice::D10 => 6,
-                Dice::D12 => 7,
-                _ => panic!("Somehow you don't have a d6, d8, d10, or d12 as your Hit Dice"),
-            };
+    pub fn set_exhaustion(&mut self, level: u8) {
+        self.exhaustion = level.min(MAX_EXHAUSTION);
+        self.current_hp = self.current_hp.min(self.effective_maximum_hp());
+    }
 
-            pretty_print(&format!("\nHP at Lv.1: {}", hp_first_level), BLUE, true);
-            pretty_print(
-                &format!(
-                    "HP increases by {} each subsequent level.\n",
-                    con_modifier + hp_per_level
-                ),
-                BLUE,
-                true,
-            );
+    /// Counts down timed conditions, returning the ones that have just ended.
+    pub fn tick_conditions(&mut self) -> Vec<ActiveCondition> {
+        for active in self.conditions.iter_mut() {
+            if let Some(rounds) = active.rounds_remaining.as_mut() {
+                *rounds = rounds.saturating_sub(1);
+            }
+        }
+        let (expired, remaining) = self
+            .conditions
+            .drain(..)
+            .partition(|c| c.rounds_remaining == Some(0));
+        self.conditions = remaining;
+        expired
+    }
 
-            hp_first_level + ((con_modifier + hp_per_level) * (*level - 1) as i8)
-        } else {
-            let mut hp_to_add = 0;
-            let mut rng = thread_rng();
-
-            pretty_print(&format!("\nHP at Lv.1: {}", hp_first_level), BLUE, true);
-
-            for lv in 1..*level {
-                let inc = match hit_dice {
-                    Dice::D6 => rng.gen_range(1..=6),
-                    Dice::D8 => rng.gen_range(1..=8),
-                    Dice::D10 => rng.gen_range(1..=10),
-                    Dice::D12 => rng.gen_range(1..=12),
-                    _ => panic!("Somehow you don't have a d6, d8, d10, or d12 as your Hit Dice"),
-                };
-                // println!(
-                //     "HP TO ADD: {}\nINC: {}\nCON MOD VALUE: {}\nHP TO ADD (AFTER): {}\n",
-                //     hp_to_add,
-                //     inc,
-                //     con_modifier,
-                //     inc + con_modifier
-                // );
-                hp_to_add += inc + con_modifier;
-                pretty_print(
-                    &format!(
-                        "\nYou rolled {}! [{}{}]\nHP at Lv.{}: {}",
-                        inc,
-                        match con_modifier.cmp(&0) {
-                            Ordering::Greater => "+",
-                            _ => "",
-                        },
-                        con_modifier,
-                        lv + 1,
-                        hp_first_level + hp_to_add
-                    ),
-                    BLUE,
-                    true,
-                );
+    pub fn roll_effects(&self, kind: RollKind) -> RollEffects {
+        roll_effects(&self.conditions, self.exhaustion, kind)
+    }
+
+    pub fn effective_speed(&self) -> u16 {
+        effective_speed(&self.conditions, self.exhaustion, self.speed)
+    }
+
+    /// Resets the movement budget to the current speed at the start of a turn.
+    pub fn start_movement(&mut self) {
+        self.movement = self.effective_speed();
+    }
+
+    /// The Dash action adds another speed's worth of movement.
+    pub fn dash(&mut self) {
+        self.movement += self.effective_speed();
+    }
+
+    /// Feet of movement each foot moved costs: one extra for difficult
+    /// terrain and one extra for crawling while prone.
+    pub fn movement_multiplier(&self, difficult_terrain: bool) -> u16 {
+        let crawling = self
+            .conditions
+            .iter()
+            .any(|c| c.condition == Condition::Prone);
+        1 + difficult_terrain as u16 + crawling as u16
+    }
+
+    /// Spends movement for moving `feet`. Returns the movement spent, or
+    /// `None` if there isn't enough left.
+    pub fn spend_movement(&mut self, feet: u16, difficult_terrain: bool) -> Option<u16> {
+        let cost = feet * self.movement_multiplier(difficult_terrain);
+        if self.effective_speed() == 0 || cost > self.movement {
+            return None;
+        }
+        self.movement -= cost;
+        Some(cost)
+    }
+
+    /// Standing up from prone costs half of the character's speed.
+    pub fn stand_up_cost(&self) -> u16 {
+        self.effective_speed() / 2
+    }
+
+    /// Stands up from prone, spending half of the character's speed. Returns
+    /// `false` if there isn't enough movement left.
+    pub fn stand_up(&mut self) -> bool {
+        let cost = self.stand_up_cost();
+        if self.effective_speed() == 0 || cost > self.movement {
+            return false;
+        }
+        self.movement -= cost;
+        self.remove_condition(Condition::Prone);
+        true
+    }
+
+    pub fn is_incapacitated(&self) -> bool {
+        self.conditions.iter().any(|c| c.condition.incapacitates())
+    }
+
+    /// Exhaustion level 4 halves the hit point maximum.
+    pub fn effective_maximum_hp(&self) -> i32 {
+        match self.exhaustion {
+            0..=3 => self.maximum_hp,
+            _ => self.maximum_hp / 2,
+        }
+    }
+
+    /// Maximum HP across every class level: the first class's hit die is
+    /// maxed at 1st level, and every later level adds that class's hit die.
+    /// `bonus_per_level` (e.g. Dwarven Toughness) is added at every level.
+    pub fn calculate_hp(
+        mut self,
+        classes: &[ClassLevel],
+        con_modifier: i8,
+        bonus_per_level: i32,
+        use_average_dice: bool,
+        rng: &mut GameRng,
+    ) -> Self {
+        let hp_first_level =
+            con_modifier as i32 + classes[0].class.hit_die().sides() as i32 + bonus_per_level;
+        pretty_print(&format!("\nHP at Lv.1: {}", hp_first_level), BLUE, true);
+
+        let mut maximum_hp = hp_first_level;
+        let mut level = 1;
+        for (i, class_level) in classes.iter().enumerate() {
+            let hit_die = class_level.class.hit_die();
+            let levels = match i {
+                0 => class_level.level.saturating_sub(1),
+                _ => class_level.level,
+            };
+            if use_average_dice {
+                if levels > 0 {
+                    let hp_per_level = Status::level_hp_increase(hit_die, con_modifier, true, rng)
+                        + bonus_per_level;
+                    pretty_print(
+                        &format!(
+                            "HP increases by {} for each further {} level.",
+                            hp_per_level, class_level.class
+                        ),
+                        BLUE,
+                        true,
+                    );
+                    maximum_hp += hp_per_level * levels as i32;
+                }
+            } else {
+                for _ in 0..levels {
+                    level += 1;
+                    maximum_hp += Status::level_hp_increase(hit_die, con_modifier, false, rng)
+                        + bonus_per_level;
+                    pretty_print(
+                        &format!("HP at Lv.{} ({}): {}", level, class_level.class, maximum_hp),
+                        BLUE,
+                        true,
+                    );
+                }
             }
-            hp_first_level + hp_to_add
-        };
+        }
+        if use_average_dice {
+            println!();
+        }
 
         self.maximum_hp = maximum_hp;
         self.current_hp = maximum_hp;
         self
     }
+
+    /// HP gained for a level past the first: the hit die's average or a roll,
+    /// plus the Constitution modifier (never less than 1).
+    pub fn level_hp_increase(
+        hit_die: Dice,
+        con_modifier: i8,
+        use_average_dice: bool,
+        rng: &mut GameRng,
+    ) -> i32 {
+        let increase = if use_average_dice {
+            hit_die.average() as i32 + con_modifier as i32
+        } else {
+            let roll = DiceExpression::from(hit_die)
+                .with_modifier(con_modifier as i32)
+                .roll(rng);
+            pretty_print(&format!("\nYou rolled {}", roll), BLUE, true);
+            roll.total
+        };
+        increase.max(1)
+    }
+
+    /// Raises both current and maximum HP, e.g. for a new level or the Tough feat.
+    pub fn increase_maximum_hp(&mut self, amount: i32) {
+        self.maximum_hp += amount;
+        self.current_hp += amount;
+    }
+
+    /// Restores HP up to the maximum, returning how much was regained. Any
+    /// healing at 0 HP brings the character back to consciousness.
+    pub fn heal(&mut self, amount: i32) -> i32 {
+        if self.dead {
+            return 0;
+        }
+        let before = self.current_hp;
+        self.current_hp = self
+            .current_hp
+            .saturating_add(amount)
+            .min(self.effective_maximum_hp());
+        if before == 0 && self.current_hp > 0 {
+            self.death_saves = DeathSaves::default();
+            self.conditions.retain(|c| {
+                c.condition != Condition::Unconscious || c.source.as_deref() != Some(DYING)
+            });
+        }
+        self.current_hp - before
+    }
+
+    /// Temporary HP don't stack, so the character keeps whichever amount is
+    /// higher. Returns whether the new temporary HP were kept.
+    pub fn gain_temporary_hp(&mut self, amount: i32) -> bool {
+        if amount <= self.temporary_hp {
+            return false;
+        }
+        self.temporary_hp = amount;
+        true
+    }
+
+    /// Takes damage already adjusted for resistances: temporary HP go first,
+    /// then HP down to 0. Damage left over at 0 HP that is at least the hit
+    /// point maximum kills outright. Dropping to 0 HP knocks the character
+    /// unconscious, and any damage after that is a death save failure, or two
+    /// from a critical hit.
+    pub fn take_damage(&mut self, amount: i32, critical: bool) -> DamageTaken {
+        let already_down = self.current_hp == 0 && !self.dead;
+        let temporary = amount.min(self.temporary_hp);
+        self.temporary_hp -= temporary;
+        let remaining = amount - temporary;
+        let lost = remaining.min(self.current_hp);
+        self.current_hp -= lost;
+        let instant_death = self.current_hp == 0 && remaining - lost >= self.effective_maximum_hp();
+        let mut death_save_failures = 0;
+        if instant_death {
+            self.dead = true;
+        } else if already_down && remaining > 0 {
+            death_save_failures = if critical { 2 } else { 1 };
+            self.fail_death_saves(death_save_failures);
+        } else if lost > 0 && self.current_hp == 0 {
+            self.death_saves = DeathSaves::default();
+            self.add_condition(ActiveCondition {
+                condition: Condition::Unconscious,
+                source: Some(String::from(DYING)),
+                rounds_remaining: None,
+            });
+        }
+        DamageTaken {
+            temporary,
+            lost,
+            instant_death,
+            death_save_failures,
+        }
+    }
+
+    /// At 0 HP, still alive and not yet stable.
+    pub fn is_dying(&self) -> bool {
+        self.current_hp == 0 && !self.dead && !self.death_saves.stable
+    }
+
+    /// Counts a d20 rolled with no modifier: 10 or higher succeeds, a
+    /// natural 1 is two failures and a natural 20 regains 1 HP.
+    pub fn death_saving_throw(&mut self, roll: &RollResult) -> DeathSaveOutcome {
+        if roll.is_critical() {
+            self.heal(1);
+            return DeathSaveOutcome::RegainedHp;
+        }
+        let failures = match (roll.is_fumble(), roll.total >= 10) {
+            (true, _) => 2,
+            (false, false) => 1,
+            (false, true) => 0,
+        };
+        if failures > 0 {
+            self.fail_death_saves(failures);
+            return match self.dead {
+                true => DeathSaveOutcome::Dead,
+                false => DeathSaveOutcome::Failure(failures),
+            };
+        }
+        self.death_saves.successes += 1;
+        match self.death_saves.successes >= 3 {
+            true => {
+                self.stabilize();
+                DeathSaveOutcome::Stable
+            }
+            false => DeathSaveOutcome::Success,
+        }
+    }
+
+    fn fail_death_saves(&mut self, count: u8) {
+        self.death_saves.stable = false;
+        self.death_saves.failures = (self.death_saves.failures + count).min(3);
+        if self.death_saves.failures == 3 {
+            self.dead = true;
+        }
+    }
+
+    /// Stops a dying character's death saves, e.g. with a Medicine check or
+    /// Spare the Dying. Returns false if they weren't dying.
+    pub fn stabilize(&mut self) -> bool {
+        if !self.is_dying() {
+            return false;
+        }
+        self.death_saves = DeathSaves {
+            stable: true,
+            ..DeathSaves::default()
+        };
+        true
+    }
+
+    pub fn armor_class(&self) -> i32 {
+        self.armor_class
+    }
+
+    pub fn initiative(&self) -> i32 {
+        self.initiative
+    }
+
+    pub fn current_hp(&self) -> i32 {
+        self.current_hp
+    }
+
+    pub fn is_raging(&self) -> bool {
+        self.rage_rounds > 0
+    }
+
+    /// Counts down a Rage at the start of a turn, returning `true` if it just ended.
+    pub fn tick_rage(&mut self) -> bool {
+        if self.rage_rounds == 0 {
+            return false;
+        }
+        self.rage_rounds -= 1;
+        self.rage_rounds == 0
+    }
 }
 
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct Character {
     pub name: String,
+    /// Total character level across all classes.
     pub level: u8,
     pub background: Background,
     pub race: Race,
-    pub class: Class,
+    pub classes: Vec<ClassLevel>,
     pub stats: [Stat; 6],
+    #[serde(default)]
+    pub stat_method: StatMethod,
+    #[serde(default)]
+    pub racial_increases: [u8; 6],
+    /// Points gained from Ability Score Improvements and feats since creation.
+    #[serde(default)]
+    pub ability_improvements: [u8; 6],
+    #[serde(default)]
+    pub skills: Vec<Skill>,
+    #[serde(default)]
+    pub expertise: Vec<Skill>,
+    #[serde(default)]
+    pub feats: Vec<Feat>,
+    #[serde(default)]
+    pub inventory: Inventory,
+    #[serde(default)]
+    pub purse: Purse,
     pub status: Status,
     pub gender: Gender,
 }
@@ -231,41 +583,64 @@ impl Character {
                 ),
             ]));
 
-            let subclass = match &self.class {
-                Class::Artificer(v) => v.to_string(),
-                Class::Barbarian(v) => v.to_string(),
-                Class::Bard(v) => v.to_string(),
-                Class::Cleric(v) => v.to_string(),
-                Class::Druid(v) => v.to_string(),
-                Class::Fighter(v) => v.to_string(),
-                Class::Monk(v) => v.to_string(),
-                Class::Paladin(v) => v.to_string(),
-                Class::Ranger(v) => v.to_string(),
-                Class::Rogue(v) => v.to_string(),
-                Class::Sorcerer(v) => v.to_string(),
-                Class::Warlock(v) => v.to_string(),
-                Class::Wizard(v) => v.to_string(),
-            };
-
+            let classes: Vec<String> = self.classes.iter().map(|c| c.to_string()).collect();
             table.add_row(Row::new(vec![
                 TableCell::new_with_alignment("Class", 1, Alignment::Center),
+                TableCell::new_with_alignment(classes.join(" / "), 11, Alignment::Left),
+            ]));
+
+            table.add_row(Row::new(vec![
+                TableCell::new_with_alignment("Level", 1, Alignment::Center),
+                TableCell::new_with_alignment(format!("{}", self.level), 11, Alignment::Left),
+            ]));
+
+            table.add_row(Row::new(vec![
+                TableCell::new_with_alignment("Proficiency", 1, Alignment::Center),
                 TableCell::new_with_alignment(
-                    format!("{} ({})", self.class, subclass),
+                    format_modifier(self.proficiency_bonus() as i32),
                     11,
                     Alignment::Left,
                 ),
             ]));
 
             table.add_row(Row::new(vec![
-                TableCell::new_with_alignment("Level", 1, Alignment::Center),
-                TableCell::new_with_alignment(format!("{}", self.level), 11, Alignment::Left),
+                TableCell::new_with_alignment("Hit Dice", 1, Alignment::Center),
+                TableCell::new_with_alignment(
+                    format!(
+                        "{} ({} left)",
+                        self.hit_dice(),
+                        format_hit_dice(&self.remaining_hit_dice())
+                    ),
+                    11,
+                    Alignment::Left,
+                ),
             ]));
 
+            if let Some(slots) = self.spell_slots() {
+                table.add_row(Row::new(vec![
+                    TableCell::new_with_alignment("Spell Slots", 1, Alignment::Center),
+                    TableCell::new_with_alignment(slots, 11, Alignment::Left),
+                ]));
+            }
+
             table.add_row(Row::new(vec![
                 TableCell::new_with_alignment("Background", 1, Alignment::Center),
                 TableCell::new_with_alignment(format!("{}", self.background), 11, Alignment::Left),
             ]));
 
+            if !self.feats.is_empty() {
+                let feats: Vec<String> = self.feats.iter().map(|f| f.to_string()).collect();
+                table.add_row(Row::new(vec![
+                    TableCell::new_with_alignment("Feats", 1, Alignment::Center),
+                    TableCell::new_with_alignment(feats.join(", "), 11, Alignment::Left),
+                ]));
+            }
+
+            table.add_row(Row::new(vec![
+                TableCell::new_with_alignment("Ability Scores", 1, Alignment::Center),
+                TableCell::new_with_alignment(format!("{}", self.stat_method), 11, Alignment::Left),
+            ]));
+
             table.add_row(Row::new(vec![TableCell::new_with_col_span("", 12)]));
         }
 
@@ -299,7 +674,19 @@ impl Character {
 
         table.add_row(Row::new(vec![
             TableCell::new_with_alignment(
-                format!("{}/{}", self.status.current_hp, self.status.maximum_hp),
+                match self.status.temporary_hp {
+                    0 => format!(
+                        "{}/{}",
+                        self.status.current_hp,
+                        self.status.effective_maximum_hp()
+                    ),
+                    temporary => format!(
+                        "{}/{} (+{} temp)",
+                        self.status.current_hp,
+                        self.status.effective_maximum_hp(),
+                        temporary
+                    ),
+                },
                 1,
                 Alignment::Center,
             ),
@@ -308,7 +695,18 @@ impl Character {
                 1,
                 Alignment::Center,
             ),
-            TableCell::new_with_alignment(format!("{}", self.status.speed), 1, Alignment::Center),
+            TableCell::new_with_alignment(
+                match verbose {
+                    true => format!("{}", self.status.effective_speed()),
+                    false => format!(
+                        "{} left / {}",
+                        self.status.movement,
+                        self.status.effective_speed()
+                    ),
+                },
+                1,
+                Alignment::Center,
+            ),
             TableCell::new_with_alignment(
                 format!("{}", self.status.initiative),
                 1,
@@ -324,13 +722,772 @@ impl Character {
             TableCell::new_with_alignment(self.get_chr().display(), 1, Alignment::Center),
         ]));
 
+        if verbose {
+            let mut scores_row = vec![
+                TableCell::new_with_alignment("Scores (base → final)", 5, Alignment::Right),
+                TableCell::new(""),
+            ];
+            for (i, stat) in self.stats.iter().enumerate() {
+                let base = stat
+                    .value()
+                    .saturating_sub(self.racial_increases[i] + self.ability_improvements[i]);
+                let score = match base == stat.value() {
+                    true => format!("{}", stat.value()),
+                    false => format!("{} → {}", base, stat.value()),
+                };
+                scores_row.push(TableCell::new_with_alignment(score, 1, Alignment::Center));
+            }
+            table.add_row(Row::new(scores_row));
+
+            let mut saves_row = vec![
+                TableCell::new_with_alignment("Saving Throws (* proficient)", 5, Alignment::Right),
+                TableCell::new(""),
+            ];
+            for ability in 0..self.stats.len() {
+                let mark = match self.is_proficient_in_save(ability) {
+                    true => "*",
+                    false => "",
+                };
+                saves_row.push(TableCell::new_with_alignment(
+                    format!(
+                        "{}{}",
+                        mark,
+                        format_modifier(self.save_modifier(ability) as i32)
+                    ),
+                    1,
+                    Alignment::Center,
+                ));
+            }
+            table.add_row(Row::new(saves_row));
+
+            table.add_row(Row::new(vec![TableCell::new_with_alignment(
+                "Skills (* proficient, ** expertise)",
+                12,
+                Alignment::Center,
+            )]));
+
+            let skills: Vec<TableCell> = Skill::iter()
+                .map(|skill| {
+                    let mark = if self.expertise.contains(&skill) {
+                        "**"
+                    } else if self.skills.contains(&skill) {
+                        "*"
+                    } else {
+                        ""
+                    };
+                    TableCell::new_with_alignment(
+                        format!(
+                            "{}{} ({}) {}",
+                            mark,
+                            skill,
+                            self.stats[skill.ability()].abbreviation(),
+                            format_modifier(self.skill_modifier(skill) as i32)
+                        ),
+                        4,
+                        Alignment::Left,
+                    )
+                })
+                .collect();
+            for row in skills.chunks(3) {
+                table.add_row(Row::new(row.to_vec()));
+            }
+        }
+
+        let mut conditions: Vec<String> = self
+            .status
+            .conditions
+            .iter()
+            .map(|c| c.to_string())
+            .collect();
+        if self.status.exhaustion > 0 {
+            conditions.push(format!("Exhaustion {}", self.status.exhaustion));
+        }
+        if self.status.is_raging() {
+            conditions.push(format!("Raging ({} rounds)", self.status.rage_rounds));
+        }
+        if self.status.dead {
+            conditions.insert(0, String::from("Dead"));
+        } else if self.status.is_dying() {
+            conditions.insert(
+                0,
+                format!("Dying (death saves: {})", self.status.death_saves),
+            );
+        } else if self.status.death_saves.stable && self.status.current_hp == 0 {
+            conditions.insert(0, String::from("Stable"));
+        }
+        if conditions.is_empty() {
+            conditions.push(String::from("None"));
+        }
+
+        let defenses: Vec<String> = DamageType::iter()
+            .flat_map(|damage_type| self.damage_defenses(damage_type))
+            .map(|defense| defense.to_string())
+            .collect();
+        if !defenses.is_empty() {
+            table.add_row(Row::new(vec![TableCell::new_with_alignment(
+                format!("Defenses: {}", defenses.join(", ")),
+                12,
+                Alignment::Left,
+            )]));
+        }
+
+        let weapons = self.weapon_summaries();
+        if !weapons.is_empty() {
+            table.add_row(Row::new(vec![TableCell::new_with_alignment(
+                format!("Weapons: {}", weapons.join(", ")),
+                12,
+                Alignment::Left,
+            )]));
+        }
+
+        let resources = self.resources();
+        if !resources.is_empty() {
+            table.add_row(Row::new(vec![TableCell::new_with_alignment(
+                format!("Resources: {}", resources.join(", ")),
+                12,
+                Alignment::Left,
+            )]));
+        }
+
         table.add_row(Row::new(vec![TableCell::new_with_alignment(
-            format!("Status Conditions: None"),
+            format!("Status Conditions: {}", conditions.join(", ")),
             12,
             Alignment::Left,
         )]));
 
         println!("{}", table.render());
+        if verbose {
+            self.display_inventory();
+        }
+    }
+
+    /// Re-derives AC, initiative and speed after ability scores, feats or
+    /// equipment change. Heavy armor without the Strength it needs costs 10
+    /// feet of speed, except for Dwarves.
+    pub fn recalculate_status(&mut self) {
+        let alert = match self.feats.contains(&Feat::Alert) {
+            true => 5,
+            false => 0,
+        };
+        let mobile = match self.feats.contains(&Feat::Mobile) {
+            true => 10,
+            false => 0,
+        };
+        let armor = self.inventory.armor();
+        self.status.armor_class = calculate_ac(
+            &self.stats,
+            &self.classes,
+            armor,
+            self.inventory.has_shield(),
+            self.status.mage_armor,
+        );
+        self.status.initiative = self.get_dex().get_modifier() as i32 + alert;
+        let too_weak = armor
+            .and_then(|armor| armor.strength_requirement())
+            .is_some_and(|strength| self.get_str().value() < strength);
+        let heavy_armor = match too_weak && !matches!(self.race, Race::Dwarf(_)) {
+            true => 10,
+            false => 0,
+        };
+        self.status.speed = self
+            .encumbrance()
+            .speed((calculate_speed(&self.race) + mobile).saturating_sub(heavy_armor));
+    }
+
+    /// Pounds the character can carry: 15 × their Strength score.
+    pub fn carrying_capacity(&self) -> f32 {
+        15.0 * self.get_str().value() as f32
+    }
+
+    pub fn encumbrance(&self) -> Encumbrance {
+        Encumbrance::new(
+            self.inventory.total_weight(),
+            self.get_str().value(),
+            self.inventory.variant_encumbrance,
+        )
+    }
+
+    pub fn display_inventory(&self) {
+        let mut table = Table::new();
+        table.style = TableStyle::extended();
+        table.add_row(Row::new(vec![TableCell::new_with_alignment(
+            "Inventory",
+            5,
+            Alignment::Center,
+        )]));
+        table.add_row(Row::new(vec![
+            TableCell::new_with_alignment("Item", 1, Alignment::Left),
+            TableCell::new_with_alignment("Qty", 1, Alignment::Left),
+            TableCell::new_with_alignment("Weight", 1, Alignment::Left),
+            TableCell::new_with_alignment("Equipped", 1, Alignment::Left),
+            TableCell::new_with_alignment("Notes", 1, Alignment::Left),
+        ]));
+        for item in &self.inventory.items {
+            table.add_row(Row::new(vec![
+                TableCell::new_with_alignment(&item.name, 1, Alignment::Left),
+                TableCell::new_with_alignment(item.quantity, 1, Alignment::Right),
+                TableCell::new_with_alignment(
+                    format!("{} lb.", item.total_weight()),
+                    1,
+                    Alignment::Right,
+                ),
+                TableCell::new_with_alignment(
+                    item.equipped.map(|s| s.to_string()).unwrap_or_default(),
+                    1,
+                    Alignment::Left,
+                ),
+                TableCell::new_with_alignment(&item.notes, 1, Alignment::Left),
+            ]));
+        }
+        table.add_row(Row::new(vec![TableCell::new_with_alignment(
+            format!("Coins: {}", self.purse),
+            5,
+            Alignment::Left,
+        )]));
+        let encumbrance = match self.encumbrance() {
+            Encumbrance::Unencumbered => String::new(),
+            encumbrance => format!(", {} (speed {} ft.)", encumbrance, self.status.speed),
+        };
+        table.add_row(Row::new(vec![TableCell::new_with_alignment(
+            format!(
+                "Carrying {} / {} lb.{}{}",
+                self.inventory.total_weight(),
+                self.carrying_capacity(),
+                encumbrance,
+                match self.inventory.variant_encumbrance {
+                    true => " (variant encumbrance)",
+                    false => "",
+                }
+            ),
+            5,
+            Alignment::Left,
+        )]));
+        println!("{}", table.render());
+    }
+
+    /// Levels held in `class`, ignoring subclass; 0 if the character has none.
+    pub fn class_level(&self, class: &Class) -> u8 {
+        self.classes
+            .iter()
+            .filter(|c| c.class.is_same_class(class))
+            .map(|c| c.level)
+            .sum()
+    }
+
+    /// How many hit dice of each size the character's levels give, largest first.
+    fn hit_dice_totals(&self) -> Vec<(Dice, u8)> {
+        let mut dice: Vec<(Dice, u8)> = Vec::new();
+        for class_level in &self.classes {
+            let die = class_level.class.hit_die();
+            match dice.iter_mut().find(|(d, _)| *d == die) {
+                Some((_, count)) => *count += class_level.level,
+                None => dice.push((die, class_level.level)),
+            }
+        }
+        dice.sort_by_key(|(die, _)| std::cmp::Reverse(die.sides()));
+        dice
+    }
+
+    /// Hit dice grouped by size, largest first, e.g. "2d10 + 5d6".
+    pub fn hit_dice(&self) -> String {
+        format_hit_dice(&self.hit_dice_totals())
+    }
+
+    /// Hit dice not yet spent, largest first.
+    pub fn remaining_hit_dice(&self) -> Vec<(Dice, u8)> {
+        self.hit_dice_totals()
+            .into_iter()
+            .map(|(die, count)| {
+                let used = self.status.hit_dice_used.get(&die).copied().unwrap_or(0);
+                (die, count.saturating_sub(used))
+            })
+            .filter(|(_, count)| *count > 0)
+            .collect()
+    }
+
+    /// Spends a hit die on a short rest, regaining its roll plus the
+    /// Constitution modifier in HP, or at least twice the modifier (minimum 2)
+    /// with Durable. Returns the roll and the HP regained, or `None` if no
+    /// dice of that size are left.
+    pub fn spend_hit_die(&mut self, die: Dice, rng: &mut GameRng) -> Option<(RollResult, i32)> {
+        if !self.remaining_hit_dice().iter().any(|(d, _)| *d == die) {
+            return None;
+        }
+        *self.status.hit_dice_used.entry(die).or_insert(0) += 1;
+        let con = self.get_con().get_modifier() as i32;
+        let roll = die.expression(1).with_modifier(con).roll(rng);
+        let minimum = match self.feats.contains(&Feat::Durable) {
+            true => (2 * con).max(2),
+            false => 0,
+        };
+        let healed = self.status.heal(roll.total.max(minimum));
+        Some((roll, healed))
+    }
+
+    /// Recovers the features that recharge on a short rest, including Pact
+    /// Magic slots. Spending hit dice is up to the player.
+    pub fn short_rest(&mut self) {
+        self.recover_resources(Recharge::ShortRest);
+        self.status.pact_slots_used = 0;
+        self.status.rage_rounds = 0;
+    }
+
+    /// Restores all HP, features and spell slots, lowers exhaustion by one
+    /// and recovers spent hit dice up to half the character's total (at least
+    /// one), largest first. Returns the hit dice recovered, or `None` if the
+    /// character started the rest at 0 HP and gains nothing from it.
+    pub fn long_rest(&mut self) -> Option<u8> {
+        if self.status.current_hp == 0 {
+            return None;
+        }
+        self.short_rest();
+        self.recover_resources(Recharge::LongRest);
+        self.status.spell_slots_used = [0; 9];
+        self.status
+            .set_exhaustion(self.status.exhaustion.saturating_sub(1));
+        self.status.current_hp = self.status.effective_maximum_hp();
+
+        let mut recoverable = (self.level / 2).max(1);
+        let mut recovered = 0;
+        for (die, _) in self.hit_dice_totals() {
+            if let Some(used) = self.status.hit_dice_used.get_mut(&die) {
+                let amount = (*used).min(recoverable);
+                *used -= amount;
+                recoverable -= amount;
+                recovered += amount;
+            }
+        }
+        self.status.hit_dice_used.retain(|_, used| *used > 0);
+        Some(recovered)
+    }
+
+    /// Clears spent uses of every resource that comes back on `rest`.
+    fn recover_resources(&mut self, rest: Recharge) {
+        let recovered: Vec<Resource> = Resource::iter()
+            .filter(|resource| rest == Recharge::LongRest || resource.recharge(self) == rest)
+            .collect();
+        for resource in recovered {
+            self.status.resources_used.remove(&resource);
+        }
+    }
+
+    /// Remaining / total spell slots by level plus any Pact Magic slots, or
+    /// `None` for a non-caster.
+    fn spell_slots(&self) -> Option<String> {
+        let remaining = self.remaining_spell_slots();
+        let mut slots: Vec<String> = spell_slots(&self.classes)
+            .iter()
+            .zip(remaining.iter())
+            .zip(1..)
+            .filter(|((total, _), _)| **total > 0)
+            .map(|((total, left), level)| format!("{}: {}/{}", ordinal(level), left, total))
+            .collect();
+        if let Some((total, level)) = pact_slots(&self.classes) {
+            slots.push(format!(
+                "Pact ({}): {}/{}",
+                ordinal(level),
+                total.saturating_sub(self.status.pact_slots_used),
+                total
+            ));
+        }
+        match slots.is_empty() {
+            true => None,
+            false => Some(slots.join(", ")),
+        }
+    }
+
+    pub fn remaining_spell_slots(&self) -> [u8; 9] {
+        let mut slots = spell_slots(&self.classes);
+        for (slot, used) in slots.iter_mut().zip(self.status.spell_slots_used.iter()) {
+            *slot = slot.saturating_sub(*used);
+        }
+        slots
+    }
+
+    /// Remaining Pact Magic slots and their spell level.
+    pub fn remaining_pact_slots(&self) -> Option<(u8, u8)> {
+        pact_slots(&self.classes)
+            .map(|(total, level)| (total.saturating_sub(self.status.pact_slots_used), level))
+    }
+
+    /// Expends the lowest remaining spell slot of at least `min_level`, falling
+    /// back to a Pact Magic slot if `pact` allows it. Returns the slot's level.
+    pub fn expend_spell_slot(&mut self, min_level: u8, pact: bool) -> Option<u8> {
+        let remaining = self.remaining_spell_slots();
+        if let Some(i) = (min_level.max(1) as usize - 1..9).find(|i| remaining[*i] > 0) {
+            self.status.spell_slots_used[i] += 1;
+            return Some(i as u8 + 1);
+        }
+        match self.remaining_pact_slots() {
+            Some((left, level)) if pact && left > 0 && level >= min_level => {
+                self.status.pact_slots_used += 1;
+                Some(level)
+            }
+            _ => None,
+        }
+    }
+
+    /// Classes that cast spells, with the index of their spellcasting ability.
+    pub fn spellcasting_classes(&self) -> Vec<(&Class, usize)> {
+        self.classes
+            .iter()
+            .filter_map(|c| spellcasting_ability(&c.class).map(|ability| (&c.class, ability)))
+            .collect()
+    }
+
+    pub fn spell_attack_bonus(&self, ability: usize) -> i8 {
+        self.stats[ability].get_modifier() + self.proficiency_bonus()
+    }
+
+    pub fn spell_save_dc(&self, ability: usize) -> i8 {
+        8 + self.spell_attack_bonus(ability)
+    }
+
+    /// Every resistance, immunity and vulnerability the character has to
+    /// `damage_type`, from their race, Rage, conditions and items.
+    pub fn damage_defenses(&self, damage_type: DamageType) -> Vec<DamageDefense> {
+        let resistance = |source: &str| DamageDefense {
+            defense: Defense::Resistance,
+            damage_type,
+            source: String::from(source),
+        };
+        let mut defenses = vec![];
+        if let Some(source) = self.race.damage_resistance(damage_type) {
+            defenses.push(resistance(source));
+        }
+        if self.status.is_raging() && damage_type.is_physical() {
+            defenses.push(resistance("Rage"));
+        }
+        if self
+            .status
+            .conditions
+            .iter()
+            .any(|c| c.condition == Condition::Petrified)
+        {
+            defenses.push(resistance("Petrified"));
+        }
+        defenses.extend(
+            self.status
+                .damage_defenses
+                .iter()
+                .filter(|d| d.damage_type == damage_type)
+                .cloned(),
+        );
+        defenses
+    }
+
+    pub fn resource_remaining(&self, resource: Resource) -> u8 {
+        let maximum = resource.maximum(self);
+        if maximum == UNLIMITED {
+            return UNLIMITED;
+        }
+        let used = self
+            .status
+            .resources_used
+            .get(&resource)
+            .copied()
+            .unwrap_or(0);
+        maximum.saturating_sub(used)
+    }
+
+    /// Spends uses of a limited feature, returning `false` if not enough are left.
+    pub fn spend_resource(&mut self, resource: Resource, uses: u8) -> bool {
+        if self.resource_remaining(resource) < uses {
+            return false;
+        }
+        *self.status.resources_used.entry(resource).or_insert(0) += uses;
+        true
+    }
+
+    /// Remaining / maximum uses of every limited feature the character has.
+    fn resources(&self) -> Vec<String> {
+        Resource::iter()
+            .filter(|resource| resource.maximum(self) > 0)
+            .map(|resource| match resource.maximum(self) {
+                UNLIMITED => format!("{}: unlimited", resource),
+                maximum => format!(
+                    "{}: {}/{}",
+                    resource,
+                    self.resource_remaining(resource),
+                    maximum
+                ),
+            })
+            .collect()
+    }
+
+    /// The Monk's Martial Arts die, which grows with Monk level.
+    pub fn martial_arts_die(&self) -> Option<Dice> {
+        match self.class_level(&Class::Monk(None)) {
+            0 => None,
+            1..=4 => Some(Dice::D4),
+            5..=10 => Some(Dice::D6),
+            11..=16 => Some(Dice::D8),
+            _ => Some(Dice::D10),
+        }
+    }
+
+    /// Attacks made with each Attack action. Extra Attack from different
+    /// classes doesn't stack.
+    pub fn attacks_per_action(&self) -> u8 {
+        self.classes
+            .iter()
+            .map(|c| match (&c.class, c.level) {
+                (Class::Fighter(_), 20) => 4,
+                (Class::Fighter(_), 11..) => 3,
+                (
+                    Class::Barbarian(_)
+                    | Class::Fighter(_)
+                    | Class::Monk(_)
+                    | Class::Paladin(_)
+                    | Class::Ranger(_),
+                    5..,
+                ) => 2,
+                _ => 1,
+            })
+            .max()
+            .unwrap_or(1)
+    }
+
+    pub fn is_proficient_with(&self, weapon: Weapon) -> bool {
+        self.race.weapon_training(weapon).is_some()
+            || self
+                .classes
+                .iter()
+                .enumerate()
+                .any(|(i, c)| c.class.is_proficient_with(weapon, i > 0))
+    }
+
+    /// The attack bonus and damage of an attack with `weapon`. Ranged weapons
+    /// use Dexterity and melee weapons Strength, or the better of the two for
+    /// finesse weapons and a Monk's Monk weapons. Off-hand attacks only add a
+    /// negative modifier to damage.
+    pub fn weapon_attack(&self, weapon: Weapon, grip: Grip) -> (i32, Option<DiceExpression>) {
+        let strength = self.get_str().get_modifier() as i32;
+        let dexterity = self.get_dex().get_modifier() as i32;
+        let martial_arts = self.martial_arts_die();
+        let modifier = if weapon.is_ranged() {
+            dexterity
+        } else if weapon.has_property(WeaponProperty::Finesse)
+            || (martial_arts.is_some() && weapon.is_monk_weapon())
+        {
+            strength.max(dexterity)
+        } else {
+            strength
+        };
+        let proficiency = match self.is_proficient_with(weapon) {
+            true => self.proficiency_bonus() as i32,
+            false => 0,
+        };
+        let rage_damage = match !weapon.is_ranged() && modifier == strength {
+            true => self.rage_damage(),
+            false => 0,
+        };
+        // The off hand only adds a penalty, but Rage still adds to its damage.
+        let damage_modifier = match grip {
+            Grip::OffHand => modifier.min(0),
+            _ => modifier,
+        } + rage_damage;
+        let damage = weapon
+            .damage(grip, martial_arts)
+            .map(|damage| damage.with_modifier(damage_modifier));
+        (modifier + proficiency, damage)
+    }
+
+    /// Each carried weapon with its attack bonus and damage.
+    fn weapon_summaries(&self) -> Vec<String> {
+        self.inventory
+            .weapons()
+            .iter()
+            .map(|weapon| {
+                let (attack_bonus, damage) = self.weapon_attack(*weapon, Grip::OneHanded);
+                match damage {
+                    Some(damage) => {
+                        format!("{} {} ({})", weapon, format_modifier(attack_bonus), damage)
+                    }
+                    None => format!("{} {}", weapon, format_modifier(attack_bonus)),
+                }
+            })
+            .collect()
+    }
+
+    /// Extra damage on Strength melee attacks while raging.
+    pub fn rage_damage(&self) -> i32 {
+        match (
+            self.status.is_raging(),
+            self.class_level(&Class::Barbarian(None)),
+        ) {
+            (false, _) | (_, 0) => 0,
+            (true, 1..=8) => 2,
+            (true, 9..=15) => 3,
+            (true, _) => 4,
+        }
+    }
+
+    pub fn bardic_inspiration_die(&self) -> Option<Dice> {
+        match self.class_level(&Class::Bard(None)) {
+            0 => None,
+            1..=4 => Some(Dice::D6),
+            5..=9 => Some(Dice::D8),
+            10..=14 => Some(Dice::D10),
+            _ => Some(Dice::D12),
+        }
+    }
+
+    /// Extra maximum HP gained at every level on top of the hit die, from
+    /// Dwarven Toughness and the Tough feat.
+    pub fn hp_bonus_per_level(&self) -> i32 {
+        let tough = match self.feats.contains(&Feat::Tough) {
+            true => 2,
+            false => 0,
+        };
+        self.race.hp_bonus_per_level() + tough
+    }
+
+    /// The lowest and highest maximum HP possible for the character's classes,
+    /// Constitution and per-level bonuses, or `None` without any classes. Older
+    /// versions gave no per-level bonuses and no minimum of 1 HP per level, so
+    /// the lowest follows their formula to keep low rolls from those saves.
+    fn hit_point_range(&self) -> Option<(i32, i32)> {
+        let con_modifier = self.get_con().get_modifier() as i32;
+        let bonus = self.hp_bonus_per_level();
+        let first_sides = self.classes.first()?.class.hit_die().sides() as i32;
+        let mut lowest = first_sides + con_modifier;
+        let mut highest = first_sides + con_modifier + bonus;
+        for (i, class_level) in self.classes.iter().enumerate() {
+            let sides = class_level.class.hit_die().sides() as i32;
+            let levels = match i {
+                0 => class_level.level.saturating_sub(1),
+                _ => class_level.level,
+            } as i32;
+            lowest += levels * (1 + con_modifier);
+            highest += levels * ((sides + con_modifier).max(1) + bonus);
+        }
+        Some((lowest, highest))
+    }
+
+    /// Older versions stored HP in a single byte, so high-level characters
+    /// could be saved with a wrapped-around maximum. A maximum that the
+    /// character's levels can't produce is recalculated with average hit dice.
+    /// Returns whether the HP were repaired.
+    pub fn repair_hit_points(&mut self) -> bool {
+        let (lowest, highest) = match self.hit_point_range() {
+            Some(range) => range,
+            None => return false,
+        };
+        if (lowest..=highest).contains(&self.status.maximum_hp) {
+            return false;
+        }
+        let con_modifier = self.get_con().get_modifier();
+        let bonus = self.hp_bonus_per_level();
+        let mut maximum_hp =
+            self.classes[0].class.hit_die().sides() as i32 + con_modifier as i32 + bonus;
+        for (i, class_level) in self.classes.iter().enumerate() {
+            let hit_die = class_level.class.hit_die();
+            let levels = match i {
+                0 => class_level.level.saturating_sub(1),
+                _ => class_level.level,
+            } as i32;
+            let per_level = (hit_die.average() as i32 + con_modifier as i32).max(1) + bonus;
+            maximum_hp += levels * per_level;
+        }
+        self.status.maximum_hp = maximum_hp;
+        self.status.current_hp = self.status.current_hp.clamp(0, maximum_hp);
+        true
+    }
+
+    pub fn gain_feat(&mut self, feat: Feat) {
+        if self.feats.contains(&feat) {
+            return;
+        }
+        self.feats.push(feat);
+        if feat == Feat::Tough {
+            self.status.increase_maximum_hp(2 * self.level as i32);
+        }
+        self.recalculate_status();
+    }
+
+    /// Raises an ability score (capped at 20). A higher Constitution modifier
+    /// raises maximum HP retroactively for every level.
+    pub fn improve_ability(&mut self, ability: usize, amount: u8) {
+        let old_con_modifier = self.get_con().get_modifier();
+        let old_score = self.stats[ability].value();
+        self.stats[ability] = self.stats[ability].increase(amount);
+        self.ability_improvements[ability] += self.stats[ability].value() - old_score;
+        let con_gain = self.get_con().get_modifier() - old_con_modifier;
+        if con_gain != 0 {
+            self.status
+                .increase_maximum_hp(con_gain as i32 * self.level as i32);
+        }
+        self.recalculate_status();
+    }
+
+    pub fn proficiency_bonus(&self) -> i8 {
+        proficiency_bonus(self.level)
+    }
+
+    /// Modifier for an ability check that doesn't use a proficient skill,
+    /// including a Bard's Jack of All Trades.
+    pub fn ability_check_modifier(&self, ability: usize) -> i8 {
+        let jack_of_all_trades = match self
+            .classes
+            .iter()
+            .any(|c| c.class.has_jack_of_all_trades(c.level))
+        {
+            true => self.proficiency_bonus() / 2,
+            false => 0,
+        };
+        self.stats[ability].get_modifier() + jack_of_all_trades
+    }
+
+    pub fn skill_modifier(&self, skill: Skill) -> i8 {
+        let ability = self.stats[skill.ability()].get_modifier();
+        if self.expertise.contains(&skill) {
+            ability + self.proficiency_bonus() * 2
+        } else if self.skills.contains(&skill) {
+            ability + self.proficiency_bonus()
+        } else {
+            self.ability_check_modifier(skill.ability())
+        }
+    }
+
+    /// Only the first class grants its 1st-level saving throws; later class
+    /// features like Slippery Mind count from any class.
+    pub fn is_proficient_in_save(&self, ability: usize) -> bool {
+        self.classes.iter().enumerate().any(|(i, c)| {
+            let saves = match i {
+                0 => c.class.saving_throws(c.level),
+                _ => c.class.feature_saving_throws(c.level),
+            };
+            saves.contains(&ability)
+        })
+    }
+
+    pub fn save_modifier(&self, ability: usize) -> i8 {
+        let proficiency = match self.is_proficient_in_save(ability) {
+            true => self.proficiency_bonus(),
+            false => 0,
+        };
+        let aura_of_protection = match self.class_level(&Class::Paladin(None)) >= 6 {
+            true => self.get_chr().get_modifier().max(1),
+            false => 0,
+        };
+        self.stats[ability].get_modifier() + proficiency + aura_of_protection
+    }
+
+    /// Racial and class features that give advantage on this saving throw.
+    pub fn save_advantages(&self, ability: usize, against: SaveAgainst) -> Vec<String> {
+        let mut advantages = Vec::new();
+        if let Some(trait_name) = self.race.save_advantage(ability, against) {
+            advantages.push(trait_name.to_owned());
+        }
+        let can_sense = !self.status.conditions.iter().any(|c| {
+            matches!(c.condition, Condition::Blinded | Condition::Deafened)
+                || c.condition.incapacitates()
+        });
+        if self.class_level(&Class::Barbarian(None)) >= 2 && ability == 1 && can_sense {
+            advantages.push(String::from("Danger Sense"));
+        }
+        advantages
     }
 
     pub fn get_str(&self) -> &Stat {
@@ -352,3 +1509,293 @@ impl Character {
         &self.stats[5]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::inventory::{Item, Slot};
+    use crate::data::races::{Dwarf, Human};
+    use crate::data::weapons::{Grip, Weapon};
+    use crate::state::play_object::legacy_save;
+
+    fn status(speed: u16) -> Status {
+        let mut status: Status = serde_yaml::from_str(&format!(
+            "armor_class: 12\nblessed: false\ninitiative: 2\ncurrent_hp: 10\nmaximum_hp: 10\nspeed: {}",
+            speed
+        ))
+        .unwrap();
+        status.start_movement();
+        status
+    }
+
+    #[test]
+    fn movement_is_spent_from_a_budget() {
+        let mut status = status(30);
+        assert_eq!(status.spend_movement(10, false), Some(10));
+        assert_eq!(status.spend_movement(10, true), Some(20));
+        assert_eq!(status.spend_movement(5, false), None);
+
+        status.dash();
+        assert_eq!(status.movement, 30);
+
+        status.add_condition(ActiveCondition {
+            condition: Condition::Prone,
+            source: None,
+            rounds_remaining: None,
+        });
+        assert!(status.stand_up());
+        assert_eq!(status.movement, 15);
+        assert!(status.conditions.is_empty());
+    }
+
+    #[test]
+    fn damage_spends_temporary_hp_first() {
+        let mut status = status(30);
+        assert!(status.gain_temporary_hp(5));
+        assert!(!status.gain_temporary_hp(3));
+
+        let taken = status.take_damage(8, false);
+        assert_eq!((taken.temporary, taken.lost), (5, 3));
+        assert_eq!(status.current_hp(), 7);
+
+        assert!(!status.take_damage(16, false).instant_death);
+        assert_eq!(status.current_hp(), 0);
+        assert!(status.take_damage(10, false).instant_death);
+        assert_eq!(status.heal(5), 0);
+    }
+
+    fn d20(value: u16) -> RollResult {
+        RollResult {
+            expression: DiceExpression::from(Dice::D20),
+            dice: vec![crate::data::dice::DieRoll {
+                sides: 20,
+                value,
+                kept: true,
+                negative: false,
+            }],
+            modifier: 0,
+            total: value as i32,
+        }
+    }
+
+    #[test]
+    fn dying_characters_make_death_saves() {
+        let mut status = status(30);
+        status.take_damage(10, false);
+        assert!(status.is_dying());
+        assert!(status.is_incapacitated());
+
+        assert_eq!(
+            status.death_saving_throw(&d20(12)),
+            DeathSaveOutcome::Success
+        );
+        assert_eq!(
+            status.death_saving_throw(&d20(1)),
+            DeathSaveOutcome::Failure(2)
+        );
+        // The tally is kept in the save file.
+        let saved = serde_yaml::to_string(&status).unwrap();
+        let mut status: Status = serde_yaml::from_str(&saved).unwrap();
+        assert_eq!(status.death_saves.successes, 1);
+        assert_eq!(status.death_saves.failures, 2);
+        status.take_damage(3, false);
+        assert!(status.dead);
+        assert_eq!(status.death_saves.failures, 3);
+
+        // A natural 20 wakes the character up with 1 HP.
+        let mut status = self::status(30);
+        status.take_damage(10, false);
+        assert_eq!(
+            status.death_saving_throw(&d20(20)),
+            DeathSaveOutcome::RegainedHp
+        );
+        assert_eq!(status.current_hp(), 1);
+        assert!(!status.is_incapacitated());
+
+        // Three successes, or a helping hand, make them stable.
+        status.take_damage(1, false);
+        for _ in 0..2 {
+            status.death_saving_throw(&d20(10));
+        }
+        assert_eq!(
+            status.death_saving_throw(&d20(15)),
+            DeathSaveOutcome::Stable
+        );
+        assert!(!status.is_dying() && !status.stabilize());
+        status.take_damage(1, true);
+        assert_eq!(status.death_saves.failures, 2);
+        assert!(status.stabilize());
+    }
+
+    #[test]
+    fn high_level_hit_points_fit() {
+        let mut rng = GameRng::new(Some(7));
+        let barbarian = [ClassLevel {
+            class: Class::Barbarian(None),
+            level: 20,
+        }];
+        let average = status(30).calculate_hp(&barbarian, 5, 0, true, &mut rng);
+        assert_eq!(average.maximum_hp, 12 + 5 + 19 * (7 + 5));
+
+        // A Tough Hill Dwarf gains 3 more HP at every level.
+        let tough = status(30).calculate_hp(&barbarian, 5, 3, true, &mut rng);
+        assert_eq!(tough.maximum_hp, 245 + 20 * 3);
+    }
+
+    #[test]
+    fn only_impossible_hit_points_are_repaired() {
+        // Monk 8 with Con 12: 9 HP at 1st level, then 2 to 9 per level.
+        let mut character = legacy_save().character;
+        assert!(!character.repair_hit_points());
+        assert_eq!(character.status.maximum_hp, 51);
+
+        // Older versions had no Hill Dwarf bonus, so low rolls stay valid.
+        character.race = Race::Dwarf(Dwarf::HillDwarf);
+        character.status.maximum_hp = 23;
+        assert!(!character.repair_hit_points());
+        assert_eq!(character.status.maximum_hp, 23);
+
+        // A maximum that wrapped around is recalculated with average dice.
+        character.race = Race::Human(Human::Variant);
+        character.status.maximum_hp = -100;
+        assert!(character.repair_hit_points());
+        assert_eq!(character.status.maximum_hp, 9 + 7 * (5 + 1));
+        assert_eq!(character.status.current_hp, 51);
+
+        // Class entries without levels don't break the check.
+        character.classes.push(ClassLevel {
+            class: Class::Wizard(None),
+            level: 0,
+        });
+        assert!(!character.repair_hit_points());
+        character.classes.clear();
+        assert!(!character.repair_hit_points());
+    }
+
+    #[test]
+    fn level_hit_points_are_at_least_1() {
+        let mut rng = GameRng::new(Some(7));
+        assert_eq!(Status::level_hp_increase(Dice::D12, 2, true, &mut rng), 9);
+        assert_eq!(Status::level_hp_increase(Dice::D6, -4, true, &mut rng), 1);
+        for _ in 0..20 {
+            let rolled = Status::level_hp_increase(Dice::D8, -3, false, &mut rng);
+            assert!((1..=5).contains(&rolled));
+        }
+    }
+
+    #[test]
+    fn ability_improvements_stop_at_20_and_raise_hp_for_every_level() {
+        let mut character = legacy_save().character;
+        // Dex 16 only has room for 4 more.
+        character.improve_ability(1, 6);
+        assert_eq!(character.get_dex().value(), 20);
+        assert_eq!(character.ability_improvements[1], 4);
+        character.improve_ability(1, 2);
+        assert_eq!(character.ability_improvements[1], 4);
+
+        // Con 12 to 13 keeps the modifier; 14 adds 1 HP for each of 8 levels.
+        character.improve_ability(2, 1);
+        assert_eq!(character.status.maximum_hp, 51);
+        character.improve_ability(2, 1);
+        assert_eq!(character.get_con().get_modifier(), 2);
+        assert_eq!(character.status.maximum_hp, 59);
+        assert_eq!(character.status.current_hp, 59);
+    }
+
+    #[test]
+    fn rests_recover_hit_dice_and_resources() {
+        let mut character = legacy_save().character;
+        let mut rng = GameRng::new(Some(5));
+        assert_eq!(character.remaining_hit_dice(), vec![(Dice::D8, 8)]);
+
+        for _ in 0..5 {
+            assert!(character.spend_hit_die(Dice::D8, &mut rng).is_some());
+        }
+        assert!(character.spend_hit_die(Dice::D10, &mut rng).is_none());
+        assert!(character.spend_resource(Resource::Ki, 3));
+        character.short_rest();
+        assert_eq!(character.resource_remaining(Resource::Ki), 8);
+
+        let saved = serde_yaml::to_string(&character).unwrap();
+        let mut character: Character = serde_yaml::from_str(&saved).unwrap();
+        assert_eq!(character.remaining_hit_dice(), vec![(Dice::D8, 3)]);
+
+        // A long rest gives back up to half the character's hit dice.
+        assert_eq!(character.long_rest(), Some(4));
+        assert_eq!(character.remaining_hit_dice(), vec![(Dice::D8, 7)]);
+    }
+
+    #[test]
+    fn durable_sets_a_floor_on_hit_dice() {
+        let mut character = legacy_save().character;
+        character.stats[2] = Stat::Con(16);
+        character.feats.push(Feat::Durable);
+        let mut rng = GameRng::new(Some(5));
+        for _ in 0..8 {
+            character.status.take_damage(20, false);
+            let (_, healed) = character.spend_hit_die(Dice::D8, &mut rng).unwrap();
+            // Twice the +3 Constitution modifier.
+            assert!(healed >= 6);
+        }
+    }
+
+    #[test]
+    fn monks_attack_with_martial_arts() {
+        let mut character = legacy_save().character;
+        assert_eq!(character.attacks_per_action(), 2);
+
+        // Dexterity 16 and a +3 proficiency bonus.
+        let (attack_bonus, damage) = character.weapon_attack(Weapon::Quarterstaff, Grip::OneHanded);
+        assert_eq!(attack_bonus, 6);
+        assert_eq!(damage.unwrap().to_string(), "1d6+3 bludgeoning");
+
+        // Not a Monk weapon, and not proficient: Strength 10 and no bonus.
+        let (attack_bonus, damage) = character.weapon_attack(Weapon::Longsword, Grip::TwoHanded);
+        assert_eq!(attack_bonus, 0);
+        assert_eq!(damage.unwrap().to_string(), "1d10 slashing");
+
+        // Rage adds to Strength attacks in either hand.
+        character.stats[0] = Stat::Str(18);
+        character.classes.push(ClassLevel {
+            class: Class::Barbarian(None),
+            level: 1,
+        });
+        character.status.rage_rounds = 10;
+        let (_, damage) = character.weapon_attack(Weapon::Handaxe, Grip::OneHanded);
+        assert_eq!(damage.unwrap().to_string(), "1d6+6 slashing");
+        let (_, damage) = character.weapon_attack(Weapon::Handaxe, Grip::OffHand);
+        assert_eq!(damage.unwrap().to_string(), "1d6+2 slashing");
+    }
+
+    #[test]
+    fn armor_class_follows_equipment() {
+        let mut character = legacy_save().character;
+        let speed = character.status.speed;
+        // Unarmored Defense: 10 + Dex 3 + Wis 3.
+        assert_eq!(character.status.armor_class(), 16);
+
+        // A Monk loses Unarmored Defense with a shield.
+        character.inventory.add(Item::shield());
+        character.inventory.equip(0, Slot::Shield).unwrap();
+        character.recalculate_status();
+        assert_eq!(character.status.armor_class(), 15);
+        character.status.mage_armor = true;
+        character.recalculate_status();
+        assert_eq!(character.status.armor_class(), 18);
+
+        // Chain Mail ignores Dex and needs Strength 13.
+        character.inventory.add(Item::from(Armor::ChainMail));
+        character.inventory.equip(1, Slot::Armor).unwrap();
+        character.recalculate_status();
+        assert_eq!(character.status.armor_class(), 18);
+        assert_eq!(character.status.speed, speed - 10);
+
+        // Medium armor caps Dex at +2.
+        character.inventory.unequip(0);
+        character.inventory.add(Item::from(Armor::HalfPlate));
+        character.inventory.equip(2, Slot::Armor).unwrap();
+        character.recalculate_status();
+        assert_eq!(character.status.armor_class(), 17);
+        assert_eq!(character.status.speed, speed);
+    }
+}
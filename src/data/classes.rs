@@ -1,26 +1,34 @@
+use std::fmt;
+
 use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
 use strum_macros::{Display, EnumIter, EnumString};
 
+use super::dice::Dice;
+use super::rng::GameRng;
 use super::utils::{choose_value, Choosable};
 
+use super::skills::Skill;
+use super::stats::Stat;
 use super::utils::StringJoin;
+use super::weapons::{Weapon, WeaponCategory};
 
 #[derive(Debug, PartialEq, Serialize, Deserialize, EnumIter, EnumString, Display)]
 #[strum(ascii_case_insensitive)]
 pub enum Class {
-    Artificer(ArtificerSubclass),
-    Barbarian(BarbarianSubclass),
-    Bard(BardSubclass),
-    Cleric(ClericSubclass),
-    Druid(DruidSubclass),
-    Fighter(FighterSubclass),
-    Monk(MonkSubclass),
-    Paladin(PaladinSubclass),
-    Ranger(RangerSubclass),
-    Rogue(RogueSubclass),
-    Sorcerer(SorcererSubclass),
-    Warlock(WarlockSubclass),
-    Wizard(WizardSubclass),
+    Artificer(Option<ArtificerSubclass>),
+    Barbarian(Option<BarbarianSubclass>),
+    Bard(Option<BardSubclass>),
+    Cleric(Option<ClericSubclass>),
+    Druid(Option<DruidSubclass>),
+    Fighter(Option<FighterSubclass>),
+    Monk(Option<MonkSubclass>),
+    Paladin(Option<PaladinSubclass>),
+    Ranger(Option<RangerSubclass>),
+    Rogue(Option<RogueSubclass>),
+    Sorcerer(Option<SorcererSubclass>),
+    Warlock(Option<WarlockSubclass>),
+    Wizard(Option<WizardSubclass>),
 }
 
 #[derive(Debug, PartialEq, Serialize, Deserialize, EnumIter, EnumString, Display)]
@@ -45,32 +53,408 @@ pub trait HasSubclass<T> {
     fn get_subclass(&self) -> String;
 }
 
+impl HasSubclass<Class> for Class {
+    /// The subclass name, or "—" if the class hasn't reached its subclass level yet.
+    fn get_subclass(&self) -> String {
+        let subclass = match self {
+            Class::Artificer(v) => v.as_ref().map(|v| v.to_string()),
+            Class::Barbarian(v) => v.as_ref().map(|v| v.to_string()),
+            Class::Bard(v) => v.as_ref().map(|v| v.to_string()),
+            Class::Cleric(v) => v.as_ref().map(|v| v.to_string()),
+            Class::Druid(v) => v.as_ref().map(|v| v.to_string()),
+            Class::Fighter(v) => v.as_ref().map(|v| v.to_string()),
+            Class::Monk(v) => v.as_ref().map(|v| v.to_string()),
+            Class::Paladin(v) => v.as_ref().map(|v| v.to_string()),
+            Class::Ranger(v) => v.as_ref().map(|v| v.to_string()),
+            Class::Rogue(v) => v.as_ref().map(|v| v.to_string()),
+            Class::Sorcerer(v) => v.as_ref().map(|v| v.to_string()),
+            Class::Warlock(v) => v.as_ref().map(|v| v.to_string()),
+            Class::Wizard(v) => v.as_ref().map(|v| v.to_string()),
+        };
+        subclass.unwrap_or_else(|| String::from("—"))
+    }
+}
+
+/// Levels held in one class, e.g. Fighter 2. A multiclass character has one
+/// entry per class, in the order the classes were taken.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct ClassLevel {
+    pub class: Class,
+    pub level: u8,
+}
+
+impl fmt::Display for ClassLevel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.class, self.level)?;
+        match self.class.has_subclass() {
+            true => write!(f, " ({})", self.class.get_subclass()),
+            false => Ok(()),
+        }
+    }
+}
+
 impl Choosable<Class> for Class {
-    fn choose() -> Class {
+    fn choose(rng: &mut GameRng) -> Class {
         choose_value(
             "What is your character's class?",
             &Class::collect_string(),
             // class_match_string
+            rng,
         )
     }
 }
 
 impl Class {
-    pub fn choose_subclass(self) -> Self {
+    /// The class level at which a subclass is chosen.
+    pub fn subclass_level(&self) -> u8 {
         match self {
-            Self::Artificer(_) => Self::Artificer(ArtificerSubclass::choose()),
-            Self::Barbarian(_) => Self::Barbarian(BarbarianSubclass::choose()),
-            Self::Bard(_) => Self::Bard(BardSubclass::choose()),
-            Self::Cleric(_) => Self::Cleric(ClericSubclass::choose()),
-            Self::Druid(_) => Self::Druid(DruidSubclass::choose()),
-            Self::Fighter(_) => Self::Fighter(FighterSubclass::choose()),
-            Self::Monk(_) => Self::Monk(MonkSubclass::choose()),
-            Self::Paladin(_) => Self::Paladin(PaladinSubclass::choose()),
-            Self::Ranger(_) => Self::Ranger(RangerSubclass::choose()),
-            Self::Rogue(_) => Self::Rogue(RogueSubclass::choose()),
-            Self::Sorcerer(_) => Self::Sorcerer(SorcererSubclass::choose()),
-            Self::Warlock(_) => Self::Warlock(WarlockSubclass::choose()),
-            Self::Wizard(_) => Self::Wizard(WizardSubclass::choose()),
+            Class::Cleric(_) | Class::Sorcerer(_) | Class::Warlock(_) => 1,
+            Class::Druid(_) | Class::Wizard(_) => 2,
+            _ => 3,
+        }
+    }
+
+    /// Whether both are the same class, regardless of subclass.
+    pub fn is_same_class(&self, other: &Class) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+
+    pub fn hit_die(&self) -> Dice {
+        match self {
+            Class::Barbarian(_) => Dice::D12,
+            Class::Fighter(_) | Class::Paladin(_) | Class::Ranger(_) => Dice::D10,
+            Class::Sorcerer(_) | Class::Wizard(_) => Dice::D6,
+            _ => Dice::D8,
+        }
+    }
+
+    /// The ability scores needed to multiclass into or out of the class.
+    pub fn multiclass_prerequisites(&self) -> &'static str {
+        match self {
+            Class::Artificer(_) | Class::Wizard(_) => "Intelligence 13",
+            Class::Barbarian(_) => "Strength 13",
+            Class::Bard(_) | Class::Sorcerer(_) | Class::Warlock(_) => "Charisma 13",
+            Class::Cleric(_) | Class::Druid(_) => "Wisdom 13",
+            Class::Fighter(_) => "Strength 13 or Dexterity 13",
+            Class::Monk(_) | Class::Ranger(_) => "Dexterity 13 and Wisdom 13",
+            Class::Paladin(_) => "Strength 13 and Charisma 13",
+            Class::Rogue(_) => "Dexterity 13",
+        }
+    }
+
+    pub fn meets_multiclass_prerequisites(&self, stats: &[Stat]) -> bool {
+        let at_least_13 = |ability: usize| stats[ability].value() >= 13;
+        match self {
+            Class::Artificer(_) | Class::Wizard(_) => at_least_13(3),
+            Class::Barbarian(_) => at_least_13(0),
+            Class::Bard(_) | Class::Sorcerer(_) | Class::Warlock(_) => at_least_13(5),
+            Class::Cleric(_) | Class::Druid(_) => at_least_13(4),
+            Class::Fighter(_) => at_least_13(0) || at_least_13(1),
+            Class::Monk(_) | Class::Ranger(_) => at_least_13(1) && at_least_13(4),
+            Class::Paladin(_) => at_least_13(0) && at_least_13(5),
+            Class::Rogue(_) => at_least_13(1),
+        }
+    }
+
+    pub fn has_subclass(&self) -> bool {
+        match self {
+            Class::Artificer(v) => v.is_some(),
+            Class::Barbarian(v) => v.is_some(),
+            Class::Bard(v) => v.is_some(),
+            Class::Cleric(v) => v.is_some(),
+            Class::Druid(v) => v.is_some(),
+            Class::Fighter(v) => v.is_some(),
+            Class::Monk(v) => v.is_some(),
+            Class::Paladin(v) => v.is_some(),
+            Class::Ranger(v) => v.is_some(),
+            Class::Rogue(v) => v.is_some(),
+            Class::Sorcerer(v) => v.is_some(),
+            Class::Warlock(v) => v.is_some(),
+            Class::Wizard(v) => v.is_some(),
+        }
+    }
+
+    /// How many class skills are picked at 1st level, and the list to pick from.
+    pub fn skill_choices(&self) -> (usize, Vec<Skill>) {
+        match self {
+            Class::Artificer(_) => (
+                2,
+                vec![
+                    Skill::Arcana,
+                    Skill::History,
+                    Skill::Investigation,
+                    Skill::Medicine,
+                    Skill::Nature,
+                    Skill::Perception,
+                    Skill::SleightOfHand,
+                ],
+            ),
+            Class::Barbarian(_) => (
+                2,
+                vec![
+                    Skill::AnimalHandling,
+                    Skill::Athletics,
+                    Skill::Intimidation,
+                    Skill::Nature,
+                    Skill::Perception,
+                    Skill::Survival,
+                ],
+            ),
+            Class::Bard(_) => (3, Skill::iter().collect()),
+            Class::Cleric(_) => (
+                2,
+                vec![
+                    Skill::History,
+                    Skill::Insight,
+                    Skill::Medicine,
+                    Skill::Persuasion,
+                    Skill::Religion,
+                ],
+            ),
+            Class::Druid(_) => (
+                2,
+                vec![
+                    Skill::Arcana,
+                    Skill::AnimalHandling,
+                    Skill::Insight,
+                    Skill::Medicine,
+                    Skill::Nature,
+                    Skill::Perception,
+                    Skill::Religion,
+                    Skill::Survival,
+                ],
+            ),
+            Class::Fighter(_) => (
+                2,
+                vec![
+                    Skill::Acrobatics,
+                    Skill::AnimalHandling,
+                    Skill::Athletics,
+                    Skill::History,
+                    Skill::Insight,
+                    Skill::Intimidation,
+                    Skill::Perception,
+                    Skill::Survival,
+                ],
+            ),
+            Class::Monk(_) => (
+                2,
+                vec![
+                    Skill::Acrobatics,
+                    Skill::Athletics,
+                    Skill::History,
+                    Skill::Insight,
+                    Skill::Religion,
+                    Skill::Stealth,
+                ],
+            ),
+            Class::Paladin(_) => (
+                2,
+                vec![
+                    Skill::Athletics,
+                    Skill::Insight,
+                    Skill::Intimidation,
+                    Skill::Medicine,
+                    Skill::Persuasion,
+                    Skill::Religion,
+                ],
+            ),
+            Class::Ranger(_) => (
+                3,
+                vec![
+                    Skill::AnimalHandling,
+                    Skill::Athletics,
+                    Skill::Insight,
+                    Skill::Investigation,
+                    Skill::Nature,
+                    Skill::Perception,
+                    Skill::Stealth,
+                    Skill::Survival,
+                ],
+            ),
+            Class::Rogue(_) => (
+                4,
+                vec![
+                    Skill::Acrobatics,
+                    Skill::Athletics,
+                    Skill::Deception,
+                    Skill::Insight,
+                    Skill::Intimidation,
+                    Skill::Investigation,
+                    Skill::Perception,
+                    Skill::Performance,
+                    Skill::Persuasion,
+                    Skill::SleightOfHand,
+                    Skill::Stealth,
+                ],
+            ),
+            Class::Sorcerer(_) => (
+                2,
+                vec![
+                    Skill::Arcana,
+                    Skill::Deception,
+                    Skill::Insight,
+                    Skill::Intimidation,
+                    Skill::Persuasion,
+                    Skill::Religion,
+                ],
+            ),
+            Class::Warlock(_) => (
+                2,
+                vec![
+                    Skill::Arcana,
+                    Skill::Deception,
+                    Skill::History,
+                    Skill::Intimidation,
+                    Skill::Investigation,
+                    Skill::Nature,
+                    Skill::Religion,
+                ],
+            ),
+            Class::Wizard(_) => (
+                2,
+                vec![
+                    Skill::Arcana,
+                    Skill::History,
+                    Skill::Insight,
+                    Skill::Investigation,
+                    Skill::Medicine,
+                    Skill::Religion,
+                ],
+            ),
+        }
+    }
+
+    /// Skill proficiencies gained when multiclassing into the class, which
+    /// only Bards, Rangers and Rogues get.
+    pub fn multiclass_skill_choices(&self) -> (usize, Vec<Skill>) {
+        match self {
+            Class::Bard(_) => (1, Skill::iter().collect()),
+            Class::Ranger(_) | Class::Rogue(_) => (1, self.skill_choices().1),
+            _ => (0, vec![]),
+        }
+    }
+
+    /// Total number of Expertise skills the class has granted by `level`.
+    pub fn expertise_count(&self, level: u8) -> usize {
+        match self {
+            Class::Rogue(_) => match level {
+                1..=5 => 2,
+                _ => 4,
+            },
+            Class::Bard(_) => match level {
+                1..=2 => 0,
+                3..=9 => 2,
+                _ => 4,
+            },
+            _ => 0,
+        }
+    }
+
+    /// Saving throw proficiencies (as indexes into `Character.stats`) at `level`,
+    /// including features like Diamond Soul and Slippery Mind that add more later.
+    pub fn saving_throws(&self, level: u8) -> Vec<usize> {
+        let mut saves = match self {
+            Class::Artificer(_) => vec![2, 3],
+            Class::Barbarian(_) | Class::Fighter(_) => vec![0, 2],
+            Class::Bard(_) => vec![1, 5],
+            Class::Cleric(_) | Class::Paladin(_) | Class::Warlock(_) => vec![4, 5],
+            Class::Druid(_) | Class::Wizard(_) => vec![3, 4],
+            Class::Monk(_) | Class::Ranger(_) => vec![0, 1],
+            Class::Rogue(_) => vec![1, 3],
+            Class::Sorcerer(_) => vec![2, 5],
+        };
+        for save in self.feature_saving_throws(level) {
+            if !saves.contains(&save) {
+                saves.push(save);
+            }
+        }
+        saves
+    }
+
+    /// Saving throws added by later class features. Unlike the 1st-level
+    /// proficiencies, these also apply when the class was multiclassed into.
+    pub fn feature_saving_throws(&self, level: u8) -> Vec<usize> {
+        match self {
+            Class::Monk(_) if level >= 14 => (0..6).collect(),
+            Class::Rogue(_) if level >= 15 => vec![4],
+            _ => vec![],
+        }
+    }
+
+    /// Whether the class gives proficiency with `weapon`. Multiclassing into
+    /// a class gives fewer weapon proficiencies than starting in it.
+    pub fn is_proficient_with(&self, weapon: Weapon, multiclassed: bool) -> bool {
+        let simple = weapon.category() == WeaponCategory::Simple;
+        match (self, multiclassed) {
+            (Class::Barbarian(_) | Class::Fighter(_) | Class::Paladin(_) | Class::Ranger(_), _) => {
+                true
+            }
+            (Class::Monk(_), _) => simple || weapon == Weapon::Shortsword,
+            (Class::Warlock(_), _) | (Class::Artificer(_) | Class::Cleric(_), false) => simple,
+            (Class::Bard(_) | Class::Rogue(_), false) => {
+                simple
+                    || matches!(
+                        weapon,
+                        Weapon::HandCrossbow
+                            | Weapon::Longsword
+                            | Weapon::Rapier
+                            | Weapon::Shortsword
+                    )
+            }
+            (Class::Druid(_), false) => matches!(
+                weapon,
+                Weapon::Club
+                    | Weapon::Dagger
+                    | Weapon::Dart
+                    | Weapon::Javelin
+                    | Weapon::Mace
+                    | Weapon::Quarterstaff
+                    | Weapon::Scimitar
+                    | Weapon::Sickle
+                    | Weapon::Sling
+                    | Weapon::Spear
+            ),
+            (Class::Sorcerer(_) | Class::Wizard(_), false) => matches!(
+                weapon,
+                Weapon::Dagger
+                    | Weapon::Dart
+                    | Weapon::Sling
+                    | Weapon::Quarterstaff
+                    | Weapon::LightCrossbow
+            ),
+            _ => false,
+        }
+    }
+
+    /// Levels at which the class grants an Ability Score Improvement (or a feat).
+    pub fn asi_levels(&self) -> Vec<u8> {
+        match self {
+            Class::Fighter(_) => vec![4, 6, 8, 12, 14, 16, 19],
+            Class::Rogue(_) => vec![4, 8, 10, 12, 16, 19],
+            _ => vec![4, 8, 12, 16, 19],
+        }
+    }
+
+    pub fn has_jack_of_all_trades(&self, level: u8) -> bool {
+        matches!(self, Class::Bard(_)) && level >= 2
+    }
+
+    pub fn choose_subclass(&self, rng: &mut GameRng) -> Self {
+        match self {
+            Self::Artificer(_) => Self::Artificer(Some(ArtificerSubclass::choose(rng))),
+            Self::Barbarian(_) => Self::Barbarian(Some(BarbarianSubclass::choose(rng))),
+            Self::Bard(_) => Self::Bard(Some(BardSubclass::choose(rng))),
+            Self::Cleric(_) => Self::Cleric(Some(ClericSubclass::choose(rng))),
+            Self::Druid(_) => Self::Druid(Some(DruidSubclass::choose(rng))),
+            Self::Fighter(_) => Self::Fighter(Some(FighterSubclass::choose(rng))),
+            Self::Monk(_) => Self::Monk(Some(MonkSubclass::choose(rng))),
+            Self::Paladin(_) => Self::Paladin(Some(PaladinSubclass::choose(rng))),
+            Self::Ranger(_) => Self::Ranger(Some(RangerSubclass::choose(rng))),
+            Self::Rogue(_) => Self::Rogue(Some(RogueSubclass::choose(rng))),
+            Self::Sorcerer(_) => Self::Sorcerer(Some(SorcererSubclass::choose(rng))),
+            Self::Warlock(_) => Self::Warlock(Some(WarlockSubclass::choose(rng))),
+            Self::Wizard(_) => Self::Wizard(Some(WizardSubclass::choose(rng))),
         }
     }
 }
@@ -86,11 +470,12 @@ pub enum ArtificerSubclass {
 }
 
 impl Choosable<ArtificerSubclass> for ArtificerSubclass {
-    fn choose() -> ArtificerSubclass {
+    fn choose(rng: &mut GameRng) -> ArtificerSubclass {
         choose_value(
             "What is your Artificer's subclass?",
             &ArtificerSubclass::collect_string(),
             // class_match_string
+            rng,
         )
     }
 }
@@ -109,11 +494,12 @@ pub enum BarbarianSubclass {
 }
 
 impl Choosable<BarbarianSubclass> for BarbarianSubclass {
-    fn choose() -> BarbarianSubclass {
+    fn choose(rng: &mut GameRng) -> BarbarianSubclass {
         choose_value(
             "What is your Barbarian's subclass?",
             &BarbarianSubclass::collect_string(),
             // class_match_string
+            rng,
         )
     }
 }
@@ -132,11 +518,12 @@ pub enum BardSubclass {
 }
 
 impl Choosable<BardSubclass> for BardSubclass {
-    fn choose() -> BardSubclass {
+    fn choose(rng: &mut GameRng) -> BardSubclass {
         choose_value(
             "What is your Bard's subclass?",
             &BardSubclass::collect_string(),
             // class_match_string
+            rng,
         )
     }
 }
@@ -160,11 +547,12 @@ pub enum ClericSubclass {
 }
 
 impl Choosable<ClericSubclass> for ClericSubclass {
-    fn choose() -> ClericSubclass {
+    fn choose(rng: &mut GameRng) -> ClericSubclass {
         choose_value(
             "What is your Cleric's subclass?",
             &ClericSubclass::collect_string(),
             // class_match_string
+            rng,
         )
     }
 }
@@ -183,11 +571,12 @@ pub enum DruidSubclass {
 }
 
 impl Choosable<DruidSubclass> for DruidSubclass {
-    fn choose() -> DruidSubclass {
+    fn choose(rng: &mut GameRng) -> DruidSubclass {
         choose_value(
             "What is your Druid's subclass?",
             &DruidSubclass::collect_string(),
             // class_match_string
+            rng,
         )
     }
 }
@@ -206,11 +595,12 @@ pub enum FighterSubclass {
 }
 
 impl Choosable<FighterSubclass> for FighterSubclass {
-    fn choose() -> FighterSubclass {
+    fn choose(rng: &mut GameRng) -> FighterSubclass {
         choose_value(
             "What is your Fighter's subclass?",
             &FighterSubclass::collect_string(),
             // class_match_string
+            rng,
         )
     }
 }
@@ -230,11 +620,12 @@ pub enum MonkSubclass {
 }
 
 impl Choosable<MonkSubclass> for MonkSubclass {
-    fn choose() -> MonkSubclass {
+    fn choose(rng: &mut GameRng) -> MonkSubclass {
         choose_value(
             "What is your Monk's subclass?",
             &MonkSubclass::collect_string(),
             // class_match_string
+            rng,
         )
     }
 }
@@ -253,11 +644,12 @@ pub enum PaladinSubclass {
 }
 
 impl Choosable<PaladinSubclass> for PaladinSubclass {
-    fn choose() -> PaladinSubclass {
+    fn choose(rng: &mut GameRng) -> PaladinSubclass {
         choose_value(
             "What is your Paladin's subclass?",
             &PaladinSubclass::collect_string(),
             // class_match_string
+            rng,
         )
     }
 }
@@ -276,11 +668,12 @@ pub enum RangerSubclass {
 }
 
 impl Choosable<RangerSubclass> for RangerSubclass {
-    fn choose() -> RangerSubclass {
+    fn choose(rng: &mut GameRng) -> RangerSubclass {
         choose_value(
             "What is your Ranger's subclass?",
             &RangerSubclass::collect_string(),
             // class_match_string
+            rng,
         )
     }
 }
@@ -301,11 +694,12 @@ pub enum RogueSubclass {
 }
 
 impl Choosable<RogueSubclass> for RogueSubclass {
-    fn choose() -> RogueSubclass {
+    fn choose(rng: &mut GameRng) -> RogueSubclass {
         choose_value(
             "What is your Rogue's subclass?",
             &RogueSubclass::collect_string(),
             // class_match_string
+            rng,
         )
     }
 }
@@ -324,11 +718,12 @@ pub enum SorcererSubclass {
 }
 
 impl Choosable<SorcererSubclass> for SorcererSubclass {
-    fn choose() -> SorcererSubclass {
+    fn choose(rng: &mut GameRng) -> SorcererSubclass {
         choose_value(
             "What is your Sorcerer's subclass?",
             &SorcererSubclass::collect_string(),
             // class_match_string
+            rng,
         )
     }
 }
@@ -347,11 +742,12 @@ pub enum WarlockSubclass {
 }
 
 impl Choosable<WarlockSubclass> for WarlockSubclass {
-    fn choose() -> WarlockSubclass {
+    fn choose(rng: &mut GameRng) -> WarlockSubclass {
         choose_value(
             "What is your Warlock's subclass?",
             &WarlockSubclass::collect_string(),
             // class_match_string
+            rng,
         )
     }
 }
@@ -373,11 +769,64 @@ pub enum WizardSubclass {
 }
 
 impl Choosable<WizardSubclass> for WizardSubclass {
-    fn choose() -> WizardSubclass {
+    fn choose(rng: &mut GameRng) -> WizardSubclass {
         choose_value(
             "What is your Wizard's subclass?",
             &WizardSubclass::collect_string(),
             // class_match_string
+            rng,
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn later_features_add_saving_throws() {
+        let monk = Class::Monk(None);
+        assert_eq!(monk.saving_throws(13), vec![0, 1]);
+        assert_eq!(monk.saving_throws(14).len(), 6);
+
+        let rogue = Class::Rogue(None);
+        assert!(!rogue.saving_throws(14).contains(&4));
+        assert!(rogue.saving_throws(15).contains(&4));
+    }
+
+    #[test]
+    fn subclass_is_empty_until_chosen() {
+        let cleric = Class::Cleric(None);
+        assert!(!cleric.has_subclass());
+        assert_eq!(cleric.get_subclass(), "—");
+        assert_eq!(cleric.subclass_level(), 1);
+
+        let wizard = Class::Wizard(Some(WizardSubclass::Evocation));
+        assert!(wizard.has_subclass());
+        assert_eq!(wizard.get_subclass(), "Evocation");
+
+        let yaml = serde_yaml::to_string(&Class::Monk(None)).unwrap();
+        assert_eq!(
+            serde_yaml::from_str::<Class>(&yaml).unwrap(),
+            Class::Monk(None)
+        );
+        let saved: Class = serde_yaml::from_str("Monk: Mercy").unwrap();
+        assert_eq!(saved, Class::Monk(Some(MonkSubclass::Mercy)));
+    }
+
+    #[test]
+    fn multiclassing_needs_ability_scores() {
+        let stats = [
+            Stat::Str(14),
+            Stat::Dex(12),
+            Stat::Con(14),
+            Stat::Int(13),
+            Stat::Wis(10),
+            Stat::Chr(8),
+        ];
+        assert!(Class::Fighter(None).meets_multiclass_prerequisites(&stats));
+        assert!(Class::Wizard(None).meets_multiclass_prerequisites(&stats));
+        assert!(!Class::Paladin(None).meets_multiclass_prerequisites(&stats));
+        assert!(!Class::Monk(None).meets_multiclass_prerequisites(&stats));
+    }
+}
@@ -0,0 +1,258 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize};
+use strum_macros::{Display, EnumIter, EnumString};
+
+use super::dice::RollMode;
+
+pub const MAX_EXHAUSTION: u8 = 6;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, EnumIter, EnumString, Display)]
+#[strum(ascii_case_insensitive, serialize_all = "title_case")]
+pub enum Condition {
+    Blinded,
+    Charmed,
+    Deafened,
+    Frightened,
+    Grappled,
+    Incapacitated,
+    Invisible,
+    Paralyzed,
+    Petrified,
+    Poisoned,
+    Prone,
+    Restrained,
+    Stunned,
+    Unconscious,
+}
+
+impl Condition {
+    pub fn description(&self) -> &str {
+        match self {
+            Condition::Blinded => {
+                "Auto-fail sight checks; attacks have disadvantage, attacks against have advantage"
+            }
+            Condition::Charmed => {
+                "Can't attack the charmer; charmer has advantage on social checks"
+            }
+            Condition::Deafened => "Auto-fail hearing checks",
+            Condition::Frightened => {
+                "Disadvantage on checks and attacks while the source is in sight; can't move closer"
+            }
+            Condition::Grappled => "Speed 0",
+            Condition::Incapacitated => "Can't take actions or reactions",
+            Condition::Invisible => "Attacks have advantage, attacks against have disadvantage",
+            Condition::Paralyzed => "Incapacitated, speed 0, auto-fail Str and Dex saves",
+            Condition::Petrified => {
+                "Incapacitated, speed 0, auto-fail Str and Dex saves, resistance to all damage"
+            }
+            Condition::Poisoned => "Disadvantage on attacks and ability checks",
+            Condition::Prone => "Attacks have disadvantage; standing costs half your movement",
+            Condition::Restrained => "Speed 0; attacks and Dex saves have disadvantage",
+            Condition::Stunned => "Incapacitated, speed 0, auto-fail Str and Dex saves",
+            Condition::Unconscious => "Incapacitated, prone, speed 0, auto-fail Str and Dex saves",
+        }
+    }
+
+    /// Conditions that include being incapacitated.
+    pub fn incapacitates(&self) -> bool {
+        matches!(
+            self,
+            Condition::Incapacitated
+                | Condition::Paralyzed
+                | Condition::Petrified
+                | Condition::Stunned
+                | Condition::Unconscious
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActiveCondition {
+    pub condition: Condition,
+    #[serde(default)]
+    pub source: Option<String>,
+    /// Rounds left before the condition ends on its own; `None` lasts until removed.
+    #[serde(default)]
+    pub rounds_remaining: Option<u8>,
+}
+
+impl fmt::Display for ActiveCondition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.condition)?;
+        match (&self.source, self.rounds_remaining) {
+            (Some(source), Some(rounds)) => write!(f, " ({}, {} rounds)", source, rounds),
+            (Some(source), None) => write!(f, " ({})", source),
+            (None, Some(rounds)) => write!(f, " ({} rounds)", rounds),
+            (None, None) => Ok(()),
+        }
+    }
+}
+
+/// What a saving throw is protecting against, for traits like Dwarven
+/// Resilience that only grant advantage against certain threats.
+#[derive(Debug, Clone, Copy, PartialEq, EnumIter, EnumString, Display)]
+#[strum(ascii_case_insensitive, serialize_all = "title_case")]
+pub enum SaveAgainst {
+    Other,
+    Poison,
+    Magic,
+    BeingFrightened,
+    BeingCharmed,
+}
+
+/// Which kind of d20 roll is being made, so conditions can decide whether
+/// it has advantage, disadvantage or fails outright. Abilities are indexed
+/// in `Character.stats` order.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RollKind {
+    Attack,
+    AbilityCheck(usize),
+    SavingThrow(usize),
+    /// A saving throw tied to no ability, so only exhaustion affects it.
+    DeathSave,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct RollEffects {
+    pub advantage: Vec<String>,
+    pub disadvantage: Vec<String>,
+    pub auto_fail: Vec<String>,
+}
+
+impl RollEffects {
+    pub fn mode(&self) -> RollMode {
+        match (self.advantage.is_empty(), self.disadvantage.is_empty()) {
+            (false, true) => RollMode::Advantage,
+            (true, false) => RollMode::Disadvantage,
+            _ => RollMode::Normal,
+        }
+    }
+}
+
+pub fn roll_effects(conditions: &[ActiveCondition], exhaustion: u8, kind: RollKind) -> RollEffects {
+    let mut effects = RollEffects::default();
+
+    for active in conditions {
+        let name = active.condition.to_string();
+        match (active.condition, kind) {
+            (Condition::Blinded, RollKind::Attack)
+            | (Condition::Frightened, RollKind::Attack)
+            | (Condition::Frightened, RollKind::AbilityCheck(_))
+            | (Condition::Poisoned, RollKind::Attack)
+            | (Condition::Poisoned, RollKind::AbilityCheck(_))
+            | (Condition::Prone, RollKind::Attack)
+            | (Condition::Restrained, RollKind::Attack)
+            | (Condition::Restrained, RollKind::SavingThrow(1)) => effects.disadvantage.push(name),
+            (Condition::Invisible, RollKind::Attack) => effects.advantage.push(name),
+            (c, RollKind::SavingThrow(0)) | (c, RollKind::SavingThrow(1)) => {
+                if matches!(
+                    c,
+                    Condition::Paralyzed
+                        | Condition::Petrified
+                        | Condition::Stunned
+                        | Condition::Unconscious
+                ) {
+                    effects.auto_fail.push(name)
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let exhausted = format!("Exhaustion {}", exhaustion);
+    match kind {
+        RollKind::AbilityCheck(_) if exhaustion >= 1 => effects.disadvantage.push(exhausted),
+        RollKind::Attack | RollKind::SavingThrow(_) | RollKind::DeathSave if exhaustion >= 3 => {
+            effects.disadvantage.push(exhausted)
+        }
+        _ => {}
+    }
+
+    effects
+}
+
+/// Speed after conditions and exhaustion are applied.
+pub fn effective_speed(conditions: &[ActiveCondition], exhaustion: u8, speed: u16) -> u16 {
+    let immobile = conditions.iter().any(|c| {
+        matches!(c.condition, Condition::Grappled | Condition::Restrained)
+            || c.condition.incapacitates()
+    });
+    if immobile || exhaustion >= 5 {
+        0
+    } else if exhaustion >= 2 {
+        speed / 2
+    } else {
+        speed
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ConditionsRepr {
+    List(Vec<ActiveCondition>),
+    Legacy(String),
+}
+
+/// Accepts both the current list of conditions and the single `conditions: None`
+/// value written by older saves.
+pub fn de_conditions<'de, D>(deserializer: D) -> Result<Vec<ActiveCondition>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(match ConditionsRepr::deserialize(deserializer)? {
+        ConditionsRepr::List(list) => list,
+        ConditionsRepr::Legacy(s) => match Condition::from_str(&s) {
+            Ok(condition) => vec![ActiveCondition {
+                condition,
+                source: None,
+                rounds_remaining: None,
+            }],
+            Err(_) => vec![],
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn active(condition: Condition) -> ActiveCondition {
+        ActiveCondition {
+            condition,
+            source: None,
+            rounds_remaining: None,
+        }
+    }
+
+    #[test]
+    fn conditions_change_roll_mode() {
+        let poisoned = vec![active(Condition::Poisoned)];
+        assert_eq!(
+            roll_effects(&poisoned, 0, RollKind::Attack).mode(),
+            RollMode::Disadvantage
+        );
+
+        let both = vec![active(Condition::Poisoned), active(Condition::Invisible)];
+        assert_eq!(
+            roll_effects(&both, 0, RollKind::Attack).mode(),
+            RollMode::Normal
+        );
+
+        let stunned = vec![active(Condition::Stunned)];
+        assert!(!roll_effects(&stunned, 0, RollKind::SavingThrow(1))
+            .auto_fail
+            .is_empty());
+        assert!(roll_effects(&stunned, 0, RollKind::SavingThrow(4))
+            .auto_fail
+            .is_empty());
+    }
+
+    #[test]
+    fn speed_drops_with_conditions_and_exhaustion() {
+        assert_eq!(effective_speed(&[active(Condition::Grappled)], 0, 30), 0);
+        assert_eq!(effective_speed(&[], 2, 30), 15);
+        assert_eq!(effective_speed(&[], 5, 30), 0);
+    }
+}
@@ -0,0 +1,274 @@
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumIter};
+
+#[derive(Debug, Clone, Copy, PartialEq, EnumIter, Display)]
+pub enum Coin {
+    #[strum(serialize = "cp")]
+    Copper,
+    #[strum(serialize = "sp")]
+    Silver,
+    #[strum(serialize = "ep")]
+    Electrum,
+    #[strum(serialize = "gp")]
+    Gold,
+    #[strum(serialize = "pp")]
+    Platinum,
+}
+
+impl Coin {
+    /// Worth in copper pieces.
+    pub fn value(&self) -> u32 {
+        match self {
+            Coin::Copper => 1,
+            Coin::Silver => 10,
+            Coin::Electrum => 50,
+            Coin::Gold => 100,
+            Coin::Platinum => 1_000,
+        }
+    }
+}
+
+/// Highest value first, the order coins are counted out in.
+const COINS: [Coin; 5] = [
+    Coin::Platinum,
+    Coin::Gold,
+    Coin::Electrum,
+    Coin::Silver,
+    Coin::Copper,
+];
+
+/// The coins given as change: gold, silver and copper.
+const CHANGE: [Coin; 3] = [Coin::Gold, Coin::Silver, Coin::Copper];
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Purse {
+    #[serde(default)]
+    pub cp: u32,
+    #[serde(default)]
+    pub sp: u32,
+    #[serde(default)]
+    pub ep: u32,
+    #[serde(default)]
+    pub gp: u32,
+    #[serde(default)]
+    pub pp: u32,
+}
+
+impl Purse {
+    /// `amount` copper pieces' worth in as few gold, silver and copper coins as possible.
+    pub fn from_value(amount: u32) -> Purse {
+        let mut purse = Purse::default();
+        purse.receive(amount);
+        purse
+    }
+
+    pub fn count(&self, coin: Coin) -> u32 {
+        match coin {
+            Coin::Copper => self.cp,
+            Coin::Silver => self.sp,
+            Coin::Electrum => self.ep,
+            Coin::Gold => self.gp,
+            Coin::Platinum => self.pp,
+        }
+    }
+
+    fn count_mut(&mut self, coin: Coin) -> &mut u32 {
+        match coin {
+            Coin::Copper => &mut self.cp,
+            Coin::Silver => &mut self.sp,
+            Coin::Electrum => &mut self.ep,
+            Coin::Gold => &mut self.gp,
+            Coin::Platinum => &mut self.pp,
+        }
+    }
+
+    /// Everything in the purse, in copper pieces. A `u64`, as a purse full
+    /// of platinum is worth more copper than a `u32` can count.
+    pub fn total(&self) -> u64 {
+        COINS
+            .iter()
+            .map(|c| self.count(*c) as u64 * c.value() as u64)
+            .sum()
+    }
+
+    /// Adds coins of one kind, up to as many as a `u32` can count.
+    pub fn add(&mut self, coin: Coin, amount: u32) {
+        let count = self.count_mut(coin);
+        *count = count.saturating_add(amount);
+    }
+
+    /// Puts all of `coins` in the purse.
+    pub fn deposit(&mut self, coins: &Purse) {
+        for coin in COINS {
+            self.add(coin, coins.count(coin));
+        }
+    }
+
+    /// Adds `amount` copper pieces' worth as gold, silver and copper.
+    pub fn receive(&mut self, mut amount: u32) {
+        for coin in CHANGE {
+            self.add(coin, amount / coin.value());
+            amount %= coin.value();
+        }
+    }
+
+    /// Pays `amount` copper pieces, counting out the largest coins that don't
+    /// overpay and then breaking the smallest coin that covers the rest, with
+    /// the change coming back as gold, silver and copper.
+    pub fn pay(&mut self, amount: u32) -> Result<(), String> {
+        if self.total() < amount as u64 {
+            return Err(format!(
+                "That costs {}, but you only have {}.",
+                Purse::from_value(amount),
+                self
+            ));
+        }
+        let mut owed = amount;
+        for coin in COINS {
+            let count = self.count_mut(coin);
+            let paid = (*count).min(owed / coin.value());
+            *count -= paid;
+            owed -= paid * coin.value();
+        }
+        if owed > 0 {
+            // Every coin left is worth more than what is still owed.
+            let coin = *COINS.iter().rev().find(|c| self.count(**c) > 0).unwrap();
+            *self.count_mut(coin) -= 1;
+            self.receive(coin.value() - owed);
+        }
+        Ok(())
+    }
+}
+
+/// Lists the coins from platinum down, e.g. "12 gp, 5 sp".
+impl fmt::Display for Purse {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let coins: Vec<String> = COINS
+            .iter()
+            .filter(|c| self.count(**c) > 0)
+            .map(|c| format!("{} {}", self.count(*c), c))
+            .collect();
+        match coins.is_empty() {
+            true => write!(f, "0 cp"),
+            false => write!(f, "{}", coins.join(", ")),
+        }
+    }
+}
+
+/// Reads amounts like "12 gp, 5 sp" or "3gp 2cp", worth no more copper than a
+/// `u32` can count.
+impl FromStr for Purse {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Purse, String> {
+        let error = || format!("\"{}\" isn't an amount like \"12 gp, 5 sp\"", s.trim());
+        let text: String = s
+            .chars()
+            .filter(|c| !c.is_whitespace() && *c != ',')
+            .collect::<String>()
+            .to_lowercase();
+        if text.is_empty() {
+            return Err(error());
+        }
+        let mut purse = Purse::default();
+        let mut rest = text.as_str();
+        while !rest.is_empty() {
+            let digits = rest
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(rest.len());
+            let amount: u32 = rest[..digits].parse().map_err(|_| error())?;
+            let coin = COINS
+                .iter()
+                .find(|c| rest[digits..].starts_with(&c.to_string()))
+                .ok_or_else(error)?;
+            purse.add(*coin, amount);
+            rest = &rest[digits + 2..];
+        }
+        match purse.total() <= u32::MAX as u64 {
+            true => Ok(purse),
+            false => Err(format!("{} is more than can be counted", s.trim())),
+        }
+    }
+}
+
+/// A purchase, sale or other change to the character's coins, kept so the
+/// DM can review spending between sessions.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Transaction {
+    pub at: DateTime<Utc>,
+    pub description: String,
+    /// Copper pieces gained, or spent when negative.
+    pub amount: i64,
+}
+
+impl Transaction {
+    pub fn new(description: &str, amount: i64) -> Transaction {
+        Transaction {
+            at: Utc::now(),
+            description: String::from(description),
+            amount,
+        }
+    }
+}
+
+/// E.g. "2024-05-01 18:30 Bought 1 × Longsword: -15 gp".
+impl fmt::Display for Transaction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {}: {}{}",
+            self.at.format("%Y-%m-%d %H:%M"),
+            self.description,
+            match self.amount < 0 {
+                true => "-",
+                false => "+",
+            },
+            Purse::from_value(self.amount.unsigned_abs() as u32)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paying_makes_change() {
+        let mut purse = Purse {
+            gp: 2,
+            ep: 1,
+            sp: 3,
+            ..Default::default()
+        };
+        assert_eq!(purse.total(), 280);
+
+        // Exact change where it can be made.
+        purse.pay(130).unwrap();
+        assert_eq!(purse.to_string(), "1 gp, 1 ep");
+
+        // The electrum covers 5 sp, then the gold piece is broken for the
+        // remaining 2 sp 3 cp.
+        purse.pay(73).unwrap();
+        assert_eq!(purse.to_string(), "7 sp, 7 cp");
+        assert_eq!(purse.total(), 77);
+
+        assert!(purse.pay(100).is_err());
+        assert_eq!(purse.total(), 77);
+        assert_eq!(Purse::from_value(1_525).to_string(), "15 gp, 2 sp, 5 cp");
+        assert_eq!(Purse::from_str("15 gp, 2 sp, 5 cp").unwrap().total(), 1_525);
+        assert_eq!(Purse::from_str("3PP 1ep").unwrap().total(), 3_050);
+        assert!(Purse::from_str("12 gold").is_err());
+        assert!(Purse::from_str("5000000 pp").is_err());
+        assert!(Purse::from_str("99999999999 cp").is_err());
+
+        let hoard = Purse {
+            pp: u32::MAX,
+            ..Default::default()
+        };
+        assert_eq!(hoard.total(), u32::MAX as u64 * 1_000);
+    }
+}
@@ -0,0 +1,172 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumIter, EnumString};
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+    EnumIter,
+    EnumString,
+    Display,
+)]
+#[serde(rename_all = "lowercase")]
+#[strum(ascii_case_insensitive, serialize_all = "lowercase")]
+pub enum DamageType {
+    Acid,
+    Bludgeoning,
+    Cold,
+    Fire,
+    Force,
+    Lightning,
+    Necrotic,
+    Piercing,
+    Poison,
+    Psychic,
+    Radiant,
+    Slashing,
+    Thunder,
+}
+
+impl DamageType {
+    /// Bludgeoning, piercing and slashing: the damage Rage resists.
+    pub fn is_physical(&self) -> bool {
+        matches!(
+            self,
+            DamageType::Bludgeoning | DamageType::Piercing | DamageType::Slashing
+        )
+    }
+
+    /// The first damage type named in a roll label such as `piercing and fire`.
+    pub fn from_label(label: &str) -> Option<DamageType> {
+        label
+            .split_whitespace()
+            .find_map(|word| DamageType::from_str(word).ok())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, EnumIter, EnumString, Display)]
+#[strum(ascii_case_insensitive, serialize_all = "title_case")]
+pub enum Defense {
+    Resistance,
+    Immunity,
+    Vulnerability,
+}
+
+/// A resistance, immunity or vulnerability and where it comes from.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DamageDefense {
+    pub defense: Defense,
+    pub damage_type: DamageType,
+    pub source: String,
+}
+
+impl fmt::Display for DamageDefense {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} to {} ({})",
+            self.defense, self.damage_type, self.source
+        )
+    }
+}
+
+/// Applies immunity, then resistance (halved, rounded down), then
+/// vulnerability (doubled). Several of the same defense count only once.
+pub fn adjust_damage(amount: i32, defenses: &[Defense]) -> i32 {
+    if defenses.contains(&Defense::Immunity) {
+        return 0;
+    }
+    let mut amount = amount;
+    if defenses.contains(&Defense::Resistance) {
+        amount /= 2;
+    }
+    if defenses.contains(&Defense::Vulnerability) {
+        amount *= 2;
+    }
+    amount
+}
+
+/// How a hit was soaked up by temporary and real hit points.
+#[derive(Debug, Default, PartialEq)]
+pub struct DamageTaken {
+    pub temporary: i32,
+    pub lost: i32,
+    /// The damage left over at 0 HP was at least the hit point maximum.
+    pub instant_death: bool,
+    /// Death save failures from being hit while already at 0 HP.
+    pub death_save_failures: u8,
+}
+
+/// Death saving throws made while dying at 0 HP. Both tallies reset on
+/// regaining hit points or becoming stable.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeathSaves {
+    pub successes: u8,
+    pub failures: u8,
+    /// Still at 0 HP, but no longer making death saves.
+    pub stable: bool,
+}
+
+impl fmt::Display for DeathSaves {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} success{}, {} failure{}",
+            self.successes,
+            if self.successes == 1 { "" } else { "es" },
+            self.failures,
+            if self.failures == 1 { "" } else { "s" }
+        )
+    }
+}
+
+/// What a death saving throw led to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DeathSaveOutcome {
+    Success,
+    /// One failure, or two on a natural 1.
+    Failure(u8),
+    /// The third success.
+    Stable,
+    /// A natural 20: 1 HP and back on their feet, so to speak.
+    RegainedHp,
+    /// The third failure.
+    Dead,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defenses_adjust_damage() {
+        assert_eq!(adjust_damage(11, &[]), 11);
+        assert_eq!(adjust_damage(11, &[Defense::Resistance]), 5);
+        assert_eq!(
+            adjust_damage(11, &[Defense::Resistance, Defense::Resistance]),
+            5
+        );
+        assert_eq!(adjust_damage(11, &[Defense::Vulnerability]), 22);
+        assert_eq!(
+            adjust_damage(11, &[Defense::Resistance, Defense::Vulnerability]),
+            10
+        );
+        assert_eq!(
+            adjust_damage(11, &[Defense::Immunity, Defense::Vulnerability]),
+            0
+        );
+        assert_eq!(
+            DamageType::from_label("piercing and fire"),
+            Some(DamageType::Piercing)
+        );
+    }
+}
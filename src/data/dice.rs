@@ -0,0 +1,575 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumIter, EnumString};
+
+const MAX_DICE_PER_TERM: u16 = 100;
+const MAX_SIDES: u16 = 1000;
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+    EnumIter,
+    EnumString,
+    Display,
+)]
+#[strum(ascii_case_insensitive)]
+pub enum Dice {
+    D4,
+    D6,
+    D8,
+    D10,
+    D12,
+    D20,
+    D100,
+}
+
+impl Dice {
+    pub fn sides(&self) -> u16 {
+        match self {
+            Dice::D4 => 4,
+            Dice::D6 => 6,
+            Dice::D8 => 8,
+            Dice::D10 => 10,
+            Dice::D12 => 12,
+            Dice::D20 => 20,
+            Dice::D100 => 100,
+        }
+    }
+
+    /// The fixed value the PHB offers in place of rolling, e.g. 5 for a d8.
+    pub fn average(&self) -> u16 {
+        self.sides() / 2 + 1
+    }
+
+    pub fn expression(&self, count: u16) -> DiceExpression {
+        DiceExpression {
+            terms: vec![DiceTerm {
+                count,
+                sides: self.sides(),
+                keep: None,
+                negative: false,
+            }],
+            modifier: 0,
+            mode: RollMode::Normal,
+            label: None,
+        }
+    }
+}
+
+impl From<Dice> for DiceExpression {
+    fn from(dice: Dice) -> Self {
+        dice.expression(1)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Keep {
+    Highest(u16),
+    Lowest(u16),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RollMode {
+    Normal,
+    Advantage,
+    Disadvantage,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiceTerm {
+    pub count: u16,
+    pub sides: u16,
+    pub keep: Option<Keep>,
+    pub negative: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiceExpression {
+    pub terms: Vec<DiceTerm>,
+    pub modifier: i32,
+    pub mode: RollMode,
+    pub label: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum DiceError {
+    Empty,
+    InvalidTerm(String),
+    TooManyDice(u16),
+    InvalidSides(u16),
+    InvalidKeep(String),
+    ConflictingModes,
+}
+
+impl fmt::Display for DiceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DiceError::Empty => write!(f, "No dice expression was given"),
+            DiceError::InvalidTerm(term) => write!(f, "'{}' is not a valid dice term", term),
+            DiceError::TooManyDice(count) => write!(
+                f,
+                "Cannot roll {} dice at once (maximum {})",
+                count, MAX_DICE_PER_TERM
+            ),
+            DiceError::InvalidSides(sides) => write!(f, "A die cannot have {} sides", sides),
+            DiceError::InvalidKeep(term) => {
+                write!(f, "'{}' keeps or drops more dice than are rolled", term)
+            }
+            DiceError::ConflictingModes => {
+                write!(f, "A roll cannot have both advantage and disadvantage")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DiceError {}
+
+fn parse_term(term: &str, negative: bool) -> Result<DiceTerm, DiceError> {
+    let lower = term.to_lowercase();
+    let invalid = || DiceError::InvalidTerm(term.to_owned());
+
+    let (count, rest) = lower.split_once('d').ok_or_else(invalid)?;
+    let count: u16 = match count {
+        "" => 1,
+        n => n.parse().map_err(|_| invalid())?,
+    };
+
+    let keep_at = rest.find(['k', 'd']);
+    let (sides, keep) = match keep_at {
+        Some(i) => rest.split_at(i),
+        None => (rest, ""),
+    };
+
+    let sides: u16 = match sides {
+        "%" => 100,
+        n => n.parse().map_err(|_| invalid())?,
+    };
+
+    if count == 0 || count > MAX_DICE_PER_TERM {
+        return Err(DiceError::TooManyDice(count));
+    }
+    if sides == 0 || sides > MAX_SIDES {
+        return Err(DiceError::InvalidSides(sides));
+    }
+
+    let keep = if keep.is_empty() {
+        None
+    } else {
+        let digits_at = keep
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(keep.len());
+        let (kind, amount) = keep.split_at(digits_at);
+        let amount: u16 = match amount {
+            "" => 1,
+            n => n.parse().map_err(|_| invalid())?,
+        };
+        if amount > count {
+            return Err(DiceError::InvalidKeep(term.to_owned()));
+        }
+        Some(match kind {
+            "kh" | "k" => Keep::Highest(amount),
+            "kl" => Keep::Lowest(amount),
+            "dl" | "d" => Keep::Highest(count - amount),
+            "dh" => Keep::Lowest(count - amount),
+            _ => return Err(invalid()),
+        })
+    };
+
+    Ok(DiceTerm {
+        count,
+        sides,
+        keep,
+        negative,
+    })
+}
+
+impl FromStr for DiceExpression {
+    type Err = DiceError;
+
+    /// Parses expressions such as `2d6+3`, `1d20+5 adv`, `4d6kh3` or `8d6 fire`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut arithmetic = String::new();
+        let mut mode = RollMode::Normal;
+        let mut label: Vec<&str> = Vec::new();
+
+        for word in s.split_whitespace() {
+            let new_mode = match word.to_lowercase().as_str() {
+                "adv" | "advantage" => Some(RollMode::Advantage),
+                "dis" | "disadv" | "disadvantage" => Some(RollMode::Disadvantage),
+                _ => None,
+            };
+            match new_mode {
+                Some(m) => {
+                    if mode != RollMode::Normal && mode != m {
+                        return Err(DiceError::ConflictingModes);
+                    }
+                    mode = m;
+                }
+                None => {
+                    if word.chars().all(|c| c.is_alphabetic()) && !word.eq_ignore_ascii_case("d") {
+                        label.push(word);
+                    } else {
+                        arithmetic.push_str(word);
+                    }
+                }
+            }
+        }
+
+        if arithmetic.is_empty() {
+            return Err(DiceError::Empty);
+        }
+
+        let mut terms = Vec::new();
+        let mut modifier: i32 = 0;
+        let mut negative = false;
+        let mut current = String::new();
+
+        let mut flush = |current: &mut String, negative: bool| -> Result<(), DiceError> {
+            if current.is_empty() {
+                return Err(DiceError::InvalidTerm(arithmetic.clone()));
+            }
+            if current.chars().all(|c| c.is_ascii_digit()) {
+                let value: i32 = current
+                    .parse()
+                    .map_err(|_| DiceError::InvalidTerm(current.clone()))?;
+                modifier += if negative { -value } else { value };
+            } else {
+                terms.push(parse_term(current, negative)?);
+            }
+            current.clear();
+            Ok(())
+        };
+
+        for (i, c) in arithmetic.chars().enumerate() {
+            match c {
+                '+' | '-' => {
+                    if i > 0 {
+                        flush(&mut current, negative)?;
+                    }
+                    negative = c == '-';
+                }
+                _ => current.push(c),
+            }
+        }
+        flush(&mut current, negative)?;
+
+        Ok(DiceExpression {
+            terms,
+            modifier,
+            mode,
+            label: match label.is_empty() {
+                true => None,
+                false => Some(label.join(" ")),
+            },
+        })
+    }
+}
+
+fn fmt_modifier(modifier: i32) -> String {
+    match modifier.cmp(&0) {
+        Ordering::Greater => format!("+{}", modifier),
+        Ordering::Less => format!("{}", modifier),
+        Ordering::Equal => String::from(""),
+    }
+}
+
+impl fmt::Display for DiceExpression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, term) in self.terms.iter().enumerate() {
+            if term.negative {
+                write!(f, "-")?;
+            } else if i > 0 {
+                write!(f, "+")?;
+            }
+            write!(f, "{}d{}", term.count, term.sides)?;
+            match term.keep {
+                Some(Keep::Highest(n)) if n != term.count => write!(f, "kh{}", n)?,
+                Some(Keep::Lowest(n)) if n != term.count => write!(f, "kl{}", n)?,
+                _ => {}
+            }
+        }
+        if self.terms.is_empty() {
+            write!(f, "{}", self.modifier)?;
+        } else {
+            write!(f, "{}", fmt_modifier(self.modifier))?;
+        }
+        match self.mode {
+            RollMode::Advantage => write!(f, " adv")?,
+            RollMode::Disadvantage => write!(f, " dis")?,
+            RollMode::Normal => {}
+        }
+        if let Some(label) = &self.label {
+            write!(f, " {}", label)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DieRoll {
+    pub sides: u16,
+    pub value: u16,
+    pub kept: bool,
+    pub negative: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RollResult {
+    pub expression: DiceExpression,
+    pub dice: Vec<DieRoll>,
+    pub modifier: i32,
+    pub total: i32,
+}
+
+fn roll_terms<R: Rng + ?Sized>(terms: &[DiceTerm], rng: &mut R) -> Vec<DieRoll> {
+    let mut dice = Vec::new();
+    for term in terms {
+        let mut rolls: Vec<DieRoll> = (0..term.count)
+            .map(|_| DieRoll {
+                sides: term.sides,
+                value: rng.gen_range(1..=term.sides),
+                kept: true,
+                negative: term.negative,
+            })
+            .collect();
+
+        if let Some(keep) = term.keep {
+            let mut order: Vec<usize> = (0..rolls.len()).collect();
+            let amount = match keep {
+                Keep::Highest(n) => {
+                    order.sort_by(|a, b| rolls[*b].value.cmp(&rolls[*a].value));
+                    n
+                }
+                Keep::Lowest(n) => {
+                    order.sort_by(|a, b| rolls[*a].value.cmp(&rolls[*b].value));
+                    n
+                }
+            };
+            for i in order.into_iter().skip(amount as usize) {
+                rolls[i].kept = false;
+            }
+        }
+        dice.append(&mut rolls);
+    }
+    dice
+}
+
+fn sum_kept(dice: &[DieRoll]) -> i32 {
+    dice.iter()
+        .filter(|d| d.kept)
+        .map(|d| match d.negative {
+            true => -(d.value as i32),
+            false => d.value as i32,
+        })
+        .sum()
+}
+
+impl DiceExpression {
+    pub fn with_modifier(mut self, modifier: i32) -> Self {
+        self.modifier += modifier;
+        self
+    }
+
+    pub fn with_mode(mut self, mode: RollMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Doubles the number of dice for a critical hit; the modifier is still added once.
+    pub fn critical(mut self) -> Self {
+        for term in self.terms.iter_mut() {
+            term.count *= 2;
+            term.keep = match term.keep {
+                Some(Keep::Highest(n)) => Some(Keep::Highest(n * 2)),
+                Some(Keep::Lowest(n)) => Some(Keep::Lowest(n * 2)),
+                None => None,
+            };
+        }
+        self
+    }
+
+    /// The average result rounded down, as stat blocks print it, e.g. 7 for
+    /// `2d6`. Keep and roll modes are ignored.
+    pub fn average(&self) -> i32 {
+        let doubled: i32 = self
+            .terms
+            .iter()
+            .map(|term| {
+                let sum = term.count as i32 * (term.sides as i32 + 1);
+                if term.negative {
+                    -sum
+                } else {
+                    sum
+                }
+            })
+            .sum();
+        doubled.div_euclid(2) + self.modifier
+    }
+
+    /// With advantage or disadvantage the dice are rolled twice and the worse
+    /// set is kept in the result, marked as dropped.
+    pub fn roll<R: Rng + ?Sized>(&self, rng: &mut R) -> RollResult {
+        let mut dice = roll_terms(&self.terms, rng);
+
+        if self.mode != RollMode::Normal {
+            let mut second = roll_terms(&self.terms, rng);
+            let first_total = sum_kept(&dice);
+            let second_total = sum_kept(&second);
+            let keep_second = match self.mode {
+                RollMode::Advantage => second_total > first_total,
+                _ => second_total < first_total,
+            };
+            let dropped = match keep_second {
+                true => &mut dice,
+                false => &mut second,
+            };
+            for die in dropped.iter_mut() {
+                die.kept = false;
+            }
+            dice.append(&mut second);
+        }
+
+        RollResult {
+            expression: self.clone(),
+            total: sum_kept(&dice) + self.modifier,
+            dice,
+            modifier: self.modifier,
+        }
+    }
+}
+
+impl RollResult {
+    /// The face showing on the kept d20, if this was a d20 roll.
+    pub fn natural(&self) -> Option<u16> {
+        self.dice
+            .iter()
+            .find(|d| d.kept && d.sides == 20)
+            .map(|d| d.value)
+    }
+
+    pub fn is_critical(&self) -> bool {
+        self.natural() == Some(20)
+    }
+
+    pub fn is_fumble(&self) -> bool {
+        self.natural() == Some(1)
+    }
+}
+
+impl fmt::Display for RollResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let faces: Vec<String> = self
+            .dice
+            .iter()
+            .map(|d| {
+                let sign = if d.negative { "-" } else { "" };
+                match d.kept {
+                    true => format!("{}{}", sign, d.value),
+                    false => format!("({}{})", sign, d.value),
+                }
+            })
+            .collect();
+        write!(
+            f,
+            "{}: [{}]{} = {}",
+            self.expression,
+            faces.join(", "),
+            fmt_modifier(self.modifier),
+            self.total
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn parses_modifiers_modes_and_labels() {
+        let expr: DiceExpression = "1d20+5 adv".parse().unwrap();
+        assert_eq!(expr.terms.len(), 1);
+        assert_eq!(expr.modifier, 5);
+        assert_eq!(expr.mode, RollMode::Advantage);
+
+        let expr: DiceExpression = "8d6 fire".parse().unwrap();
+        assert_eq!(expr.terms[0].count, 8);
+        assert_eq!(expr.label, Some(String::from("fire")));
+
+        let expr: DiceExpression = "2d6 + 1d4 - 2".parse().unwrap();
+        assert_eq!(expr.terms.len(), 2);
+        assert_eq!(expr.modifier, -2);
+    }
+
+    #[test]
+    fn parses_keep_and_drop() {
+        let expr: DiceExpression = "4d6kh3".parse().unwrap();
+        assert_eq!(expr.terms[0].keep, Some(Keep::Highest(3)));
+        let expr: DiceExpression = "4d6dl1".parse().unwrap();
+        assert_eq!(expr.terms[0].keep, Some(Keep::Highest(3)));
+        let expr: DiceExpression = "2d20kl1".parse().unwrap();
+        assert_eq!(expr.terms[0].keep, Some(Keep::Lowest(1)));
+    }
+
+    #[test]
+    fn critical_doubles_dice_but_not_modifier() {
+        let expr: DiceExpression = "2d6+1d4+3".parse().unwrap();
+        let crit = expr.critical();
+        assert_eq!(crit.terms[0].count, 4);
+        assert_eq!(crit.terms[1].count, 2);
+        assert_eq!(crit.modifier, 3);
+    }
+
+    #[test]
+    fn rejects_bad_expressions() {
+        assert_eq!("".parse::<DiceExpression>(), Err(DiceError::Empty));
+        assert!("2d".parse::<DiceExpression>().is_err());
+        assert!("3d6kh4".parse::<DiceExpression>().is_err());
+        assert!("1d20 adv dis".parse::<DiceExpression>().is_err());
+        assert!("1000d6".parse::<DiceExpression>().is_err());
+    }
+
+    #[test]
+    fn keeps_highest_dice() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let expr: DiceExpression = "4d6kh3".parse().unwrap();
+        for _ in 0..50 {
+            let result = expr.roll(&mut rng);
+            assert_eq!(result.dice.len(), 4);
+            assert_eq!(result.dice.iter().filter(|d| d.kept).count(), 3);
+            let dropped = result.dice.iter().find(|d| !d.kept).unwrap().value;
+            assert!(result
+                .dice
+                .iter()
+                .filter(|d| d.kept)
+                .all(|d| d.value >= dropped));
+            assert!((3..=18).contains(&result.total));
+        }
+    }
+
+    #[test]
+    fn advantage_keeps_the_better_roll() {
+        let mut rng = StdRng::seed_from_u64(11);
+        let expr: DiceExpression = "1d20+2 adv".parse().unwrap();
+        for _ in 0..50 {
+            let result = expr.roll(&mut rng);
+            assert_eq!(result.dice.len(), 2);
+            let kept = result.dice.iter().find(|d| d.kept).unwrap().value;
+            let dropped = result.dice.iter().find(|d| !d.kept).unwrap().value;
+            assert!(kept >= dropped);
+            assert_eq!(result.total, kept as i32 + 2);
+        }
+    }
+}
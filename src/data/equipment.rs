@@ -0,0 +1,576 @@
+use strum::IntoEnumIterator;
+use strum_macros::Display;
+
+use super::armor::Armor;
+use super::background::Background;
+use super::classes::Class;
+use super::dice::{Dice, DiceExpression};
+use super::gear::Gear;
+use super::inventory::{Item, ItemKind};
+use super::weapons::{Weapon, WeaponCategory};
+
+/// The equipment packs classes start with, which are unpacked into the
+/// inventory.
+#[derive(Debug, Clone, Copy, PartialEq, Display)]
+pub enum Pack {
+    #[strum(serialize = "Burglar's Pack")]
+    Burglars,
+    #[strum(serialize = "Diplomat's Pack")]
+    Diplomats,
+    #[strum(serialize = "Dungeoneer's Pack")]
+    Dungeoneers,
+    #[strum(serialize = "Entertainer's Pack")]
+    Entertainers,
+    #[strum(serialize = "Explorer's Pack")]
+    Explorers,
+    #[strum(serialize = "Priest's Pack")]
+    Priests,
+    #[strum(serialize = "Scholar's Pack")]
+    Scholars,
+}
+
+impl Pack {
+    pub fn contents(&self) -> Vec<Item> {
+        match self {
+            Pack::Burglars => vec![
+                gear(Gear::Backpack, 1),
+                gear(Gear::BallBearings, 1),
+                other("String (10 feet)", 1, 0.0),
+                gear(Gear::Bell, 1),
+                gear(Gear::Candle, 5),
+                gear(Gear::Crowbar, 1),
+                gear(Gear::Hammer, 1),
+                gear(Gear::Piton, 10),
+                gear(Gear::HoodedLantern, 1),
+                gear(Gear::Oil, 2),
+                gear(Gear::Rations, 5),
+                gear(Gear::Tinderbox, 1),
+                gear(Gear::Waterskin, 1),
+                gear(Gear::Rope, 1),
+            ],
+            Pack::Diplomats => vec![
+                gear(Gear::Chest, 1),
+                gear(Gear::Case, 2),
+                gear(Gear::FineClothes, 1),
+                gear(Gear::Ink, 1),
+                gear(Gear::InkPen, 1),
+                gear(Gear::Lamp, 1),
+                gear(Gear::Oil, 2),
+                gear(Gear::Paper, 5),
+                gear(Gear::Perfume, 1),
+                gear(Gear::SealingWax, 1),
+                gear(Gear::Soap, 1),
+            ],
+            Pack::Dungeoneers => vec![
+                gear(Gear::Backpack, 1),
+                gear(Gear::Crowbar, 1),
+                gear(Gear::Hammer, 1),
+                gear(Gear::Piton, 10),
+                gear(Gear::Torch, 10),
+                gear(Gear::Tinderbox, 1),
+                gear(Gear::Rations, 10),
+                gear(Gear::Waterskin, 1),
+                gear(Gear::Rope, 1),
+            ],
+            Pack::Entertainers => vec![
+                gear(Gear::Backpack, 1),
+                gear(Gear::Bedroll, 1),
+                gear(Gear::Costume, 2),
+                gear(Gear::Candle, 5),
+                gear(Gear::Rations, 5),
+                gear(Gear::Waterskin, 1),
+                gear(Gear::DisguiseKit, 1),
+            ],
+            Pack::Explorers => vec![
+                gear(Gear::Backpack, 1),
+                gear(Gear::Bedroll, 1),
+                gear(Gear::MessKit, 1),
+                gear(Gear::Tinderbox, 1),
+                gear(Gear::Torch, 10),
+                gear(Gear::Rations, 10),
+                gear(Gear::Waterskin, 1),
+                gear(Gear::Rope, 1),
+            ],
+            Pack::Priests => vec![
+                gear(Gear::Backpack, 1),
+                gear(Gear::Blanket, 1),
+                gear(Gear::Candle, 10),
+                gear(Gear::Tinderbox, 1),
+                other("Alms Box", 1, 0.0),
+                gear(Gear::Incense, 2),
+                other("Censer", 1, 0.0),
+                gear(Gear::Vestments, 1),
+                gear(Gear::Rations, 2),
+                gear(Gear::Waterskin, 1),
+            ],
+            Pack::Scholars => vec![
+                gear(Gear::Backpack, 1),
+                gear(Gear::Book, 1),
+                gear(Gear::Ink, 1),
+                gear(Gear::InkPen, 1),
+                gear(Gear::Parchment, 10),
+                other("Little Bag of Sand", 1, 1.0),
+                other("Small Knife", 1, 0.5),
+            ],
+        }
+    }
+}
+
+/// `count` bundles of the gear, e.g. `gear(Gear::Arrows, 1)` for 20 arrows.
+fn gear(gear: Gear, count: u32) -> Item {
+    let mut item = Item::from(gear);
+    item.quantity *= count;
+    item
+}
+
+fn other(name: &str, quantity: u32, weight: f32) -> Item {
+    Item::new(name, ItemKind::Gear, quantity, weight)
+}
+
+/// Part of a class's or background's starting equipment.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Equipment {
+    Weapon(Weapon, u32),
+    /// Any simple or martial weapon, or only a melee one.
+    AnyWeapon {
+        category: WeaponCategory,
+        melee: bool,
+    },
+    Armor(Armor),
+    Shield,
+    /// Bundles of the gear, e.g. `Gear(Gear::Arrows, 1)` for 20 arrows.
+    Gear(Gear, u32),
+    Pack(Pack),
+    /// Any musical instrument.
+    Instrument,
+    /// Any artisan's tools.
+    ArtisansTools,
+    /// Something without a price of its own, with its weight in pounds.
+    Other(&'static str, f32),
+    /// Gold pieces.
+    Gold(u32),
+}
+
+impl Equipment {
+    /// E.g. "2 × Handaxe" or "any martial melee weapon".
+    pub fn describe(&self) -> String {
+        match self {
+            Equipment::Weapon(weapon, 1) => weapon.to_string(),
+            Equipment::Weapon(weapon, count) => format!("{} × {}", count, weapon),
+            Equipment::AnyWeapon { category, melee } => format!(
+                "any {}{} weapon",
+                category.to_string().to_lowercase(),
+                match melee {
+                    true => " melee",
+                    false => "",
+                }
+            ),
+            Equipment::Armor(armor) => armor.to_string(),
+            Equipment::Shield => String::from("Shield"),
+            Equipment::Gear(item, count) => match item.bundle() * count {
+                1 => item.to_string(),
+                quantity => format!("{} × {}", quantity, item),
+            },
+            Equipment::Pack(pack) => pack.to_string(),
+            Equipment::Instrument => String::from("a musical instrument"),
+            Equipment::ArtisansTools => String::from("artisan's tools"),
+            Equipment::Other(name, _) => String::from(*name),
+            Equipment::Gold(gp) => format!("{} gp", gp),
+        }
+    }
+
+    /// The items to pick one of, for equipment such as "any simple weapon";
+    /// empty when there is nothing to choose.
+    pub fn options(&self) -> Vec<Item> {
+        match self {
+            Equipment::AnyWeapon { category, melee } => Weapon::iter()
+                .filter(|w| w.category() == *category && !(*melee && w.is_ranged()))
+                .map(Item::from)
+                .collect(),
+            Equipment::Instrument => Gear::iter()
+                .filter(|g| g.is_instrument())
+                .map(Item::from)
+                .collect(),
+            Equipment::ArtisansTools => Gear::iter()
+                .filter(|g| g.is_artisans_tools())
+                .map(Item::from)
+                .collect(),
+            _ => vec![],
+        }
+    }
+
+    /// The items given, once any choice among `options` has been made.
+    pub fn items(&self) -> Vec<Item> {
+        match self {
+            Equipment::Weapon(weapon, count) => {
+                let mut item = Item::from(*weapon);
+                item.quantity = *count;
+                vec![item]
+            }
+            Equipment::Armor(armor) => vec![Item::from(*armor)],
+            Equipment::Shield => vec![Item::shield()],
+            Equipment::Gear(item, count) => vec![gear(*item, *count)],
+            Equipment::Pack(pack) => pack.contents(),
+            Equipment::Other(name, weight) => vec![other(name, 1, *weight)],
+            _ => vec![],
+        }
+    }
+}
+
+/// One line of starting equipment: a choice between options such as
+/// "(a) a greataxe or (b) any martial melee weapon", or a single option that
+/// is given outright.
+pub type Choice = Vec<Vec<Equipment>>;
+
+/// The starting equipment of the Player's Handbook (and Tasha's Cauldron of
+/// Everything for the Artificer).
+pub fn class_equipment(class: &Class) -> Vec<Choice> {
+    let simple = Equipment::AnyWeapon {
+        category: WeaponCategory::Simple,
+        melee: false,
+    };
+    let simple_melee = Equipment::AnyWeapon {
+        category: WeaponCategory::Simple,
+        melee: true,
+    };
+    let martial = Equipment::AnyWeapon {
+        category: WeaponCategory::Martial,
+        melee: false,
+    };
+    let martial_melee = Equipment::AnyWeapon {
+        category: WeaponCategory::Martial,
+        melee: true,
+    };
+    let crossbow = vec![
+        Equipment::Weapon(Weapon::LightCrossbow, 1),
+        Equipment::Gear(Gear::CrossbowBolts, 1),
+    ];
+    let spellcasting = vec![
+        vec![Equipment::Gear(Gear::ComponentPouch, 1)],
+        vec![Equipment::Gear(Gear::ArcaneFocus, 1)],
+    ];
+    let packs = |options: &[Pack]| options.iter().map(|p| vec![Equipment::Pack(*p)]).collect();
+
+    match class {
+        Class::Artificer(_) => vec![
+            vec![vec![simple, simple]],
+            vec![crossbow],
+            vec![
+                vec![Equipment::Armor(Armor::StuddedLeather)],
+                vec![Equipment::Armor(Armor::ScaleMail)],
+            ],
+            vec![vec![
+                Equipment::Gear(Gear::ThievesTools, 1),
+                Equipment::Pack(Pack::Dungeoneers),
+            ]],
+        ],
+        Class::Barbarian(_) => vec![
+            vec![
+                vec![Equipment::Weapon(Weapon::Greataxe, 1)],
+                vec![martial_melee],
+            ],
+            vec![vec![Equipment::Weapon(Weapon::Handaxe, 2)], vec![simple]],
+            vec![vec![
+                Equipment::Pack(Pack::Explorers),
+                Equipment::Weapon(Weapon::Javelin, 4),
+            ]],
+        ],
+        Class::Bard(_) => vec![
+            vec![
+                vec![Equipment::Weapon(Weapon::Rapier, 1)],
+                vec![Equipment::Weapon(Weapon::Longsword, 1)],
+                vec![simple],
+            ],
+            packs(&[Pack::Diplomats, Pack::Entertainers]),
+            vec![
+                vec![Equipment::Gear(Gear::Lute, 1)],
+                vec![Equipment::Instrument],
+            ],
+            vec![vec![
+                Equipment::Armor(Armor::Leather),
+                Equipment::Weapon(Weapon::Dagger, 1),
+            ]],
+        ],
+        Class::Cleric(_) => vec![
+            vec![
+                vec![Equipment::Weapon(Weapon::Mace, 1)],
+                vec![Equipment::Weapon(Weapon::Warhammer, 1)],
+            ],
+            vec![
+                vec![Equipment::Armor(Armor::ScaleMail)],
+                vec![Equipment::Armor(Armor::Leather)],
+                vec![Equipment::Armor(Armor::ChainMail)],
+            ],
+            vec![crossbow, vec![simple]],
+            packs(&[Pack::Priests, Pack::Explorers]),
+            vec![vec![
+                Equipment::Shield,
+                Equipment::Gear(Gear::HolySymbol, 1),
+            ]],
+        ],
+        Class::Druid(_) => vec![
+            vec![vec![Equipment::Shield], vec![simple]],
+            vec![
+                vec![Equipment::Weapon(Weapon::Scimitar, 1)],
+                vec![simple_melee],
+            ],
+            vec![vec![
+                Equipment::Armor(Armor::Leather),
+                Equipment::Pack(Pack::Explorers),
+                Equipment::Gear(Gear::DruidicFocus, 1),
+            ]],
+        ],
+        Class::Fighter(_) => vec![
+            vec![
+                vec![Equipment::Armor(Armor::ChainMail)],
+                vec![
+                    Equipment::Armor(Armor::Leather),
+                    Equipment::Weapon(Weapon::Longbow, 1),
+                    Equipment::Gear(Gear::Arrows, 1),
+                ],
+            ],
+            vec![vec![martial, Equipment::Shield], vec![martial, martial]],
+            vec![crossbow, vec![Equipment::Weapon(Weapon::Handaxe, 2)]],
+            packs(&[Pack::Dungeoneers, Pack::Explorers]),
+        ],
+        Class::Monk(_) => vec![
+            vec![vec![Equipment::Weapon(Weapon::Shortsword, 1)], vec![simple]],
+            packs(&[Pack::Dungeoneers, Pack::Explorers]),
+            vec![vec![Equipment::Weapon(Weapon::Dart, 10)]],
+        ],
+        Class::Paladin(_) => vec![
+            vec![vec![martial, Equipment::Shield], vec![martial, martial]],
+            vec![
+                vec![Equipment::Weapon(Weapon::Javelin, 5)],
+                vec![simple_melee],
+            ],
+            packs(&[Pack::Priests, Pack::Explorers]),
+            vec![vec![
+                Equipment::Armor(Armor::ChainMail),
+                Equipment::Gear(Gear::HolySymbol, 1),
+            ]],
+        ],
+        Class::Ranger(_) => vec![
+            vec![
+                vec![Equipment::Armor(Armor::ScaleMail)],
+                vec![Equipment::Armor(Armor::Leather)],
+            ],
+            vec![
+                vec![Equipment::Weapon(Weapon::Shortsword, 2)],
+                vec![simple_melee, simple_melee],
+            ],
+            packs(&[Pack::Dungeoneers, Pack::Explorers]),
+            vec![vec![
+                Equipment::Weapon(Weapon::Longbow, 1),
+                Equipment::Gear(Gear::Quiver, 1),
+                Equipment::Gear(Gear::Arrows, 1),
+            ]],
+        ],
+        Class::Rogue(_) => vec![
+            vec![
+                vec![Equipment::Weapon(Weapon::Rapier, 1)],
+                vec![Equipment::Weapon(Weapon::Shortsword, 1)],
+            ],
+            vec![
+                vec![
+                    Equipment::Weapon(Weapon::Shortbow, 1),
+                    Equipment::Gear(Gear::Quiver, 1),
+                    Equipment::Gear(Gear::Arrows, 1),
+                ],
+                vec![Equipment::Weapon(Weapon::Shortsword, 1)],
+            ],
+            packs(&[Pack::Burglars, Pack::Dungeoneers, Pack::Explorers]),
+            vec![vec![
+                Equipment::Armor(Armor::Leather),
+                Equipment::Weapon(Weapon::Dagger, 2),
+                Equipment::Gear(Gear::ThievesTools, 1),
+            ]],
+        ],
+        Class::Sorcerer(_) => vec![
+            vec![crossbow, vec![simple]],
+            spellcasting,
+            packs(&[Pack::Dungeoneers, Pack::Explorers]),
+            vec![vec![Equipment::Weapon(Weapon::Dagger, 2)]],
+        ],
+        Class::Warlock(_) => vec![
+            vec![crossbow, vec![simple]],
+            spellcasting,
+            packs(&[Pack::Scholars, Pack::Dungeoneers]),
+            vec![vec![
+                Equipment::Armor(Armor::Leather),
+                simple,
+                Equipment::Weapon(Weapon::Dagger, 2),
+            ]],
+        ],
+        Class::Wizard(_) => vec![
+            vec![
+                vec![Equipment::Weapon(Weapon::Quarterstaff, 1)],
+                vec![Equipment::Weapon(Weapon::Dagger, 1)],
+            ],
+            spellcasting,
+            packs(&[Pack::Scholars, Pack::Explorers]),
+            vec![vec![Equipment::Gear(Gear::Spellbook, 1)]],
+        ],
+    }
+}
+
+/// The equipment each background comes with, including the coins in its pouch.
+pub fn background_equipment(background: &Background) -> Vec<Choice> {
+    let pouch = |gp| vec![vec![Equipment::Gear(Gear::Pouch, 1), Equipment::Gold(gp)]];
+    let given = |equipment: Vec<Equipment>| -> Vec<Choice> {
+        equipment.into_iter().map(|e| vec![vec![e]]).collect()
+    };
+
+    let mut equipment = match background {
+        Background::Acolyte => {
+            let mut equipment = given(vec![Equipment::Gear(Gear::HolySymbol, 1)]);
+            equipment.push(vec![
+                vec![Equipment::Other("Prayer Book", 5.0)],
+                vec![Equipment::Other("Prayer Wheel", 1.0)],
+            ]);
+            equipment.append(&mut given(vec![
+                Equipment::Gear(Gear::Incense, 5),
+                Equipment::Gear(Gear::Vestments, 1),
+                Equipment::Gear(Gear::CommonClothes, 1),
+            ]));
+            equipment
+        }
+        Background::Charlatan => {
+            let mut equipment = given(vec![
+                Equipment::Gear(Gear::FineClothes, 1),
+                Equipment::Gear(Gear::DisguiseKit, 1),
+            ]);
+            equipment.push(vec![
+                vec![Equipment::Other(
+                    "Ten Stoppered Bottles of Colored Liquid",
+                    10.0,
+                )],
+                vec![Equipment::Other("Weighted Dice", 0.0)],
+                vec![Equipment::Other("Deck of Marked Cards", 0.0)],
+                vec![Equipment::Other("Signet Ring of an Imaginary Duke", 0.0)],
+            ]);
+            equipment
+        }
+        Background::Criminal => given(vec![
+            Equipment::Gear(Gear::Crowbar, 1),
+            Equipment::Other("Dark Common Clothes with a Hood", 3.0),
+        ]),
+        Background::Entertainer => given(vec![
+            Equipment::Instrument,
+            Equipment::Other("Favor of an Admirer", 0.0),
+            Equipment::Gear(Gear::Costume, 1),
+        ]),
+        Background::FolkHero => given(vec![
+            Equipment::ArtisansTools,
+            Equipment::Gear(Gear::Shovel, 1),
+            Equipment::Gear(Gear::IronPot, 1),
+            Equipment::Gear(Gear::CommonClothes, 1),
+        ]),
+        Background::GuildArtisan => given(vec![
+            Equipment::ArtisansTools,
+            Equipment::Other("Letter of Introduction from Your Guild", 0.0),
+            Equipment::Gear(Gear::TravelersClothes, 1),
+        ]),
+        Background::Hermit => given(vec![
+            Equipment::Other("Scroll Case Stuffed with Notes", 1.0),
+            Equipment::Other("Winter Blanket", 3.0),
+            Equipment::Gear(Gear::CommonClothes, 1),
+            Equipment::Gear(Gear::HerbalismKit, 1),
+        ]),
+        Background::Noble => given(vec![
+            Equipment::Gear(Gear::FineClothes, 1),
+            Equipment::Other("Signet Ring", 0.0),
+            Equipment::Other("Scroll of Pedigree", 0.0),
+        ]),
+        Background::Outlander => given(vec![
+            Equipment::Weapon(Weapon::Quarterstaff, 1),
+            Equipment::Gear(Gear::HuntingTrap, 1),
+            Equipment::Other("Trophy from an Animal You Killed", 0.0),
+            Equipment::Gear(Gear::TravelersClothes, 1),
+        ]),
+        Background::Sailor => given(vec![
+            Equipment::Weapon(Weapon::Club, 1),
+            Equipment::Other("Silk Rope (50 feet)", 5.0),
+            Equipment::Other("Lucky Charm", 0.0),
+            Equipment::Gear(Gear::CommonClothes, 1),
+        ]),
+        Background::Soldier => {
+            let mut equipment = given(vec![
+                Equipment::Other("Insignia of Rank", 0.0),
+                Equipment::Other("Trophy from a Fallen Enemy", 0.0),
+            ]);
+            equipment.push(vec![
+                vec![Equipment::Gear(Gear::DiceSet, 1)],
+                vec![Equipment::Gear(Gear::PlayingCardSet, 1)],
+            ]);
+            equipment.append(&mut given(vec![Equipment::Gear(Gear::CommonClothes, 1)]));
+            equipment
+        }
+        Background::Urchin => given(vec![
+            Equipment::Other("Small Knife", 0.5),
+            Equipment::Other("Map of Your Home City", 0.0),
+            Equipment::Other("Pet Mouse", 0.0),
+            Equipment::Other("Token to Remember Your Parents By", 0.0),
+            Equipment::Gear(Gear::CommonClothes, 1),
+        ]),
+    };
+    equipment.push(pouch(match background {
+        Background::Hermit => 5,
+        Background::Noble => 25,
+        Background::FolkHero
+        | Background::Outlander
+        | Background::Sailor
+        | Background::Soldier
+        | Background::Urchin => 10,
+        _ => 15,
+    }));
+    equipment
+}
+
+/// The gold a class can start with instead of its and the background's
+/// equipment: the dice to roll and what to multiply them by.
+pub fn starting_gold(class: &Class) -> (DiceExpression, u32) {
+    match class {
+        Class::Barbarian(_) | Class::Druid(_) => (Dice::D4.expression(2), 10),
+        Class::Sorcerer(_) => (Dice::D4.expression(3), 10),
+        Class::Rogue(_) | Class::Warlock(_) | Class::Wizard(_) => (Dice::D4.expression(4), 10),
+        Class::Monk(_) => (Dice::D4.expression(5), 1),
+        _ => (Dice::D4.expression(5), 10),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn starting_equipment_unpacks() {
+        let weight = |items: Vec<Item>| -> f32 { items.iter().map(|i| i.total_weight()).sum() };
+        assert_eq!(weight(Pack::Explorers.contents()), 59.0);
+        assert_eq!(weight(Pack::Dungeoneers.contents()), 61.5);
+
+        let quiver = Equipment::Gear(Gear::Arrows, 1);
+        assert_eq!(quiver.describe(), "20 × Arrows");
+        assert_eq!(quiver.items()[0].quantity, 20);
+
+        let martial_melee = Equipment::AnyWeapon {
+            category: WeaponCategory::Martial,
+            melee: true,
+        };
+        assert_eq!(martial_melee.describe(), "any martial melee weapon");
+        assert_eq!(martial_melee.options().len(), 18);
+        assert!(martial_melee.items().is_empty());
+
+        let (dice, multiplier) = starting_gold(&Class::Monk(None));
+        assert_eq!((dice.to_string().as_str(), multiplier), ("5d4", 1));
+
+        // Every class and background gives something from each line.
+        for class in Class::iter() {
+            assert!(class_equipment(&class).iter().all(|c| !c.is_empty()));
+        }
+        for background in Background::iter() {
+            let equipment = background_equipment(&background);
+            assert!(equipment.iter().all(|c| !c.is_empty()));
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+use strum_macros::{Display, EnumIter, EnumString};
+
+use super::character::Character;
+use super::rng::GameRng;
+use super::skills::{choose_skills, Skill};
+use super::utils::{choose_value, pretty_print, BLUE};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, EnumIter, EnumString, Display)]
+#[strum(ascii_case_insensitive, serialize_all = "title_case")]
+pub enum Feat {
+    Alert,
+    Durable,
+    GreatWeaponMaster,
+    Lucky,
+    Mobile,
+    Observant,
+    Sentinel,
+    Sharpshooter,
+    Skilled,
+    Tough,
+    WarCaster,
+}
+
+impl Feat {
+    pub fn description(&self) -> &str {
+        match self {
+            Feat::Alert => "+5 to initiative; you can't be surprised while conscious",
+            Feat::Durable => "Hit dice spent to recover regain at least twice your Con modifier",
+            Feat::GreatWeaponMaster => "Bonus attack on crits and kills; -5 to hit for +10 damage with heavy weapons",
+            Feat::Lucky => "3 luck points per long rest to reroll a d20",
+            Feat::Mobile => "+10 speed; Dash ignores difficult terrain; no opportunity attacks from creatures you attack",
+            Feat::Observant => "+5 to passive Perception and Investigation",
+            Feat::Sentinel => "Opportunity attacks stop movement; react when an ally is attacked",
+            Feat::Sharpshooter => "Ignore cover and long range; -5 to hit for +10 damage with ranged weapons",
+            Feat::Skilled => "Gain proficiency in three skills",
+            Feat::Tough => "+2 hit points per level",
+            Feat::WarCaster => "Advantage on concentration saves; cast spells as opportunity attacks",
+        }
+    }
+}
+
+/// Lets the player pick a feat they don't already have and applies it to the character.
+pub fn take_feat(character: &mut Character, rng: &mut GameRng) {
+    let available: Vec<String> = Feat::iter()
+        .filter(|feat| !character.feats.contains(feat))
+        .map(|feat| feat.to_string())
+        .collect();
+    let feat: Feat = choose_value("Which feat would you like to take?", &available, rng);
+    pretty_print(feat.description(), BLUE, true);
+
+    if feat == Feat::Skilled {
+        let all: Vec<Skill> = Skill::iter().collect();
+        let mut skills = choose_skills("Choose a skill", 3, &all, &character.skills);
+        character.skills.append(&mut skills);
+    }
+    character.gain_feat(feat);
+}
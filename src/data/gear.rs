@@ -0,0 +1,311 @@
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumIter, EnumString};
+
+/// Adventuring gear, tools and ammunition from the Player's Handbook.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, EnumIter, EnumString, Display)]
+#[strum(ascii_case_insensitive, serialize_all = "title_case")]
+pub enum Gear {
+    #[strum(serialize = "Alchemist's Supplies")]
+    AlchemistsSupplies,
+    #[strum(serialize = "Arcane Focus")]
+    ArcaneFocus,
+    Arrows,
+    Backpack,
+    Bagpipes,
+    #[strum(serialize = "Ball Bearings")]
+    BallBearings,
+    Bedroll,
+    Bell,
+    Blanket,
+    #[strum(serialize = "Blowgun Needles")]
+    BlowgunNeedles,
+    Book,
+    #[strum(serialize = "Brewer's Supplies")]
+    BrewersSupplies,
+    Caltrops,
+    Candle,
+    #[strum(serialize = "Carpenter's Tools")]
+    CarpentersTools,
+    #[strum(serialize = "Case (map or scroll)")]
+    Case,
+    Chest,
+    #[strum(serialize = "Clothes (common)")]
+    CommonClothes,
+    #[strum(serialize = "Clothes (costume)")]
+    Costume,
+    #[strum(serialize = "Clothes (fine)")]
+    FineClothes,
+    #[strum(serialize = "Clothes (traveler's)")]
+    TravelersClothes,
+    #[strum(serialize = "Component Pouch")]
+    ComponentPouch,
+    #[strum(serialize = "Cook's Utensils")]
+    CooksUtensils,
+    #[strum(serialize = "Crossbow Bolts")]
+    CrossbowBolts,
+    Crowbar,
+    #[strum(serialize = "Dice Set")]
+    DiceSet,
+    #[strum(serialize = "Disguise Kit")]
+    DisguiseKit,
+    #[strum(serialize = "Druidic Focus")]
+    DruidicFocus,
+    Drum,
+    Dulcimer,
+    Flute,
+    Hammer,
+    #[strum(serialize = "Healer's Kit")]
+    HealersKit,
+    #[strum(serialize = "Herbalism Kit")]
+    HerbalismKit,
+    #[strum(serialize = "Holy Symbol")]
+    HolySymbol,
+    #[strum(serialize = "Hooded Lantern")]
+    HoodedLantern,
+    Horn,
+    #[strum(serialize = "Hunting Trap")]
+    HuntingTrap,
+    Incense,
+    Ink,
+    #[strum(serialize = "Ink Pen")]
+    InkPen,
+    #[strum(serialize = "Iron Pot")]
+    IronPot,
+    Lamp,
+    #[strum(serialize = "Leatherworker's Tools")]
+    LeatherworkersTools,
+    Lute,
+    Lyre,
+    #[strum(serialize = "Mason's Tools")]
+    MasonsTools,
+    #[strum(serialize = "Mess Kit")]
+    MessKit,
+    Oil,
+    #[strum(serialize = "Pan Flute")]
+    PanFlute,
+    Paper,
+    Parchment,
+    Perfume,
+    Piton,
+    #[strum(serialize = "Playing Card Set")]
+    PlayingCardSet,
+    #[strum(serialize = "Potion of Healing")]
+    PotionOfHealing,
+    Pouch,
+    Quiver,
+    Rations,
+    #[strum(serialize = "Rope (50 feet)")]
+    Rope,
+    #[strum(serialize = "Sealing Wax")]
+    SealingWax,
+    Shawm,
+    Shovel,
+    #[strum(serialize = "Signal Whistle")]
+    SignalWhistle,
+    #[strum(serialize = "Sling Bullets")]
+    SlingBullets,
+    #[strum(serialize = "Smith's Tools")]
+    SmithsTools,
+    Soap,
+    Spellbook,
+    #[strum(serialize = "Thieves' Tools")]
+    ThievesTools,
+    Tinderbox,
+    #[strum(serialize = "Tinker's Tools")]
+    TinkersTools,
+    Torch,
+    Vestments,
+    Viol,
+    Waterskin,
+    #[strum(serialize = "Weaver's Tools")]
+    WeaversTools,
+    #[strum(serialize = "Woodcarver's Tools")]
+    WoodcarversTools,
+}
+
+impl Gear {
+    pub fn is_instrument(&self) -> bool {
+        matches!(
+            self,
+            Gear::Bagpipes
+                | Gear::Drum
+                | Gear::Dulcimer
+                | Gear::Flute
+                | Gear::Horn
+                | Gear::Lute
+                | Gear::Lyre
+                | Gear::PanFlute
+                | Gear::Shawm
+                | Gear::Viol
+        )
+    }
+
+    pub fn is_artisans_tools(&self) -> bool {
+        matches!(
+            self,
+            Gear::AlchemistsSupplies
+                | Gear::BrewersSupplies
+                | Gear::CarpentersTools
+                | Gear::CooksUtensils
+                | Gear::LeatherworkersTools
+                | Gear::MasonsTools
+                | Gear::SmithsTools
+                | Gear::TinkersTools
+                | Gear::WeaversTools
+                | Gear::WoodcarversTools
+        )
+    }
+
+    /// How many come together for the price, e.g. 20 arrows for 1 gp.
+    pub fn bundle(&self) -> u32 {
+        match self {
+            Gear::Arrows | Gear::CrossbowBolts | Gear::SlingBullets | Gear::Caltrops => 20,
+            Gear::BlowgunNeedles => 50,
+            _ => 1,
+        }
+    }
+
+    /// Price of a bundle in copper pieces.
+    pub fn price(&self) -> u32 {
+        match self {
+            Gear::Candle | Gear::Torch => 1,
+            Gear::InkPen | Gear::Soap => 2,
+            Gear::SlingBullets => 4,
+            Gear::Piton | Gear::SignalWhistle => 5,
+            Gear::DiceSet | Gear::Incense | Gear::Oil | Gear::Parchment => 10,
+            Gear::MessKit | Gear::Paper | Gear::Waterskin => 20,
+            Gear::Blanket
+            | Gear::CommonClothes
+            | Gear::Lamp
+            | Gear::PlayingCardSet
+            | Gear::Pouch
+            | Gear::Rations
+            | Gear::SealingWax
+            | Gear::Tinderbox => 50,
+            Gear::Arrows
+            | Gear::BallBearings
+            | Gear::Bedroll
+            | Gear::Bell
+            | Gear::BlowgunNeedles
+            | Gear::Caltrops
+            | Gear::Case
+            | Gear::CooksUtensils
+            | Gear::CrossbowBolts
+            | Gear::DruidicFocus
+            | Gear::Hammer
+            | Gear::Quiver
+            | Gear::Rope
+            | Gear::Vestments
+            | Gear::WeaversTools
+            | Gear::WoodcarversTools => 100,
+            Gear::Backpack
+            | Gear::Crowbar
+            | Gear::Flute
+            | Gear::IronPot
+            | Gear::Shawm
+            | Gear::Shovel
+            | Gear::TravelersClothes => 200,
+            Gear::Horn => 300,
+            Gear::Chest
+            | Gear::Costume
+            | Gear::HealersKit
+            | Gear::HerbalismKit
+            | Gear::HolySymbol
+            | Gear::HoodedLantern
+            | Gear::HuntingTrap
+            | Gear::LeatherworkersTools
+            | Gear::Perfume => 500,
+            Gear::Drum => 600,
+            Gear::CarpentersTools => 800,
+            Gear::ArcaneFocus | Gear::Ink | Gear::MasonsTools => 1_000,
+            Gear::PanFlute => 1_200,
+            Gear::FineClothes => 1_500,
+            Gear::BrewersSupplies | Gear::SmithsTools => 2_000,
+            Gear::Book
+            | Gear::ComponentPouch
+            | Gear::DisguiseKit
+            | Gear::Dulcimer
+            | Gear::ThievesTools => 2_500,
+            Gear::Bagpipes | Gear::Lyre | Gear::Viol => 3_000,
+            Gear::Lute => 3_500,
+            Gear::AlchemistsSupplies
+            | Gear::PotionOfHealing
+            | Gear::Spellbook
+            | Gear::TinkersTools => 5_000,
+        }
+    }
+
+    /// Weight in pounds of each one.
+    pub fn weight(&self) -> f32 {
+        match self {
+            Gear::BlowgunNeedles => 0.02,
+            Gear::Arrows => 0.05,
+            Gear::CrossbowBolts | Gear::SlingBullets => 0.075,
+            Gear::Caltrops => 0.1,
+            Gear::Piton => 0.25,
+            Gear::PotionOfHealing => 0.5,
+            Gear::Bell
+            | Gear::Candle
+            | Gear::DiceSet
+            | Gear::DruidicFocus
+            | Gear::Ink
+            | Gear::InkPen
+            | Gear::Paper
+            | Gear::Parchment
+            | Gear::Perfume
+            | Gear::PlayingCardSet
+            | Gear::SealingWax
+            | Gear::SignalWhistle
+            | Gear::Soap => 0.0,
+            Gear::ArcaneFocus
+            | Gear::Case
+            | Gear::Flute
+            | Gear::HolySymbol
+            | Gear::Incense
+            | Gear::Lamp
+            | Gear::MessKit
+            | Gear::Oil
+            | Gear::Pouch
+            | Gear::Quiver
+            | Gear::Shawm
+            | Gear::ThievesTools
+            | Gear::Tinderbox
+            | Gear::Torch
+            | Gear::Viol => 1.0,
+            Gear::BallBearings
+            | Gear::ComponentPouch
+            | Gear::HoodedLantern
+            | Gear::Horn
+            | Gear::Lute
+            | Gear::Lyre
+            | Gear::PanFlute
+            | Gear::Rations => 2.0,
+            Gear::Blanket
+            | Gear::CommonClothes
+            | Gear::DisguiseKit
+            | Gear::Drum
+            | Gear::Hammer
+            | Gear::HealersKit
+            | Gear::HerbalismKit
+            | Gear::Spellbook => 3.0,
+            Gear::Costume | Gear::TravelersClothes | Gear::Vestments => 4.0,
+            Gear::Backpack
+            | Gear::Book
+            | Gear::Crowbar
+            | Gear::LeatherworkersTools
+            | Gear::Shovel
+            | Gear::Waterskin
+            | Gear::WeaversTools
+            | Gear::WoodcarversTools => 5.0,
+            Gear::Bagpipes | Gear::CarpentersTools | Gear::FineClothes => 6.0,
+            Gear::Bedroll => 7.0,
+            Gear::AlchemistsSupplies
+            | Gear::CooksUtensils
+            | Gear::MasonsTools
+            | Gear::SmithsTools => 8.0,
+            Gear::BrewersSupplies => 9.0,
+            Gear::Dulcimer | Gear::IronPot | Gear::Rope | Gear::TinkersTools => 10.0,
+            Gear::Chest | Gear::HuntingTrap => 25.0,
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 use strum_macros::{Display, EnumIter, EnumString};
 
+use super::rng::GameRng;
 use super::utils::{choose_value, Choosable, StringJoin};
 
 #[derive(Debug, PartialEq, Serialize, Deserialize, EnumIter, EnumString, Display)]
@@ -12,10 +13,11 @@ pub enum Gender {
 }
 
 impl Choosable<Gender> for Gender {
-    fn choose() -> Gender {
+    fn choose(rng: &mut GameRng) -> Gender {
         choose_value(
             "What is your character's gender?",
             &Gender::collect_string(), // gender_match_string,
+            rng,
         )
     }
 }
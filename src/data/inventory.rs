@@ -0,0 +1,391 @@
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+use strum_macros::{Display, EnumIter};
+
+use std::str::FromStr;
+
+use super::armor::{Armor, SHIELD_PRICE, SHIELD_WEIGHT};
+use super::gear::Gear;
+use super::weapons::{Weapon, WeaponProperty};
+
+/// Where an equipped item is worn or held.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, EnumIter, Display,
+)]
+#[strum(serialize_all = "title_case")]
+pub enum Slot {
+    Armor,
+    Shield,
+    MainHand,
+    OffHand,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ItemKind {
+    #[default]
+    Gear,
+    Weapon(Weapon),
+    Armor(Armor),
+    Shield,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Item {
+    pub name: String,
+    #[serde(default)]
+    pub kind: ItemKind,
+    pub quantity: u32,
+    /// Pounds for each one.
+    pub weight: f32,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub notes: String,
+    #[serde(default)]
+    pub equipped: Option<Slot>,
+}
+
+impl Item {
+    pub fn new(name: &str, kind: ItemKind, quantity: u32, weight: f32) -> Item {
+        Item {
+            name: String::from(name),
+            kind,
+            quantity,
+            weight,
+            notes: String::new(),
+            equipped: None,
+        }
+    }
+
+    pub fn shield() -> Item {
+        Item::new("Shield", ItemKind::Shield, 1, SHIELD_WEIGHT)
+    }
+
+    pub fn total_weight(&self) -> f32 {
+        self.weight * self.quantity as f32
+    }
+
+    /// Price in copper pieces of each one, for items from the catalogue.
+    pub fn price(&self) -> Option<u32> {
+        match &self.kind {
+            ItemKind::Gear => Gear::from_str(&self.name)
+                .ok()
+                .map(|gear| gear.price() / gear.bundle()),
+            ItemKind::Weapon(weapon) => Some(weapon.price()),
+            ItemKind::Armor(armor) => Some(armor.price()),
+            ItemKind::Shield => Some(SHIELD_PRICE),
+        }
+    }
+
+    /// The slots the item can be equipped in.
+    pub fn slots(&self) -> Vec<Slot> {
+        match &self.kind {
+            ItemKind::Gear => vec![],
+            ItemKind::Weapon(weapon) if weapon.has_property(WeaponProperty::TwoHanded) => {
+                vec![Slot::MainHand]
+            }
+            ItemKind::Weapon(_) => vec![Slot::MainHand, Slot::OffHand],
+            ItemKind::Armor(_) => vec![Slot::Armor],
+            ItemKind::Shield => vec![Slot::Shield],
+        }
+    }
+
+    /// Whether the item takes up both hands, leaving no room for an off-hand
+    /// weapon or a shield.
+    fn two_handed(&self) -> bool {
+        matches!(&self.kind, ItemKind::Weapon(weapon) if weapon.has_property(WeaponProperty::TwoHanded))
+    }
+}
+
+impl From<Weapon> for Item {
+    fn from(weapon: Weapon) -> Item {
+        Item::new(
+            &weapon.to_string(),
+            ItemKind::Weapon(weapon),
+            1,
+            weapon.weight(),
+        )
+    }
+}
+
+/// A bundle of the gear, e.g. 20 arrows.
+impl From<Gear> for Item {
+    fn from(gear: Gear) -> Item {
+        Item::new(
+            &gear.to_string(),
+            ItemKind::Gear,
+            gear.bundle(),
+            gear.weight(),
+        )
+    }
+}
+
+impl From<Armor> for Item {
+    fn from(armor: Armor) -> Item {
+        Item::new(
+            &armor.to_string(),
+            ItemKind::Armor(armor),
+            1,
+            armor.weight(),
+        )
+    }
+}
+
+/// Everything a shop sells: weapons, armor, shields and adventuring gear,
+/// each with the price in copper pieces of the bundle it comes in.
+pub fn catalogue() -> Vec<(Item, u32)> {
+    let weapons = Weapon::iter().map(|w| (Item::from(w), w.price()));
+    let armor = Armor::iter().map(|a| (Item::from(a), a.price()));
+    let gear = Gear::iter().map(|g| (Item::from(g), g.price()));
+    weapons
+        .chain(armor)
+        .chain([(Item::shield(), SHIELD_PRICE)])
+        .chain(gear)
+        .collect()
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Inventory {
+    #[serde(default)]
+    pub items: Vec<Item>,
+    /// The variant rule in which carrying over 5 × Strength in pounds slows
+    /// the character down, rather than only going over their carrying capacity.
+    #[serde(default)]
+    pub variant_encumbrance: bool,
+}
+
+impl Inventory {
+    /// Adds an item, stacking it with an unequipped item of the same name and kind.
+    pub fn add(&mut self, item: Item) {
+        match self.items.iter_mut().find(|i| {
+            i.equipped.is_none()
+                && i.name == item.name
+                && i.kind == item.kind
+                && i.notes == item.notes
+        }) {
+            Some(existing) => existing.quantity += item.quantity,
+            None => self.items.push(item),
+        }
+    }
+
+    /// Takes up to `quantity` of the item at `index` out of the inventory.
+    pub fn remove(&mut self, index: usize, quantity: u32) -> Option<Item> {
+        let item = self.items.get_mut(index)?;
+        if quantity < item.quantity {
+            item.quantity -= quantity;
+            let mut removed = item.clone();
+            removed.quantity = quantity;
+            removed.equipped = None;
+            return Some(removed);
+        }
+        Some(self.items.remove(index))
+    }
+
+    pub fn total_weight(&self) -> f32 {
+        self.items.iter().map(|i| i.total_weight()).sum()
+    }
+
+    /// The armor being worn, if any.
+    pub fn armor(&self) -> Option<Armor> {
+        self.items.iter().find_map(|i| match (&i.kind, i.equipped) {
+            (ItemKind::Armor(armor), Some(Slot::Armor)) => Some(*armor),
+            _ => None,
+        })
+    }
+
+    pub fn has_shield(&self) -> bool {
+        self.items
+            .iter()
+            .any(|i| i.kind == ItemKind::Shield && i.equipped == Some(Slot::Shield))
+    }
+
+    /// Every weapon carried, equipped ones first.
+    pub fn weapons(&self) -> Vec<Weapon> {
+        let mut items: Vec<&Item> = self.items.iter().collect();
+        items.sort_by_key(|i| i.equipped.is_none());
+        let mut weapons = vec![];
+        for item in items {
+            if let ItemKind::Weapon(weapon) = item.kind {
+                if !weapons.contains(&weapon) {
+                    weapons.push(weapon);
+                }
+            }
+        }
+        weapons
+    }
+
+    /// Equips one of the item at `index` in `slot`, unequipping whatever is
+    /// in the way: the slot's current item, and the off hand and shield for a
+    /// two-handed weapon (or a two-handed weapon for an off-hand item or
+    /// shield). Returns the names of the items unequipped.
+    pub fn equip(&mut self, index: usize, slot: Slot) -> Result<Vec<String>, String> {
+        let item = &self.items[index];
+        if !item.slots().contains(&slot) {
+            return Err(format!(
+                "The {} can't be equipped in your {}.",
+                item.name, slot
+            ));
+        }
+        let two_handed = item.two_handed();
+        let index = match item.quantity {
+            1 => index,
+            _ => {
+                let mut single = self.items[index].clone();
+                self.items[index].quantity -= 1;
+                single.quantity = 1;
+                self.items.push(single);
+                self.items.len() - 1
+            }
+        };
+
+        let mut unequipped = vec![];
+        for (i, other) in self.items.iter_mut().enumerate() {
+            let in_the_way = match other.equipped {
+                Some(_) if i == index => false,
+                Some(s) if s == slot => true,
+                Some(Slot::OffHand | Slot::Shield) => two_handed,
+                Some(Slot::MainHand) => {
+                    matches!(slot, Slot::OffHand | Slot::Shield) && other.two_handed()
+                }
+                _ => false,
+            };
+            if in_the_way {
+                other.equipped = None;
+                unequipped.push(other.name.clone());
+            }
+        }
+        self.items[index].equipped = Some(slot);
+        Ok(unequipped)
+    }
+
+    /// Puts on the first armor and shield carried and takes the first weapon
+    /// that leaves room for the shield in hand, as a new character would.
+    pub fn equip_starting_gear(&mut self) {
+        let items = &self.items;
+        let shield = items.iter().position(|i| i.kind == ItemKind::Shield);
+        let armor = items
+            .iter()
+            .position(|i| matches!(i.kind, ItemKind::Armor(_)));
+        let weapon = items.iter().position(|i| {
+            matches!(i.kind, ItemKind::Weapon(_)) && !(shield.is_some() && i.two_handed())
+        });
+        // Equipping one of a stack moves it to the end, so the other indexes
+        // still hold, and each item goes in a slot it fits.
+        for (index, slot) in [
+            (armor, Slot::Armor),
+            (shield, Slot::Shield),
+            (weapon, Slot::MainHand),
+        ] {
+            if let Some(index) = index {
+                self.equip(index, slot).unwrap();
+            }
+        }
+    }
+
+    pub fn unequip(&mut self, index: usize) {
+        let mut item = self.items.remove(index);
+        item.equipped = None;
+        self.add(item);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Display)]
+#[strum(serialize_all = "title_case")]
+pub enum Encumbrance {
+    Unencumbered,
+    /// Over 5 × Strength with variant encumbrance.
+    Encumbered,
+    /// Over 10 × Strength with variant encumbrance.
+    HeavilyEncumbered,
+    /// Over 15 × Strength, the carrying capacity.
+    OverCapacity,
+}
+
+impl Encumbrance {
+    pub fn new(weight: f32, strength: u8, variant: bool) -> Encumbrance {
+        let strength = strength as f32;
+        if weight > 15.0 * strength {
+            Encumbrance::OverCapacity
+        } else if variant && weight > 10.0 * strength {
+            Encumbrance::HeavilyEncumbered
+        } else if variant && weight > 5.0 * strength {
+            Encumbrance::Encumbered
+        } else {
+            Encumbrance::Unencumbered
+        }
+    }
+
+    /// The character's speed after carrying this much. Over capacity, they
+    /// can only push or drag their load along at 5 feet.
+    pub fn speed(&self, speed: u16) -> u16 {
+        match self {
+            Encumbrance::Unencumbered => speed,
+            Encumbrance::Encumbered => speed.saturating_sub(10),
+            Encumbrance::HeavilyEncumbered => speed.saturating_sub(20),
+            Encumbrance::OverCapacity => speed.min(5),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn equipping_clears_the_way() {
+        let mut inventory = Inventory::default();
+        inventory.add(Item::from(Weapon::Dagger));
+        inventory.add(Item::from(Weapon::Dagger));
+        inventory.add(Item::from(Weapon::Greatsword));
+        inventory.add(Item::shield());
+        assert_eq!(inventory.items.len(), 3);
+        assert_eq!(inventory.total_weight(), 14.0);
+
+        assert_eq!(inventory.equip(0, Slot::MainHand), Ok(vec![]));
+        assert_eq!(inventory.equip(0, Slot::OffHand), Ok(vec![]));
+        assert_eq!(inventory.items.len(), 4);
+        assert_eq!(inventory.equip(2, Slot::Shield), Ok(vec![]));
+        assert!(inventory.equip(1, Slot::OffHand).is_err());
+
+        let unequipped = inventory.equip(1, Slot::MainHand).unwrap();
+        assert_eq!(unequipped, ["Dagger", "Shield", "Dagger"]);
+        assert_eq!(inventory.weapons(), [Weapon::Greatsword, Weapon::Dagger]);
+
+        let saved = serde_yaml::to_string(&inventory).unwrap();
+        assert_eq!(
+            serde_yaml::from_str::<Inventory>(&saved).unwrap(),
+            inventory
+        );
+    }
+
+    #[test]
+    fn new_characters_wear_their_gear() {
+        let mut inventory = Inventory::default();
+        inventory.add(Item::from(Weapon::Greatsword));
+        inventory.add(Item::from(Weapon::Dagger));
+        inventory.add(Item::from(Weapon::Dagger));
+        inventory.add(Item::shield());
+        inventory.add(Item::from(Armor::ChainMail));
+        inventory.equip_starting_gear();
+
+        assert_eq!(inventory.armor(), Some(Armor::ChainMail));
+        assert!(inventory.has_shield());
+        let main_hand: Vec<&str> = inventory
+            .items
+            .iter()
+            .filter(|i| i.equipped == Some(Slot::MainHand))
+            .map(|i| i.name.as_str())
+            .collect();
+        assert_eq!(main_hand, ["Dagger"]);
+    }
+
+    #[test]
+    fn heavy_loads_slow_the_character() {
+        assert_eq!(Encumbrance::new(60.0, 10, false), Encumbrance::Unencumbered);
+        assert_eq!(Encumbrance::new(60.0, 10, true), Encumbrance::Encumbered);
+        assert_eq!(
+            Encumbrance::new(101.0, 10, true),
+            Encumbrance::HeavilyEncumbered
+        );
+        assert_eq!(Encumbrance::new(151.0, 10, false).speed(30), 5);
+        assert_eq!(Encumbrance::HeavilyEncumbered.speed(25), 5);
+    }
+}
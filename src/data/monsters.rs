@@ -0,0 +1,483 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
+use term_table::{
+    row::Row,
+    table_cell::{Alignment, TableCell},
+    Table, TableStyle,
+};
+
+use super::damage::{DamageType, Defense};
+use super::dice::DiceExpression;
+use super::stats::Stat;
+use super::utils::format_modifier;
+
+/// The SRD monsters that ship with the game, so there is always something to fight.
+const SRD_MONSTERS: &str = include_str!("../../monsters/srd.yaml");
+
+/// Where extra or replacement monsters are read from, one or more per file.
+pub const MONSTER_DIR: &str = "./monsters";
+
+/// Experience points for each challenge rating.
+const EXPERIENCE: [(&str, u32); 34] = [
+    ("0", 10),
+    ("1/8", 25),
+    ("1/4", 50),
+    ("1/2", 100),
+    ("1", 200),
+    ("2", 450),
+    ("3", 700),
+    ("4", 1100),
+    ("5", 1800),
+    ("6", 2300),
+    ("7", 2900),
+    ("8", 3900),
+    ("9", 5000),
+    ("10", 5900),
+    ("11", 7200),
+    ("12", 8400),
+    ("13", 10000),
+    ("14", 11500),
+    ("15", 13000),
+    ("16", 15000),
+    ("17", 18000),
+    ("18", 20000),
+    ("19", 22000),
+    ("20", 25000),
+    ("21", 33000),
+    ("22", 41000),
+    ("23", 50000),
+    ("24", 62000),
+    ("25", 75000),
+    ("26", 90000),
+    ("27", 105000),
+    ("28", 120000),
+    ("29", 135000),
+    ("30", 155000),
+];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Speed {
+    pub walk: u16,
+    #[serde(default)]
+    pub burrow: Option<u16>,
+    #[serde(default)]
+    pub climb: Option<u16>,
+    #[serde(default)]
+    pub fly: Option<u16>,
+    #[serde(default)]
+    pub swim: Option<u16>,
+}
+
+impl fmt::Display for Speed {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ft.", self.walk)?;
+        let others = [
+            ("burrow", self.burrow),
+            ("climb", self.climb),
+            ("fly", self.fly),
+            ("swim", self.swim),
+        ];
+        for (kind, feet) in others {
+            if let Some(feet) = feet {
+                write!(f, ", {} {} ft.", kind, feet)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A trait, action, reaction or legendary action from a stat block. Attacks
+/// and damaging effects carry their numbers so they can be rolled.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MonsterAction {
+    pub name: String,
+    pub description: String,
+    #[serde(default)]
+    pub attack_bonus: Option<i8>,
+    /// A dice expression, optionally labelled with the damage type, e.g. `1d6+2 slashing`.
+    #[serde(default)]
+    pub damage: Option<String>,
+}
+
+impl MonsterAction {
+    pub fn damage(&self) -> Option<DiceExpression> {
+        self.damage
+            .as_ref()
+            .and_then(|damage| DiceExpression::from_str(damage).ok())
+    }
+
+    /// Whether it can be made as a ranged weapon attack, going by the stat
+    /// block's "Ranged Weapon Attack" or "Melee or Ranged Weapon Attack".
+    pub fn is_ranged_weapon_attack(&self) -> bool {
+        self.description.contains("Ranged Weapon Attack")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Monster {
+    pub name: String,
+    pub size: String,
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub alignment: String,
+    pub armor_class: u8,
+    #[serde(default)]
+    pub armor: Option<String>,
+    /// The hit dice formula, e.g. `2d6` or `7d10+21`.
+    pub hit_points: String,
+    pub speed: Speed,
+    pub stats: [Stat; 6],
+    /// Saving throw bonuses keyed by ability abbreviation (Str, Dex, ...);
+    /// other saves use the plain ability modifier.
+    #[serde(default)]
+    pub saving_throws: BTreeMap<String, i8>,
+    #[serde(default)]
+    pub skills: BTreeMap<String, i8>,
+    #[serde(default)]
+    pub damage_vulnerabilities: Vec<DamageType>,
+    #[serde(default)]
+    pub damage_resistances: Vec<DamageType>,
+    #[serde(default)]
+    pub damage_immunities: Vec<DamageType>,
+    #[serde(default)]
+    pub condition_immunities: Vec<String>,
+    #[serde(default)]
+    pub senses: String,
+    #[serde(default)]
+    pub languages: String,
+    /// Challenge rating as written in the stat block, e.g. `1/4` or `17`.
+    pub challenge: String,
+    #[serde(default)]
+    pub traits: Vec<MonsterAction>,
+    #[serde(default)]
+    pub actions: Vec<MonsterAction>,
+    #[serde(default)]
+    pub reactions: Vec<MonsterAction>,
+    #[serde(default)]
+    pub legendary_actions: Vec<MonsterAction>,
+}
+
+impl Monster {
+    pub fn hit_dice(&self) -> DiceExpression {
+        DiceExpression::from_str(&self.hit_points).expect("hit points are checked on load")
+    }
+
+    /// Rolls the monster's hit dice, or takes the average printed in the stat
+    /// block. A monster always has at least 1 HP.
+    pub fn roll_hit_points<R: Rng + ?Sized>(&self, use_average: bool, rng: &mut R) -> i32 {
+        let hit_dice = self.hit_dice();
+        let hit_points = match use_average {
+            true => hit_dice.average(),
+            false => hit_dice.roll(rng).total,
+        };
+        hit_points.max(1)
+    }
+
+    pub fn dexterity(&self) -> &Stat {
+        &self.stats[1]
+    }
+
+    /// The saving throw bonus for the ability at `index` in `stats`.
+    pub fn saving_throw(&self, index: usize) -> i8 {
+        let stat = &self.stats[index];
+        self.saving_throws
+            .iter()
+            .find(|(ability, _)| ability.eq_ignore_ascii_case(stat.abbreviation()))
+            .map_or(stat.get_modifier(), |(_, bonus)| *bonus)
+    }
+
+    pub fn damage_defenses(&self, damage_type: DamageType) -> Vec<Defense> {
+        [
+            (&self.damage_vulnerabilities, Defense::Vulnerability),
+            (&self.damage_resistances, Defense::Resistance),
+            (&self.damage_immunities, Defense::Immunity),
+        ]
+        .iter()
+        .filter(|(types, _)| types.contains(&damage_type))
+        .map(|(_, defense)| *defense)
+        .collect()
+    }
+
+    pub fn experience(&self) -> u32 {
+        EXPERIENCE
+            .iter()
+            .find(|(challenge, _)| *challenge == self.challenge)
+            .map_or(0, |(_, xp)| *xp)
+    }
+
+    /// Checks the parts of the stat block that are only text in YAML.
+    fn validate(&self) -> Result<(), String> {
+        DiceExpression::from_str(&self.hit_points)
+            .map_err(|e| format!("{}: hit points '{}': {}", self.name, self.hit_points, e))?;
+        if !EXPERIENCE.iter().any(|(c, _)| *c == self.challenge) {
+            return Err(format!(
+                "{}: '{}' is not a challenge rating",
+                self.name, self.challenge
+            ));
+        }
+        // Initiative and saving throws look abilities up by position.
+        let stat_names: Vec<&str> = self.stats.iter().map(|s| s.abbreviation()).collect();
+        let expected: Vec<&str> = Stat::iter().map(|s| s.abbreviation()).collect();
+        if stat_names != expected {
+            return Err(format!(
+                "{}: stats must be listed as {}, not {}",
+                self.name,
+                expected.join(", "),
+                stat_names.join(", ")
+            ));
+        }
+        for ability in self.saving_throws.keys() {
+            if !stat_names.iter().any(|s| s.eq_ignore_ascii_case(ability)) {
+                return Err(format!(
+                    "{}: '{}' is not an ability for a saving throw",
+                    self.name, ability
+                ));
+            }
+        }
+        let actions = self
+            .traits
+            .iter()
+            .chain(&self.actions)
+            .chain(&self.reactions)
+            .chain(&self.legendary_actions);
+        for action in actions {
+            if let Some(damage) = &action.damage {
+                DiceExpression::from_str(damage).map_err(|e| {
+                    format!("{}: {} damage '{}': {}", self.name, action.name, damage, e)
+                })?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn display(&self) {
+        let mut table = Table::new();
+        table.max_column_width = 70;
+        table.style = TableStyle::extended();
+
+        table.add_row(Row::new(vec![TableCell::new_with_alignment(
+            &self.name,
+            6,
+            Alignment::Center,
+        )]));
+        table.add_row(Row::new(vec![TableCell::new_with_alignment(
+            format!("{} {}, {}", self.size, self.kind, self.alignment),
+            6,
+            Alignment::Center,
+        )]));
+
+        let armor = match &self.armor {
+            Some(armor) => format!("{} ({})", self.armor_class, armor),
+            None => self.armor_class.to_string(),
+        };
+        add_detail(&mut table, "Armor Class", armor);
+        add_detail(
+            &mut table,
+            "Hit Points",
+            format!("{} ({})", self.hit_dice().average(), self.hit_points),
+        );
+        add_detail(&mut table, "Speed", self.speed.to_string());
+
+        table.add_row(Row::new(
+            self.stats
+                .iter()
+                .map(|stat| {
+                    TableCell::new_with_alignment(
+                        format!(
+                            "{} {} ({})",
+                            stat.abbreviation(),
+                            stat.value(),
+                            stat.display()
+                        ),
+                        1,
+                        Alignment::Center,
+                    )
+                })
+                .collect::<Vec<TableCell>>(),
+        ));
+
+        let saves: Vec<String> = (0..6)
+            .filter(|index| {
+                self.saving_throws
+                    .keys()
+                    .any(|k| k.eq_ignore_ascii_case(self.stats[*index].abbreviation()))
+            })
+            .map(|index| {
+                format!(
+                    "{} {}",
+                    self.stats[index].abbreviation(),
+                    format_modifier(self.saving_throw(index) as i32)
+                )
+            })
+            .collect();
+        let skills: Vec<String> = self
+            .skills
+            .iter()
+            .map(|(skill, bonus)| format!("{} {}", skill, format_modifier(*bonus as i32)))
+            .collect();
+        let lists = [
+            ("Saving Throws", saves),
+            ("Skills", skills),
+            ("Vulnerabilities", to_strings(&self.damage_vulnerabilities)),
+            ("Resistances", to_strings(&self.damage_resistances)),
+            ("Immunities", to_strings(&self.damage_immunities)),
+            ("Condition Immunities", self.condition_immunities.clone()),
+        ];
+        for (label, list) in lists {
+            if !list.is_empty() {
+                add_detail(&mut table, label, list.join(", "));
+            }
+        }
+        if !self.senses.is_empty() {
+            add_detail(&mut table, "Senses", self.senses.clone());
+        }
+        if !self.languages.is_empty() {
+            add_detail(&mut table, "Languages", self.languages.clone());
+        }
+        add_detail(
+            &mut table,
+            "Challenge",
+            format!("{} ({} XP)", self.challenge, self.experience()),
+        );
+
+        let sections = [
+            ("Traits", &self.traits),
+            ("Actions", &self.actions),
+            ("Reactions", &self.reactions),
+            ("Legendary Actions", &self.legendary_actions),
+        ];
+        for (heading, actions) in sections {
+            if actions.is_empty() {
+                continue;
+            }
+            table.add_row(Row::new(vec![TableCell::new_with_alignment(
+                heading,
+                6,
+                Alignment::Center,
+            )]));
+            for action in actions {
+                add_detail(&mut table, &action.name, action.description.clone());
+            }
+        }
+
+        println!("{}", table.render());
+    }
+}
+
+fn to_strings(damage_types: &[DamageType]) -> Vec<String> {
+    damage_types.iter().map(|t| t.to_string()).collect()
+}
+
+fn add_detail(table: &mut Table, label: &str, value: String) {
+    table.add_row(Row::new(vec![
+        TableCell::new_with_alignment(label, 1, Alignment::Left),
+        TableCell::new_with_alignment(value, 5, Alignment::Left),
+    ]));
+}
+
+/// Parses a YAML document holding either one monster or a list of them.
+pub fn parse_monsters(yaml: &str) -> Result<Vec<Monster>, String> {
+    let monsters = match serde_yaml::from_str::<Vec<Monster>>(yaml) {
+        Ok(monsters) => monsters,
+        Err(list_error) => match serde_yaml::from_str::<Monster>(yaml) {
+            Ok(monster) => vec![monster],
+            Err(_) => return Err(list_error.to_string()),
+        },
+    };
+    for monster in &monsters {
+        monster.validate()?;
+    }
+    Ok(monsters)
+}
+
+/// The bundled SRD monsters plus any YAML files in `dir`, sorted by name. A
+/// monster from `dir` replaces a bundled one with the same name. Files that
+/// can't be read are skipped and described in the returned warnings.
+pub fn load_monsters(dir: &str) -> (Vec<Monster>, Vec<String>) {
+    let mut monsters = parse_monsters(SRD_MONSTERS).expect("bundled monsters are valid");
+    let mut warnings = vec![];
+
+    let mut paths: Vec<_> = match fs::read_dir(Path::new(dir)) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| {
+                path.extension()
+                    .is_some_and(|ext| ext == "yaml" || ext == "yml")
+            })
+            .collect(),
+        Err(_) => vec![],
+    };
+    paths.sort();
+
+    for path in paths {
+        let loaded = fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|yaml| parse_monsters(&yaml));
+        match loaded {
+            Ok(loaded) => {
+                for monster in loaded {
+                    monsters.retain(|m| !m.name.eq_ignore_ascii_case(&monster.name));
+                    monsters.push(monster);
+                }
+            }
+            Err(e) => warnings.push(format!("Skipped {}: {}", path.display(), e)),
+        }
+    }
+
+    monsters.sort_by(|a, b| a.name.cmp(&b.name));
+    (monsters, warnings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_monsters_load() {
+        let monsters = parse_monsters(SRD_MONSTERS).unwrap();
+        let goblin = monsters.iter().find(|m| m.name == "Goblin").unwrap();
+        assert_eq!(goblin.hit_dice().average(), 7);
+        assert_eq!(goblin.experience(), 50);
+        assert_eq!(goblin.dexterity().get_modifier(), 2);
+        let ranged: Vec<bool> = goblin
+            .actions
+            .iter()
+            .map(|a| a.is_ranged_weapon_attack())
+            .collect();
+        assert_eq!(ranged, [false, true]);
+
+        let dragon = monsters
+            .iter()
+            .find(|m| m.name == "Adult Red Dragon")
+            .unwrap();
+        assert_eq!(dragon.hit_dice().average(), 256);
+        assert_eq!(dragon.saving_throw(1), 6);
+        assert_eq!(dragon.saving_throw(0), 8);
+        assert_eq!(dragon.legendary_actions.len(), 3);
+    }
+
+    #[test]
+    fn rejects_bad_hit_dice() {
+        let yaml = "name: Blob\nsize: Small\ntype: ooze\nalignment: unaligned\n\
+            armor_class: 8\nhit_points: lots\nspeed:\n  walk: 10\n\
+            stats: [Str: 8, Dex: 6, Con: 12, Int: 1, Wis: 6, Chr: 2]\nchallenge: 1/8\n";
+        assert!(parse_monsters(yaml).unwrap_err().contains("hit points"));
+    }
+
+    #[test]
+    fn rejects_stats_out_of_order() {
+        let yaml = "name: Blob\nsize: Small\ntype: ooze\nalignment: unaligned\n\
+            armor_class: 8\nhit_points: 1d6\nspeed:\n  walk: 10\n\
+            stats: [Dex: 6, Str: 8, Con: 12, Int: 1, Wis: 6, Chr: 2]\nchallenge: 1/8\n";
+        assert!(parse_monsters(yaml)
+            .unwrap_err()
+            .contains("stats must be listed as STR, DEX, CON, INT, WIS, CHR"));
+    }
+}
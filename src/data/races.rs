@@ -7,7 +7,12 @@ use serde::{Deserialize, Serialize};
 use strum::IntoEnumIterator;
 use strum_macros::{Display, EnumIter, EnumString};
 
+use super::conditions::SaveAgainst;
+use super::damage::DamageType;
+use super::rng::GameRng;
+use super::stats::Stat;
 use super::utils::{choose_value, pretty_print, Choosable, BLUE};
+use super::weapons::Weapon;
 
 use super::utils::StringJoin;
 
@@ -26,9 +31,131 @@ pub enum Race {
 }
 
 impl Race {
-    pub fn choose_subrace(self) -> Self {
-        let mut rng = rand::thread_rng();
+    /// Fixed racial and subracial ability score increases, in the same
+    /// Str/Dex/Con/Int/Wis/Chr order as `Character.stats`.
+    pub fn ability_score_increases(&self) -> [u8; 6] {
+        match self {
+            Race::Dwarf(v) => match v {
+                Dwarf::HillDwarf => [0, 0, 2, 0, 1, 0],
+                Dwarf::MountainDwarf => [2, 0, 2, 0, 0, 0],
+            },
+            Race::Elf(v) => match v {
+                Elf::DarkElf => [0, 2, 0, 0, 0, 1],
+                Elf::HighElf => [0, 2, 0, 1, 0, 0],
+                Elf::WoodElf => [0, 2, 0, 0, 1, 0],
+            },
+            Race::Halfling(v) => match v {
+                Halfling::Lightfoot => [0, 2, 0, 0, 0, 1],
+                Halfling::Stout => [0, 2, 1, 0, 0, 0],
+            },
+            Race::Human(v) => match v {
+                Human::Standard => [1, 1, 1, 1, 1, 1],
+                Human::Variant => [0, 0, 0, 0, 0, 0],
+            },
+            Race::Dragonborn => [2, 0, 0, 0, 0, 1],
+            Race::Gnome(v) => match v {
+                Gnome::Forest => [0, 1, 0, 2, 0, 0],
+                Gnome::Rock => [0, 0, 1, 2, 0, 0],
+            },
+            Race::HalfElf => [0, 0, 0, 0, 0, 2],
+            Race::HalfOrc => [2, 0, 1, 0, 0, 0],
+            Race::Tiefling => [0, 0, 0, 1, 0, 2],
+        }
+    }
+
+    /// Abilities the player may pick for a +1 increase, and how many picks
+    /// they get (Variant Human and Half-Elf).
+    fn ability_score_choices(&self) -> (usize, Vec<usize>) {
+        match self {
+            Race::Human(Human::Variant) => (2, (0..6).collect()),
+            Race::HalfElf => (2, (0..5).collect()),
+            _ => (0, vec![]),
+        }
+    }
+
+    pub fn choose_ability_increases(&self) -> [u8; 6] {
+        let mut increases = self.ability_score_increases();
+        let (picks, mut options) = self.ability_score_choices();
+        let names = Stat::collect_names();
+
+        for pick in 1..=picks {
+            let selections: Vec<&str> = options.iter().map(|i| names[*i]).collect();
+            let selection = Select::with_theme(&ColorfulTheme::default())
+                .with_prompt(format!(
+                    "As a {}, choose an ability to increase by 1 ({} of {}):",
+                    self, pick, picks
+                ))
+                .default(0)
+                .items(&selections)
+                .interact()
+                .unwrap();
+            increases[options.remove(selection)] += 1;
+        }
+
+        let summary: Vec<String> = increases
+            .iter()
+            .enumerate()
+            .filter(|(_, inc)| **inc > 0)
+            .map(|(i, inc)| format!("{} +{}", names[i], inc))
+            .collect();
+        pretty_print(
+            &format!("\nRacial ability score increases: {}\n", summary.join(", ")),
+            BLUE,
+            true,
+        );
+        increases
+    }
+
+    /// The racial trait granting advantage on this saving throw, if any.
+    pub fn save_advantage(&self, ability: usize, against: SaveAgainst) -> Option<&'static str> {
+        match (self, against) {
+            (Race::Dwarf(_), SaveAgainst::Poison) => Some("Dwarven Resilience"),
+            (Race::Halfling(Halfling::Stout), SaveAgainst::Poison) => Some("Stout Resilience"),
+            (Race::Halfling(_), SaveAgainst::BeingFrightened) => Some("Brave"),
+            (Race::Elf(_) | Race::HalfElf, SaveAgainst::BeingCharmed) => Some("Fey Ancestry"),
+            (Race::Gnome(_), SaveAgainst::Magic) if ability >= 3 => Some("Gnome Cunning"),
+            _ => None,
+        }
+    }
 
+    /// The racial trait granting resistance to this damage type, if any.
+    pub fn damage_resistance(&self, damage_type: DamageType) -> Option<&'static str> {
+        match (self, damage_type) {
+            (Race::Dwarf(_), DamageType::Poison) => Some("Dwarven Resilience"),
+            (Race::Halfling(Halfling::Stout), DamageType::Poison) => Some("Stout Resilience"),
+            (Race::Tiefling, DamageType::Fire) => Some("Hellish Resistance"),
+            _ => None,
+        }
+    }
+
+    /// The racial trait granting proficiency with this weapon, if any.
+    pub fn weapon_training(&self, weapon: Weapon) -> Option<&'static str> {
+        match (self, weapon) {
+            (
+                Race::Dwarf(_),
+                Weapon::Battleaxe | Weapon::Handaxe | Weapon::LightHammer | Weapon::Warhammer,
+            ) => Some("Dwarven Combat Training"),
+            (
+                Race::Elf(Elf::HighElf | Elf::WoodElf),
+                Weapon::Longsword | Weapon::Shortsword | Weapon::Shortbow | Weapon::Longbow,
+            ) => Some("Elf Weapon Training"),
+            (
+                Race::Elf(Elf::DarkElf),
+                Weapon::Rapier | Weapon::Shortsword | Weapon::HandCrossbow,
+            ) => Some("Drow Weapon Training"),
+            _ => None,
+        }
+    }
+
+    /// Extra maximum HP at every level, from Dwarven Toughness.
+    pub fn hp_bonus_per_level(&self) -> i32 {
+        match self {
+            Race::Dwarf(Dwarf::HillDwarf) => 1,
+            _ => 0,
+        }
+    }
+
+    pub fn choose_subrace(self, rng: &mut GameRng) -> Self {
         loop {
             let selections = match self {
                 Self::Dwarf(_) => Dwarf::collect_string(),
@@ -54,12 +181,12 @@ impl Race {
             let result = match self {
                 Self::Dwarf(_) => match selection_result {
                     Ok(v) => match v {
-                        0 => Self::Dwarf(Dwarf::iter().choose(&mut rng).unwrap()),
+                        0 => Self::Dwarf(Dwarf::iter().choose(rng).unwrap()),
                         _ => match &fuzzy_selections[..].get(v) {
                             Some(selection_string) => {
                                 Self::Dwarf(Dwarf::from_str(selection_string).unwrap())
                             }
-                            None => Self::Dwarf(Dwarf::iter().choose(&mut rng).unwrap()),
+                            None => Self::Dwarf(Dwarf::iter().choose(rng).unwrap()),
                         },
                     },
                     Err(_) => {
@@ -69,12 +196,12 @@ impl Race {
                 },
                 Self::Elf(_) => match selection_result {
                     Ok(v) => match v {
-                        0 => Self::Elf(Elf::iter().choose(&mut rng).unwrap()),
+                        0 => Self::Elf(Elf::iter().choose(rng).unwrap()),
                         _ => match &fuzzy_selections[..].get(v) {
                             Some(selection_string) => {
                                 Self::Elf(Elf::from_str(selection_string).unwrap())
                             }
-                            None => Self::Elf(Elf::iter().choose(&mut rng).unwrap()),
+                            None => Self::Elf(Elf::iter().choose(rng).unwrap()),
                         },
                     },
                     Err(_) => {
@@ -84,12 +211,12 @@ impl Race {
                 },
                 Self::Halfling(_) => match selection_result {
                     Ok(v) => match v {
-                        0 => Self::Halfling(Halfling::iter().choose(&mut rng).unwrap()),
+                        0 => Self::Halfling(Halfling::iter().choose(rng).unwrap()),
                         _ => match &fuzzy_selections[..].get(v) {
                             Some(selection_string) => {
                                 Self::Halfling(Halfling::from_str(selection_string).unwrap())
                             }
-                            None => Self::Halfling(Halfling::iter().choose(&mut rng).unwrap()),
+                            None => Self::Halfling(Halfling::iter().choose(rng).unwrap()),
                         },
                     },
                     Err(_) => {
@@ -99,12 +226,12 @@ impl Race {
                 },
                 Self::Human(_) => match selection_result {
                     Ok(v) => match v {
-                        0 => Self::Human(Human::iter().choose(&mut rng).unwrap()),
+                        0 => Self::Human(Human::iter().choose(rng).unwrap()),
                         _ => match &fuzzy_selections[..].get(v) {
                             Some(selection_string) => {
                                 Self::Human(Human::from_str(selection_string).unwrap())
                             }
-                            None => Self::Human(Human::iter().choose(&mut rng).unwrap()),
+                            None => Self::Human(Human::iter().choose(rng).unwrap()),
                         },
                     },
                     Err(_) => {
@@ -114,12 +241,12 @@ impl Race {
                 },
                 Self::Gnome(_) => match selection_result {
                     Ok(v) => match v {
-                        0 => Self::Gnome(Gnome::iter().choose(&mut rng).unwrap()),
+                        0 => Self::Gnome(Gnome::iter().choose(rng).unwrap()),
                         _ => match &fuzzy_selections[..].get(v) {
                             Some(selection_string) => {
                                 Self::Gnome(Gnome::from_str(selection_string).unwrap())
                             }
-                            None => Self::Gnome(Gnome::iter().choose(&mut rng).unwrap()),
+                            None => Self::Gnome(Gnome::iter().choose(rng).unwrap()),
                         },
                     },
                     Err(_) => {
@@ -261,7 +388,28 @@ pub enum Gnome {
 }
 
 impl Choosable<Race> for Race {
-    fn choose() -> Race {
-        choose_value("\nWhat is your character's race?", &Race::collect_string())
+    fn choose(rng: &mut GameRng) -> Race {
+        choose_value(
+            "\nWhat is your character's race?",
+            &Race::collect_string(),
+            rng,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn subraces_have_different_increases() {
+        let hill = Race::Dwarf(Dwarf::HillDwarf).ability_score_increases();
+        let mountain = Race::Dwarf(Dwarf::MountainDwarf).ability_score_increases();
+        assert_ne!(hill, mountain);
+        assert_eq!(mountain, [2, 0, 2, 0, 0, 0]);
+        assert_eq!(
+            Race::Human(Human::Standard).ability_score_increases(),
+            [1; 6]
+        );
     }
 }
@@ -0,0 +1,88 @@
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumIter, EnumString};
+
+use super::character::Character;
+use super::classes::Class;
+use super::races::Race;
+
+/// Limited-use class features, tracked by how many uses have been spent.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+    EnumIter,
+    EnumString,
+    Display,
+)]
+#[strum(ascii_case_insensitive, serialize_all = "title_case")]
+pub enum Resource {
+    Ki,
+    Rage,
+    SecondWind,
+    BardicInspiration,
+    /// A Tiefling's Infernal Legacy casting of Hellish Rebuke.
+    HellishRebuke,
+}
+
+/// Which rest brings back a resource's spent uses. A long rest recovers
+/// everything a short rest does.
+#[derive(Debug, Clone, Copy, PartialEq, Display)]
+#[strum(serialize_all = "title_case")]
+pub enum Recharge {
+    ShortRest,
+    LongRest,
+}
+
+/// Stands in for the unlimited rages of a 20th-level Barbarian.
+pub const UNLIMITED: u8 = u8::MAX;
+
+impl Resource {
+    /// Uses available after a rest, or 0 if the character doesn't have the feature.
+    pub fn maximum(&self, character: &Character) -> u8 {
+        match self {
+            Resource::Ki => match character.class_level(&Class::Monk(None)) {
+                0..=1 => 0,
+                level => level,
+            },
+            Resource::Rage => match character.class_level(&Class::Barbarian(None)) {
+                0 => 0,
+                1..=2 => 2,
+                3..=5 => 3,
+                6..=11 => 4,
+                12..=16 => 5,
+                17..=19 => 6,
+                _ => UNLIMITED,
+            },
+            Resource::SecondWind => match character.class_level(&Class::Fighter(None)) {
+                0 => 0,
+                _ => 1,
+            },
+            Resource::BardicInspiration => match character.class_level(&Class::Bard(None)) {
+                0 => 0,
+                _ => character.get_chr().get_modifier().max(1) as u8,
+            },
+            Resource::HellishRebuke => match (&character.race, character.level) {
+                (Race::Tiefling, 3..) => 1,
+                _ => 0,
+            },
+        }
+    }
+
+    pub fn recharge(&self, character: &Character) -> Recharge {
+        match self {
+            Resource::Ki | Resource::SecondWind => Recharge::ShortRest,
+            Resource::Rage | Resource::HellishRebuke => Recharge::LongRest,
+            // Font of Inspiration
+            Resource::BardicInspiration => match character.class_level(&Class::Bard(None)) {
+                5.. => Recharge::ShortRest,
+                _ => Recharge::LongRest,
+            },
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use rand::{rngs::StdRng, Error, Rng, RngCore, SeedableRng};
+
+/// The single source of randomness for a session. Every random choice made
+/// during character creation and play draws from the same `GameRng`, so a
+/// session started with the same seed (and the same inputs) replays exactly.
+pub struct GameRng {
+    seed: u64,
+    rng: StdRng,
+}
+
+impl GameRng {
+    pub fn new(seed: Option<u64>) -> GameRng {
+        let seed = seed.unwrap_or_else(|| rand::thread_rng().gen());
+        GameRng {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+impl RngCore for GameRng {
+    fn next_u32(&mut self) -> u32 {
+        self.rng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.rng.try_fill_bytes(dest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_replays_the_same_rolls() {
+        let mut first = GameRng::new(Some(42));
+        let mut second = GameRng::new(Some(42));
+        let first_rolls: Vec<u8> = (0..20).map(|_| first.gen_range(1..=20)).collect();
+        let second_rolls: Vec<u8> = (0..20).map(|_| second.gen_range(1..=20)).collect();
+        assert_eq!(first_rolls, second_rolls);
+        assert_eq!(first.seed(), 42);
+    }
+}
@@ -0,0 +1,103 @@
+use dialoguer::{theme::ColorfulTheme, Select};
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumIter, EnumString};
+
+use super::utils::{pretty_print, BLUE};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, EnumIter, EnumString, Display)]
+#[strum(ascii_case_insensitive, serialize_all = "title_case")]
+pub enum Skill {
+    Acrobatics,
+    AnimalHandling,
+    Arcana,
+    Athletics,
+    Deception,
+    History,
+    Insight,
+    Intimidation,
+    Investigation,
+    Medicine,
+    Nature,
+    Perception,
+    Performance,
+    Persuasion,
+    Religion,
+    SleightOfHand,
+    Stealth,
+    Survival,
+}
+
+impl Skill {
+    /// Index of the governing ability in `Character.stats`.
+    pub fn ability(&self) -> usize {
+        match self {
+            Skill::Athletics => 0,
+            Skill::Acrobatics | Skill::SleightOfHand | Skill::Stealth => 1,
+            Skill::Arcana
+            | Skill::History
+            | Skill::Investigation
+            | Skill::Nature
+            | Skill::Religion => 3,
+            Skill::AnimalHandling
+            | Skill::Insight
+            | Skill::Medicine
+            | Skill::Perception
+            | Skill::Survival => 4,
+            Skill::Deception | Skill::Intimidation | Skill::Performance | Skill::Persuasion => 5,
+        }
+    }
+}
+
+/// Proficiency bonus by total character level: +2 at 1st, rising by one every four levels.
+pub fn proficiency_bonus(level: u8) -> i8 {
+    ((level.clamp(1, 20) - 1) / 4 + 2) as i8
+}
+
+/// Prompts the player to pick `count` skills from `options`, skipping any they already have.
+pub fn choose_skills(prompt: &str, count: usize, options: &[Skill], known: &[Skill]) -> Vec<Skill> {
+    let mut options: Vec<Skill> = options
+        .iter()
+        .filter(|skill| !known.contains(skill))
+        .copied()
+        .collect();
+    let mut chosen = Vec::new();
+
+    for pick in 1..=count {
+        if options.is_empty() {
+            break;
+        }
+        let selections: Vec<String> = options.iter().map(|s| s.to_string()).collect();
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!("{} ({} of {})", prompt, pick, count))
+            .default(0)
+            .max_length(7)
+            .items(&selections)
+            .interact()
+            .unwrap();
+        chosen.push(options.remove(selection));
+    }
+
+    if !chosen.is_empty() {
+        let names: Vec<String> = chosen.iter().map(|s| s.to_string()).collect();
+        pretty_print(
+            &format!("\nYour choice: {}\n", names.join(", ")),
+            BLUE,
+            true,
+        );
+    }
+    chosen
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn proficiency_bonus_follows_level() {
+        assert_eq!(proficiency_bonus(1), 2);
+        assert_eq!(proficiency_bonus(4), 2);
+        assert_eq!(proficiency_bonus(5), 3);
+        assert_eq!(proficiency_bonus(13), 5);
+        assert_eq!(proficiency_bonus(20), 6);
+    }
+}
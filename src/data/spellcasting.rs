@@ -0,0 +1,172 @@
+use super::classes::{Class, ClassLevel, FighterSubclass, RogueSubclass};
+
+/// Spell slots per spell level (1st to 9th) for each spellcaster level, as in
+/// the multiclass spellcaster table.
+const SPELL_SLOTS: [[u8; 9]; 20] = [
+    [2, 0, 0, 0, 0, 0, 0, 0, 0],
+    [3, 0, 0, 0, 0, 0, 0, 0, 0],
+    [4, 2, 0, 0, 0, 0, 0, 0, 0],
+    [4, 3, 0, 0, 0, 0, 0, 0, 0],
+    [4, 3, 2, 0, 0, 0, 0, 0, 0],
+    [4, 3, 3, 0, 0, 0, 0, 0, 0],
+    [4, 3, 3, 1, 0, 0, 0, 0, 0],
+    [4, 3, 3, 2, 0, 0, 0, 0, 0],
+    [4, 3, 3, 3, 1, 0, 0, 0, 0],
+    [4, 3, 3, 3, 2, 0, 0, 0, 0],
+    [4, 3, 3, 3, 2, 1, 0, 0, 0],
+    [4, 3, 3, 3, 2, 1, 0, 0, 0],
+    [4, 3, 3, 3, 2, 1, 1, 0, 0],
+    [4, 3, 3, 3, 2, 1, 1, 0, 0],
+    [4, 3, 3, 3, 2, 1, 1, 1, 0],
+    [4, 3, 3, 3, 2, 1, 1, 1, 0],
+    [4, 3, 3, 3, 2, 1, 1, 1, 1],
+    [4, 3, 3, 3, 3, 1, 1, 1, 1],
+    [4, 3, 3, 3, 3, 2, 1, 1, 1],
+    [4, 3, 3, 3, 3, 2, 2, 1, 1],
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Caster {
+    None,
+    Full,
+    Half,
+    /// A half caster that rounds up, so it has slots from 1st level.
+    Artificer,
+    Third,
+    /// Warlock Pact Magic, which doesn't use the shared slot table.
+    Pact,
+}
+
+impl Caster {
+    pub fn of(class: &Class) -> Caster {
+        match class {
+            Class::Bard(_)
+            | Class::Cleric(_)
+            | Class::Druid(_)
+            | Class::Sorcerer(_)
+            | Class::Wizard(_) => Caster::Full,
+            Class::Paladin(_) | Class::Ranger(_) => Caster::Half,
+            Class::Artificer(_) => Caster::Artificer,
+            Class::Fighter(Some(FighterSubclass::EldritchKnight))
+            | Class::Rogue(Some(RogueSubclass::ArcaneTrickster)) => Caster::Third,
+            Class::Warlock(_) => Caster::Pact,
+            _ => Caster::None,
+        }
+    }
+}
+
+/// Index in `Character.stats` of the ability the class casts spells with.
+pub fn spellcasting_ability(class: &Class) -> Option<usize> {
+    match Caster::of(class) {
+        Caster::None => None,
+        _ => Some(match class {
+            Class::Cleric(_) | Class::Druid(_) | Class::Ranger(_) => 4,
+            Class::Bard(_) | Class::Paladin(_) | Class::Sorcerer(_) | Class::Warlock(_) => 5,
+            _ => 3,
+        }),
+    }
+}
+
+/// Spellcaster level used to look up spell slots. A single spellcasting class
+/// uses its own table (half and third casters round up once they get slots);
+/// with several, their levels are combined and rounded down.
+pub fn caster_level(classes: &[ClassLevel]) -> u8 {
+    let casters: Vec<(Caster, u8)> = classes
+        .iter()
+        .map(|c| (Caster::of(&c.class), c.level))
+        .filter(|(caster, _)| !matches!(caster, Caster::None | Caster::Pact))
+        .collect();
+
+    if let [(caster, level)] = casters[..] {
+        return match caster {
+            Caster::Full => level,
+            Caster::Half if level >= 2 => level.div_ceil(2),
+            Caster::Artificer => level.div_ceil(2),
+            Caster::Third if level >= 3 => level.div_ceil(3),
+            _ => 0,
+        };
+    }
+    casters
+        .iter()
+        .map(|(caster, level)| match caster {
+            Caster::Full => *level,
+            Caster::Half => level / 2,
+            Caster::Artificer => level.div_ceil(2),
+            Caster::Third => level / 3,
+            _ => 0,
+        })
+        .sum()
+}
+
+/// Spell slots for each spell level, 1st to 9th.
+pub fn spell_slots(classes: &[ClassLevel]) -> [u8; 9] {
+    match caster_level(classes) {
+        0 => [0; 9],
+        level => SPELL_SLOTS[level.min(20) as usize - 1],
+    }
+}
+
+/// Warlock Pact Magic slots as (number of slots, slot level).
+pub fn pact_slots(classes: &[ClassLevel]) -> Option<(u8, u8)> {
+    let level = classes
+        .iter()
+        .find(|c| Caster::of(&c.class) == Caster::Pact)?
+        .level;
+    Some(match level {
+        1 => (1, 1),
+        2 => (2, 1),
+        3..=10 => (2, level.div_ceil(2)),
+        11..=16 => (3, 5),
+        _ => (4, 5),
+    })
+}
+
+/// "1st", "2nd", "3rd", ... for spell levels 1 to 9.
+pub fn ordinal(level: u8) -> String {
+    let suffix = match level {
+        1 => "st",
+        2 => "nd",
+        3 => "rd",
+        _ => "th",
+    };
+    format!("{}{}", level, suffix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn multiclass_casters_combine_levels() {
+        let paladin = vec![ClassLevel {
+            class: Class::Paladin(None),
+            level: 5,
+        }];
+        assert_eq!(caster_level(&paladin), 3);
+
+        let multiclass = vec![
+            ClassLevel {
+                class: Class::Paladin(None),
+                level: 5,
+            },
+            ClassLevel {
+                class: Class::Wizard(None),
+                level: 3,
+            },
+            ClassLevel {
+                class: Class::Warlock(None),
+                level: 3,
+            },
+        ];
+        assert_eq!(caster_level(&multiclass), 5);
+        assert_eq!(spell_slots(&multiclass)[..3], [4, 3, 2]);
+        assert_eq!(pact_slots(&multiclass), Some((2, 2)));
+
+        let fighter = vec![ClassLevel {
+            class: Class::Fighter(None),
+            level: 5,
+        }];
+        assert_eq!(spell_slots(&fighter), [0; 9]);
+        assert_eq!(pact_slots(&fighter), None);
+    }
+}
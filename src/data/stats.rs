@@ -1,10 +1,57 @@
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
+use std::str::FromStr;
+use strum::IntoEnumIterator;
 use strum_macros::{Display, EnumIter, EnumString};
 
-use super::utils::StringJoin;
+use super::dice::{DiceExpression, RollResult};
+use super::rng::GameRng;
+use super::utils::{choose_value, Choosable, StringJoin};
 
-#[derive(Debug, PartialEq, Serialize, Deserialize, EnumIter, EnumString, Display)]
+pub const STANDARD_ARRAY: [u8; 6] = [15, 14, 13, 12, 10, 8];
+pub const POINT_BUY_BUDGET: u8 = 27;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize, EnumIter, EnumString, Display, Default)]
+#[strum(ascii_case_insensitive, serialize_all = "title_case")]
+pub enum StatMethod {
+    #[default]
+    Manual,
+    /// 4d6 drop lowest, keeping the log of every roll made (including rerolled sets).
+    Rolled(Vec<String>),
+    StandardArray,
+    PointBuy,
+}
+
+impl Choosable<StatMethod> for StatMethod {
+    fn choose(rng: &mut GameRng) -> StatMethod {
+        choose_value(
+            "How would you like to generate your ability scores?",
+            &StatMethod::collect_string(),
+            rng,
+        )
+    }
+}
+
+/// The point-buy cost of a score, or `None` if the score can't be bought.
+pub fn point_buy_cost(score: u8) -> Option<u8> {
+    match score {
+        8 => Some(0),
+        9 => Some(1),
+        10 => Some(2),
+        11 => Some(3),
+        12 => Some(4),
+        13 => Some(5),
+        14 => Some(7),
+        15 => Some(9),
+        _ => None,
+    }
+}
+
+pub fn roll_ability_score(rng: &mut GameRng) -> RollResult {
+    DiceExpression::from_str("4d6kh3").unwrap().roll(rng)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, EnumIter, EnumString, Display)]
 pub enum Stat {
     Str(u8),
     Dex(u8),
@@ -15,16 +62,35 @@ pub enum Stat {
 }
 
 impl Stat {
-    pub fn get_modifier(&self) -> i8 {
-        let stat_val = match *self {
+    pub fn value(&self) -> u8 {
+        match *self {
             Stat::Str(val) => val,
             Stat::Dex(val) => val,
             Stat::Con(val) => val,
             Stat::Int(val) => val,
             Stat::Wis(val) => val,
             Stat::Chr(val) => val,
+        }
+    }
+
+    /// Returns the same ability with `amount` added, capped at 20.
+    pub fn increase(&self, amount: u8) -> Stat {
+        let value = match self.value() {
+            v if v >= 20 => v,
+            v => (v + amount).min(20),
         };
-        match stat_val {
+        match self {
+            Stat::Str(_) => Stat::Str(value),
+            Stat::Dex(_) => Stat::Dex(value),
+            Stat::Con(_) => Stat::Con(value),
+            Stat::Int(_) => Stat::Int(value),
+            Stat::Wis(_) => Stat::Wis(value),
+            Stat::Chr(_) => Stat::Chr(value),
+        }
+    }
+
+    pub fn get_modifier(&self) -> i8 {
+        match self.value() {
             1 => -5,
             2 | 3 => -4,
             4 | 5 => -3,
@@ -53,7 +119,7 @@ impl Stat {
         format!("{}{}", sign, stat_modifier)
     }
 
-    pub fn show_name(&self) -> &str {
+    pub fn show_name(&self) -> &'static str {
         match &self {
             Stat::Str(_) => "Strength",
             Stat::Dex(_) => "Dexterity",
@@ -64,7 +130,48 @@ impl Stat {
         }
     }
 
+    pub fn abbreviation(&self) -> &'static str {
+        match &self {
+            Stat::Str(_) => "STR",
+            Stat::Dex(_) => "DEX",
+            Stat::Con(_) => "CON",
+            Stat::Int(_) => "INT",
+            Stat::Wis(_) => "WIS",
+            Stat::Chr(_) => "CHR",
+        }
+    }
+
     pub fn list() -> String {
         Stat::join_string()
     }
+
+    pub fn collect_names() -> Vec<&'static str> {
+        Stat::iter().map(|s| s.show_name()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn standard_array_costs_exactly_the_point_buy_budget() {
+        let cost: u8 = STANDARD_ARRAY
+            .iter()
+            .map(|score| point_buy_cost(*score).unwrap())
+            .sum();
+        assert_eq!(cost, POINT_BUY_BUDGET);
+        assert_eq!(point_buy_cost(7), None);
+        assert_eq!(point_buy_cost(16), None);
+    }
+
+    #[test]
+    fn rolled_scores_stay_in_range() {
+        let mut rng = GameRng::new(Some(5));
+        for _ in 0..100 {
+            let roll = roll_ability_score(&mut rng);
+            assert!((3..=18).contains(&roll.total));
+            assert_eq!(roll.dice.len(), 4);
+        }
+    }
 }
@@ -12,6 +12,8 @@ use std::{
 };
 use strum::IntoEnumIterator;
 
+use super::rng::GameRng;
+
 pub const _PURPLE: Color = Color::Rgb {
     r: 183,
     g: 117,
@@ -45,6 +47,14 @@ pub fn pretty_print(string: &str, color: Color, newline: bool) {
     .expect("ERROR: stdout unavailable");
 }
 
+/// Formats a bonus the way a character sheet shows it, e.g. `+3`, `+0`, `-1`.
+pub fn format_modifier(modifier: i32) -> String {
+    match modifier {
+        m if m >= 0 => format!("+{}", m),
+        m => format!("{}", m),
+    }
+}
+
 pub fn choose_yes_or_no(character_name: &str) -> bool {
     let choice = Confirm::with_theme(&ColorfulTheme::default())
         .with_prompt(&format!(
@@ -102,15 +112,14 @@ where
 }
 
 pub trait Choosable<T> {
-    fn choose() -> T;
+    fn choose(rng: &mut GameRng) -> T;
 }
 
-pub fn choose_value<T>(string_one: &str, selections: &Vec<String>) -> T
+pub fn choose_value<T>(string_one: &str, selections: &Vec<String>, rng: &mut GameRng) -> T
 where
     T: std::fmt::Debug + IntoEnumIterator + FromStr + Display,
     <T as std::str::FromStr>::Err: std::error::Error,
 {
-    let mut rng = rand::thread_rng();
     let mut fuzzy_selections = selections.clone();
     fuzzy_selections.insert(0, String::from("Random"));
 
@@ -122,15 +131,20 @@ where
         .items(&fuzzy_selections[..])
         .interact();
 
+    // "Random" only picks among the offered selections, which may be a subset of `T`.
+    let random = |rng: &mut GameRng| match selections.choose(rng) {
+        Some(selection_string) => T::from_str(selection_string).unwrap(),
+        None => T::iter().choose(rng).unwrap(),
+    };
     let result = match selection_result {
         Ok(u) => match u {
-            0 => T::iter().choose(&mut rng).unwrap(),
+            0 => random(rng),
             _ => match &fuzzy_selections[..].get(u) {
                 Some(selection_string) => T::from_str(selection_string).unwrap(),
-                None => T::iter().choose(&mut rng).unwrap(),
+                None => random(rng),
             },
         },
-        Err(_) => T::iter().choose(&mut rng).unwrap(),
+        Err(_) => random(rng),
     };
     pretty_print(&format!("\nYour choice: {}\n", result), BLUE, true);
     result
@@ -0,0 +1,406 @@
+use serde::{Deserialize, Serialize};
+use strum_macros::{Display, EnumIter, EnumString};
+
+use super::damage::DamageType;
+use super::dice::{Dice, DiceExpression};
+
+#[derive(Debug, Clone, Copy, PartialEq, Display)]
+pub enum WeaponCategory {
+    Simple,
+    Martial,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Display)]
+#[strum(serialize_all = "title_case")]
+pub enum WeaponProperty {
+    Ammunition,
+    Finesse,
+    Heavy,
+    Light,
+    Loading,
+    Reach,
+    /// Rules unique to the weapon, e.g. the Lance's disadvantage within 5 feet.
+    Special,
+    Thrown,
+    #[strum(serialize = "Two-Handed")]
+    TwoHanded,
+    Versatile,
+}
+
+/// How a weapon is held for an attack.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Grip {
+    OneHanded,
+    /// With both hands, for a versatile weapon's larger die.
+    TwoHanded,
+    /// The bonus action attack of two-weapon fighting.
+    OffHand,
+}
+
+/// The weapons of the Player's Handbook.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, EnumIter, EnumString, Display)]
+#[strum(ascii_case_insensitive, serialize_all = "title_case")]
+pub enum Weapon {
+    Club,
+    Dagger,
+    Greatclub,
+    Handaxe,
+    Javelin,
+    LightHammer,
+    Mace,
+    Quarterstaff,
+    Sickle,
+    Spear,
+    LightCrossbow,
+    Dart,
+    Shortbow,
+    Sling,
+    Battleaxe,
+    Flail,
+    Glaive,
+    Greataxe,
+    Greatsword,
+    Halberd,
+    Lance,
+    Longsword,
+    Maul,
+    Morningstar,
+    Pike,
+    Rapier,
+    Scimitar,
+    Shortsword,
+    Trident,
+    WarPick,
+    Warhammer,
+    Whip,
+    Blowgun,
+    HandCrossbow,
+    HeavyCrossbow,
+    Longbow,
+    Net,
+}
+
+impl Weapon {
+    pub fn category(&self) -> WeaponCategory {
+        match self {
+            Weapon::Club
+            | Weapon::Dagger
+            | Weapon::Greatclub
+            | Weapon::Handaxe
+            | Weapon::Javelin
+            | Weapon::LightHammer
+            | Weapon::Mace
+            | Weapon::Quarterstaff
+            | Weapon::Sickle
+            | Weapon::Spear
+            | Weapon::LightCrossbow
+            | Weapon::Dart
+            | Weapon::Shortbow
+            | Weapon::Sling => WeaponCategory::Simple,
+            _ => WeaponCategory::Martial,
+        }
+    }
+
+    /// Ranged weapons are fired or thrown; thrown melee weapons like the
+    /// Handaxe are still melee weapons.
+    pub fn is_ranged(&self) -> bool {
+        matches!(
+            self,
+            Weapon::LightCrossbow
+                | Weapon::Dart
+                | Weapon::Shortbow
+                | Weapon::Sling
+                | Weapon::Blowgun
+                | Weapon::HandCrossbow
+                | Weapon::HeavyCrossbow
+                | Weapon::Longbow
+                | Weapon::Net
+        )
+    }
+
+    /// The weapon's damage dice, or `None` for the Blowgun's flat 1 and the Net.
+    fn dice(&self) -> Option<(u16, Dice)> {
+        match self {
+            Weapon::Club
+            | Weapon::Dagger
+            | Weapon::LightHammer
+            | Weapon::Sickle
+            | Weapon::Dart
+            | Weapon::Sling
+            | Weapon::Whip => Some((1, Dice::D4)),
+            Weapon::Handaxe
+            | Weapon::Javelin
+            | Weapon::Mace
+            | Weapon::Quarterstaff
+            | Weapon::Spear
+            | Weapon::Shortbow
+            | Weapon::Scimitar
+            | Weapon::Shortsword
+            | Weapon::Trident
+            | Weapon::HandCrossbow => Some((1, Dice::D6)),
+            Weapon::Greatclub
+            | Weapon::LightCrossbow
+            | Weapon::Battleaxe
+            | Weapon::Flail
+            | Weapon::Longsword
+            | Weapon::Morningstar
+            | Weapon::Rapier
+            | Weapon::WarPick
+            | Weapon::Warhammer
+            | Weapon::Longbow => Some((1, Dice::D8)),
+            Weapon::Glaive | Weapon::Halberd | Weapon::Pike | Weapon::HeavyCrossbow => {
+                Some((1, Dice::D10))
+            }
+            Weapon::Greataxe | Weapon::Lance => Some((1, Dice::D12)),
+            Weapon::Greatsword | Weapon::Maul => Some((2, Dice::D6)),
+            Weapon::Blowgun | Weapon::Net => None,
+        }
+    }
+
+    /// The die used when a versatile weapon is held in two hands.
+    pub fn versatile_die(&self) -> Option<Dice> {
+        match self {
+            Weapon::Quarterstaff | Weapon::Spear | Weapon::Trident => Some(Dice::D8),
+            Weapon::Battleaxe | Weapon::Longsword | Weapon::Warhammer => Some(Dice::D10),
+            _ => None,
+        }
+    }
+
+    pub fn damage_type(&self) -> Option<DamageType> {
+        match self {
+            Weapon::Club
+            | Weapon::Greatclub
+            | Weapon::LightHammer
+            | Weapon::Mace
+            | Weapon::Quarterstaff
+            | Weapon::Sling
+            | Weapon::Flail
+            | Weapon::Maul
+            | Weapon::Warhammer => Some(DamageType::Bludgeoning),
+            Weapon::Handaxe
+            | Weapon::Sickle
+            | Weapon::Battleaxe
+            | Weapon::Glaive
+            | Weapon::Greataxe
+            | Weapon::Greatsword
+            | Weapon::Halberd
+            | Weapon::Longsword
+            | Weapon::Scimitar
+            | Weapon::Whip => Some(DamageType::Slashing),
+            Weapon::Net => None,
+            _ => Some(DamageType::Piercing),
+        }
+    }
+
+    pub fn properties(&self) -> Vec<WeaponProperty> {
+        use WeaponProperty::*;
+        match self {
+            Weapon::Club | Weapon::Sickle => vec![Light],
+            Weapon::Dagger => vec![Finesse, Light, Thrown],
+            Weapon::Greatclub => vec![TwoHanded],
+            Weapon::Handaxe | Weapon::LightHammer => vec![Light, Thrown],
+            Weapon::Javelin => vec![Thrown],
+            Weapon::Quarterstaff | Weapon::Battleaxe | Weapon::Longsword | Weapon::Warhammer => {
+                vec![Versatile]
+            }
+            Weapon::Spear | Weapon::Trident => vec![Thrown, Versatile],
+            Weapon::LightCrossbow => vec![Ammunition, Loading, TwoHanded],
+            Weapon::Dart => vec![Finesse, Thrown],
+            Weapon::Shortbow => vec![Ammunition, TwoHanded],
+            Weapon::Sling => vec![Ammunition],
+            Weapon::Glaive | Weapon::Halberd | Weapon::Pike => vec![Heavy, Reach, TwoHanded],
+            Weapon::Greataxe | Weapon::Greatsword | Weapon::Maul => vec![Heavy, TwoHanded],
+            Weapon::Lance => vec![Reach, Special],
+            Weapon::Rapier => vec![Finesse],
+            Weapon::Scimitar | Weapon::Shortsword => vec![Finesse, Light],
+            Weapon::Whip => vec![Finesse, Reach],
+            Weapon::Blowgun => vec![Ammunition, Loading],
+            Weapon::HandCrossbow => vec![Ammunition, Light, Loading],
+            Weapon::HeavyCrossbow => vec![Ammunition, Heavy, Loading, TwoHanded],
+            Weapon::Longbow => vec![Ammunition, Heavy, TwoHanded],
+            Weapon::Net => vec![Special, Thrown],
+            Weapon::Mace | Weapon::Flail | Weapon::Morningstar | Weapon::WarPick => vec![],
+        }
+    }
+
+    pub fn has_property(&self, property: WeaponProperty) -> bool {
+        self.properties().contains(&property)
+    }
+
+    /// Normal and long range in feet, for ranged and thrown weapons.
+    pub fn range(&self) -> Option<(u16, u16)> {
+        match self {
+            Weapon::Dagger
+            | Weapon::Handaxe
+            | Weapon::LightHammer
+            | Weapon::Spear
+            | Weapon::Dart
+            | Weapon::Trident => Some((20, 60)),
+            Weapon::Javelin | Weapon::Sling | Weapon::HandCrossbow => Some((30, 120)),
+            Weapon::LightCrossbow | Weapon::Shortbow => Some((80, 320)),
+            Weapon::Blowgun => Some((25, 100)),
+            Weapon::HeavyCrossbow => Some((100, 400)),
+            Weapon::Longbow => Some((150, 600)),
+            Weapon::Net => Some((5, 15)),
+            _ => None,
+        }
+    }
+
+    /// Weight in pounds.
+    pub fn weight(&self) -> f32 {
+        match self {
+            Weapon::Sling => 0.0,
+            Weapon::Dart => 0.25,
+            Weapon::Dagger | Weapon::Blowgun => 1.0,
+            Weapon::Club
+            | Weapon::Handaxe
+            | Weapon::Javelin
+            | Weapon::LightHammer
+            | Weapon::Sickle
+            | Weapon::Shortbow
+            | Weapon::Flail
+            | Weapon::Rapier
+            | Weapon::Shortsword
+            | Weapon::WarPick
+            | Weapon::Warhammer
+            | Weapon::Longbow => 2.0,
+            Weapon::Spear
+            | Weapon::Longsword
+            | Weapon::Scimitar
+            | Weapon::Whip
+            | Weapon::HandCrossbow
+            | Weapon::Net => 3.0,
+            Weapon::Mace
+            | Weapon::Quarterstaff
+            | Weapon::Battleaxe
+            | Weapon::Morningstar
+            | Weapon::Trident => 4.0,
+            Weapon::LightCrossbow => 5.0,
+            Weapon::Glaive | Weapon::Greatsword | Weapon::Halberd | Weapon::Lance => 6.0,
+            Weapon::Greataxe => 7.0,
+            Weapon::Greatclub | Weapon::Maul => 10.0,
+            Weapon::Pike | Weapon::HeavyCrossbow => 18.0,
+        }
+    }
+
+    /// Price in copper pieces.
+    pub fn price(&self) -> u32 {
+        match self {
+            Weapon::Dart => 5,
+            Weapon::Club | Weapon::Sling => 10,
+            Weapon::Greatclub | Weapon::Quarterstaff => 20,
+            Weapon::Javelin => 50,
+            Weapon::Sickle | Weapon::Spear | Weapon::Net => 100,
+            Weapon::Dagger | Weapon::LightHammer | Weapon::Whip => 200,
+            Weapon::Handaxe | Weapon::Mace | Weapon::Pike | Weapon::Trident | Weapon::WarPick => {
+                500
+            }
+            Weapon::Battleaxe
+            | Weapon::Flail
+            | Weapon::Lance
+            | Weapon::Maul
+            | Weapon::Shortsword
+            | Weapon::Blowgun => 1_000,
+            Weapon::Longsword | Weapon::Morningstar | Weapon::Warhammer => 1_500,
+            Weapon::Glaive | Weapon::Halberd => 2_000,
+            Weapon::LightCrossbow | Weapon::Shortbow | Weapon::Rapier | Weapon::Scimitar => 2_500,
+            Weapon::Greataxe => 3_000,
+            Weapon::Greatsword | Weapon::HeavyCrossbow | Weapon::Longbow => 5_000,
+            Weapon::HandCrossbow => 7_500,
+        }
+    }
+
+    /// Shortswords and simple melee weapons that aren't two-handed or heavy.
+    pub fn is_monk_weapon(&self) -> bool {
+        *self == Weapon::Shortsword
+            || (self.category() == WeaponCategory::Simple
+                && !self.is_ranged()
+                && !self.has_property(WeaponProperty::TwoHanded)
+                && !self.has_property(WeaponProperty::Heavy))
+    }
+
+    /// Damage before the ability modifier, labelled with its type. A Monk's
+    /// `martial_arts` die replaces the weapon's own if it is larger.
+    pub fn damage(&self, grip: Grip, martial_arts: Option<Dice>) -> Option<DiceExpression> {
+        let (count, mut die) = match self.dice() {
+            Some(dice) => dice,
+            None if *self == Weapon::Blowgun => {
+                let mut expression: DiceExpression = "1".parse().ok()?;
+                expression.label = Some(DamageType::Piercing.to_string());
+                return Some(expression);
+            }
+            None => return None,
+        };
+        if let (Grip::TwoHanded, Some(versatile)) = (grip, self.versatile_die()) {
+            die = versatile;
+        }
+        if let Some(martial_arts) = martial_arts {
+            if self.is_monk_weapon() && martial_arts.average() > count * die.average() {
+                die = martial_arts;
+            }
+        }
+        let mut expression = die.expression(count);
+        expression.label = self.damage_type().map(|t| t.to_string());
+        Some(expression)
+    }
+
+    /// A short summary, e.g. "1d8 slashing, Versatile (1d10)".
+    pub fn describe(&self) -> String {
+        let mut details = vec![match self.damage(Grip::OneHanded, None) {
+            Some(damage) => damage.to_string(),
+            None => String::from("no damage"),
+        }];
+        for property in self.properties() {
+            details.push(match (property, self.versatile_die(), self.range()) {
+                (WeaponProperty::Versatile, Some(die), _) => {
+                    format!("{} ({})", property, die.expression(1))
+                }
+                (WeaponProperty::Ammunition | WeaponProperty::Thrown, _, Some((near, far))) => {
+                    format!("{} ({}/{} ft.)", property, near, far)
+                }
+                _ => property.to_string(),
+            });
+        }
+        format!("{} ({})", self, details.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn weapons_roll_the_right_damage() {
+        let longsword = Weapon::Longsword;
+        assert_eq!(longsword.category(), WeaponCategory::Martial);
+        assert_eq!(
+            longsword.damage(Grip::TwoHanded, None).unwrap().to_string(),
+            "1d10 slashing"
+        );
+        assert_eq!(
+            Weapon::Greatsword.describe(),
+            "Greatsword (2d6 slashing, Heavy, Two-Handed)"
+        );
+
+        // A Monk's d6 replaces a Quarterstaff's d6 only when larger, and
+        // never on a weapon that isn't a Monk weapon.
+        let quarterstaff = Weapon::Quarterstaff;
+        assert!(quarterstaff.is_monk_weapon());
+        assert_eq!(
+            quarterstaff
+                .damage(Grip::OneHanded, Some(Dice::D8))
+                .unwrap()
+                .to_string(),
+            "1d8 bludgeoning"
+        );
+        assert!(!Weapon::Greataxe.is_monk_weapon());
+        assert_eq!(
+            Weapon::Blowgun
+                .damage(Grip::OneHanded, None)
+                .unwrap()
+                .average(),
+            1
+        );
+        assert!(Weapon::Net.damage(Grip::OneHanded, None).is_none());
+    }
+}
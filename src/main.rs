@@ -5,17 +5,22 @@ use chrono::prelude::*;
 use data::background::*;
 use data::character::*;
 use data::classes::*;
+use data::dice::RollResult;
+use data::feats::take_feat;
 use data::gender::*;
 use data::names;
 use data::races::*;
+use data::rng::GameRng;
+use data::skills::{choose_skills, Skill};
 use data::stats::*;
 use data::utils::*;
 use dialoguer::theme::ColorfulTheme;
 use dialoguer::Confirm;
 use dialoguer::Input;
+use dialoguer::Select;
 use rand::prelude::*;
 use serde_yaml;
-use state::play_object::PlayObject;
+use state::play_object::{multiclass_options, PlayObject, SAVE_FILE};
 use std::fmt::Display;
 use std::{
     fs,
@@ -35,49 +40,48 @@ use crate::state::play_state::PlayState;
 //     }
 // }
 
-fn choose_and_stringify<T>(slice: T) -> String
+fn choose_and_stringify<T>(slice: T, rng: &mut GameRng) -> String
 where
     T: IntoIterator,
     T::Item: Display,
 {
-    let mut rng = rand::thread_rng();
-    slice.into_iter().choose(&mut rng).unwrap().to_string()
+    slice.into_iter().choose(rng).unwrap().to_string()
 }
 
-fn random_name_from_race_gender(race: &Race, gender: &Gender) -> String {
+fn random_name_from_race_gender(race: &Race, gender: &Gender, rng: &mut GameRng) -> String {
     match race {
         Race::Dragonborn => match gender {
-            Gender::Male => choose_and_stringify(names::DRAGONBORN_MALE),
-            Gender::Female | Gender::None => choose_and_stringify(names::DRAGONBORN_FEMALE),
+            Gender::Male => choose_and_stringify(names::DRAGONBORN_MALE, rng),
+            Gender::Female | Gender::None => choose_and_stringify(names::DRAGONBORN_FEMALE, rng),
         },
         Race::Dwarf(_) => match gender {
-            Gender::Male => choose_and_stringify(names::DWARF_MALE),
-            Gender::Female | Gender::None => choose_and_stringify(names::DWARF_FEMALE),
+            Gender::Male => choose_and_stringify(names::DWARF_MALE, rng),
+            Gender::Female | Gender::None => choose_and_stringify(names::DWARF_FEMALE, rng),
         },
         Race::Elf(_) => match gender {
-            Gender::Male => choose_and_stringify(names::ELF_MALE),
-            Gender::Female | Gender::None => choose_and_stringify(names::ELF_FEMALE),
+            Gender::Male => choose_and_stringify(names::ELF_MALE, rng),
+            Gender::Female | Gender::None => choose_and_stringify(names::ELF_FEMALE, rng),
         },
         Race::Halfling(_) | Race::Gnome(_) => match gender {
-            Gender::Male => choose_and_stringify(names::HALFLING_MALE),
-            Gender::Female | Gender::None => choose_and_stringify(names::HALFLING_FEMALE),
+            Gender::Male => choose_and_stringify(names::HALFLING_MALE, rng),
+            Gender::Female | Gender::None => choose_and_stringify(names::HALFLING_FEMALE, rng),
         },
         Race::HalfOrc => match gender {
-            Gender::Male => choose_and_stringify(names::HALFORC_MALE),
-            Gender::Female | Gender::None => choose_and_stringify(names::HALFORC_FEMALE),
+            Gender::Male => choose_and_stringify(names::HALFORC_MALE, rng),
+            Gender::Female | Gender::None => choose_and_stringify(names::HALFORC_FEMALE, rng),
         },
         Race::Human(_) | Race::HalfElf => match gender {
-            Gender::Male => choose_and_stringify(names::HUMAN_MALE),
-            Gender::Female | Gender::None => choose_and_stringify(names::HUMAN_FEMALE),
+            Gender::Male => choose_and_stringify(names::HUMAN_MALE, rng),
+            Gender::Female | Gender::None => choose_and_stringify(names::HUMAN_FEMALE, rng),
         },
         Race::Tiefling => match gender {
-            Gender::Male => choose_and_stringify(names::TIEFLING_MALE),
-            Gender::Female | Gender::None => choose_and_stringify(names::TIEFLING_FEMALE),
+            Gender::Male => choose_and_stringify(names::TIEFLING_MALE, rng),
+            Gender::Female | Gender::None => choose_and_stringify(names::TIEFLING_FEMALE, rng),
         },
     }
 }
 
-fn choose_name(race: &Race, gender: &Gender) -> String {
+fn choose_name(race: &Race, gender: &Gender, rng: &mut GameRng) -> String {
     let name_result: Result<String, io::Error> = Input::with_theme(&ColorfulTheme::default())
         .allow_empty(true)
         .show_default(false)
@@ -89,10 +93,10 @@ fn choose_name(race: &Race, gender: &Gender) -> String {
             if name.chars().count() > 0 {
                 name.to_owned()
             } else {
-                random_name_from_race_gender(&race, &gender)
+                random_name_from_race_gender(race, gender, rng)
             }
         }
-        Err(_) => random_name_from_race_gender(&race, &gender),
+        Err(_) => random_name_from_race_gender(race, gender, rng),
     };
 
     pretty_print(&format!("\nYour choice: {}\n", result), BLUE, true);
@@ -109,15 +113,17 @@ fn is_valid_level(input: &String) -> bool {
     }
 }
 
-fn choose_level() -> u8 {
+fn choose_level(class: &Class, max_level: u8) -> u8 {
     let level: String = Input::with_theme(&ColorfulTheme::default())
-        .with_prompt("What is your character's level?")
+        .with_prompt(format!(
+            "How many levels does your character have in {}?",
+            class
+        ))
         .default(String::from("1"))
         .validate_with(|input: &String| -> Result<(), &str> {
-            if is_valid_level(&input) {
-                Ok(())
-            } else {
-                Err("That is not a valid level")
+            match input.parse::<u8>() {
+                Ok(level) if is_valid_level(input) && level <= max_level => Ok(()),
+                _ => Err("That is not a valid level"),
             }
         })
         .interact_text()
@@ -185,21 +191,25 @@ fn match_stat(stat: &Stat, new_value: u8) -> Stat {
     }
 }
 
-fn choose_stats() -> [Stat; 6] {
-    pretty_print(
-        &format!("You will need to provide values for {}...", &Stat::list()),
-        BLUE,
-        true,
-    );
-
-    let mut result = [
+fn empty_stats() -> [Stat; 6] {
+    [
         Stat::Str(0),
         Stat::Dex(0),
         Stat::Con(0),
         Stat::Int(0),
         Stat::Wis(0),
         Stat::Chr(0),
-    ];
+    ]
+}
+
+fn choose_stats_manually() -> [Stat; 6] {
+    pretty_print(
+        &format!("You will need to provide values for {}...", &Stat::list()),
+        BLUE,
+        true,
+    );
+
+    let mut result = empty_stats();
     for stat in &mut result {
         let prompt = format!("Enter a value for {}:", stat.show_name());
         let stat_input: String = Input::with_theme(&ColorfulTheme::default())
@@ -207,7 +217,7 @@ fn choose_stats() -> [Stat; 6] {
             .default(String::from("10"))
             .show_default(true)
             .validate_with(|input: &String| -> Result<(), &str> {
-                if is_valid_level(&input) {
+                if is_valid_level(input) {
                     Ok(())
                 } else {
                     Err("That is not a valid stat range")
@@ -216,78 +226,389 @@ fn choose_stats() -> [Stat; 6] {
             .interact_text()
             .unwrap();
         let stat_value: u8 = stat_input.parse().unwrap();
-        *stat = match_stat(&stat, stat_value);
+        *stat = match_stat(stat, stat_value);
     }
     println!("\n");
     result
 }
 
-fn create_new_character() -> PlayObject {
+fn assign_scores(mut scores: Vec<u8>) -> [Stat; 6] {
+    let mut result = empty_stats();
+    for stat in &mut result {
+        let selection = if scores.len() == 1 {
+            0
+        } else {
+            let options: Vec<String> = scores.iter().map(|s| s.to_string()).collect();
+            Select::with_theme(&ColorfulTheme::default())
+                .with_prompt(format!(
+                    "Which score would you like to assign to {}?",
+                    stat.show_name()
+                ))
+                .default(0)
+                .items(&options)
+                .interact()
+                .unwrap()
+        };
+        *stat = match_stat(stat, scores.remove(selection));
+    }
+    pretty_print(
+        &format!(
+            "\nYour scores: {}\n",
+            result
+                .iter()
+                .map(|s| format!("{} {}", s.show_name(), s.value()))
+                .collect::<Vec<String>>()
+                .join(", ")
+        ),
+        BLUE,
+        true,
+    );
+    result
+}
+
+fn roll_stats(rng: &mut GameRng) -> ([Stat; 6], Vec<String>) {
+    let minimum_total: String = Input::with_theme(&ColorfulTheme::default())
+        .with_prompt("Reroll the whole set if the scores total less than? (0 keeps any set)")
+        .default(String::from("0"))
+        .validate_with(|input: &String| -> Result<(), &str> {
+            match input.parse::<u8>() {
+                Ok(0..=90) => Ok(()),
+                _ => Err("Please enter a total between 0 and 90"),
+            }
+        })
+        .interact_text()
+        .unwrap();
+    let minimum_total: i32 = minimum_total.parse().unwrap();
+
+    let mut log = Vec::new();
+    let mut scores: Vec<u8> = loop {
+        let rolls: Vec<RollResult> = (0..6).map(|_| roll_ability_score(rng)).collect();
+        for roll in &rolls {
+            pretty_print(&format!("You rolled {}", roll), BLUE, true);
+            log.push(roll.to_string());
+        }
+        let total: i32 = rolls.iter().map(|r| r.total).sum();
+        if total >= minimum_total {
+            break rolls.iter().map(|r| r.total as u8).collect();
+        }
+        pretty_print(
+            &format!(
+                "Your scores total {}, which is less than {}. Rerolling...\n",
+                total, minimum_total
+            ),
+            RED,
+            true,
+        );
+        log.push(format!(
+            "Rerolled: total {} was below {}",
+            total, minimum_total
+        ));
+    };
+    scores.sort_unstable_by(|a, b| b.cmp(a));
+    println!();
+    (assign_scores(scores), log)
+}
+
+fn point_buy_stats() -> [Stat; 6] {
+    loop {
+        let mut remaining = POINT_BUY_BUDGET;
+        let mut result = empty_stats();
+        for stat in &mut result {
+            pretty_print(
+                &format!("\nYou have {} points remaining.", remaining),
+                BLUE,
+                true,
+            );
+            let options: Vec<(u8, u8)> = (8..=15)
+                .filter_map(|score| point_buy_cost(score).map(|cost| (score, cost)))
+                .filter(|(_, cost)| *cost <= remaining)
+                .collect();
+            let labels: Vec<String> = options
+                .iter()
+                .map(|(score, cost)| {
+                    format!("{} (costs {}, leaves {})", score, cost, remaining - cost)
+                })
+                .collect();
+            let selection = Select::with_theme(&ColorfulTheme::default())
+                .with_prompt(format!("Choose a score for {}:", stat.show_name()))
+                .default(0)
+                .items(&labels)
+                .interact()
+                .unwrap();
+            let (score, cost) = options[selection];
+            remaining -= cost;
+            *stat = match_stat(stat, score);
+        }
+
+        pretty_print(
+            &format!(
+                "\nYour scores: {}",
+                result
+                    .iter()
+                    .map(|s| format!("{} {}", s.show_name(), s.value()))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+            BLUE,
+            true,
+        );
+        if remaining > 0 {
+            pretty_print(
+                &format!("You have {} unspent points.", remaining),
+                RED,
+                true,
+            );
+        }
+        let keep = Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt("Keep these scores? ('NO' starts the point buy over)")
+            .default(true)
+            .wait_for_newline(true)
+            .interact()
+            .unwrap();
+        if keep {
+            println!();
+            break result;
+        }
+    }
+}
+
+/// Applies the racial increases, returning the new scores and how much each
+/// actually rose once capped at 20.
+fn apply_increases(stats: [Stat; 6], increases: &[u8; 6]) -> ([Stat; 6], [u8; 6]) {
+    let mut result = stats;
+    let mut applied = [0; 6];
+    for (i, stat) in result.iter_mut().enumerate() {
+        let increased = stat.increase(increases[i]);
+        applied[i] = increased.value() - stat.value();
+        *stat = increased;
+    }
+    (result, applied)
+}
+
+fn choose_stats(rng: &mut GameRng) -> ([Stat; 6], StatMethod) {
+    let method = StatMethod::choose(rng);
+    match method {
+        StatMethod::Manual => (choose_stats_manually(), method),
+        StatMethod::Rolled(_) => {
+            let (stats, log) = roll_stats(rng);
+            (stats, StatMethod::Rolled(log))
+        }
+        StatMethod::StandardArray => (assign_scores(STANDARD_ARRAY.to_vec()), method),
+        StatMethod::PointBuy => (point_buy_stats(), method),
+    }
+}
+
+/// Offers further classes to multiclass into until the player is done or
+/// reaches level 20.
+fn choose_multiclasses(classes: &mut Vec<ClassLevel>, stats: &[Stat], rng: &mut GameRng) {
+    loop {
+        let total_level: u8 = classes.iter().map(|c| c.level).sum();
+        if total_level >= 20 {
+            break;
+        }
+        let multiclass = Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt("Would you like to add levels in another class? (multiclassing)")
+            .default(false)
+            .wait_for_newline(true)
+            .interact()
+            .unwrap();
+        if !multiclass {
+            break;
+        }
+        let options = multiclass_options(classes, stats);
+        if options.is_empty() {
+            pretty_print("There are no classes you can multiclass into.", RED, true);
+            break;
+        }
+        let class: Class = choose_value("Which class would you like to add?", &options, rng);
+        let level = choose_level(&class, 20 - total_level);
+        classes.push(ClassLevel { class, level });
+    }
+}
+
+fn choose_skills_and_expertise(
+    classes: &[ClassLevel],
+    background: &Background,
+) -> (Vec<Skill>, Vec<Skill>) {
+    let mut skills = background.skills().to_vec();
+    let names: Vec<String> = skills.iter().map(|s| s.to_string()).collect();
+    pretty_print(
+        &format!(
+            "Your {} background grants proficiency in {}.\n",
+            background,
+            names.join(" and ")
+        ),
+        BLUE,
+        true,
+    );
+
+    for (i, class_level) in classes.iter().enumerate() {
+        let class = &class_level.class;
+        let (count, options) = match i {
+            0 => class.skill_choices(),
+            _ => class.multiclass_skill_choices(),
+        };
+        let prompt = format!("Choose a {} skill proficiency", class);
+        skills.append(&mut choose_skills(&prompt, count, &options, &skills));
+    }
+
+    let expertise = choose_skills(
+        "Choose a skill to gain Expertise in",
+        classes
+            .iter()
+            .map(|c| c.class.expertise_count(c.level))
+            .sum(),
+        &skills,
+        &[],
+    );
+    (skills, expertise)
+}
+
+fn create_new_character(rng: &mut GameRng) -> PlayObject {
     let one_second = time::Duration::from_secs(1);
     // pretty_print("Let's get started.", BLUE, true);
     thread::sleep(one_second);
 
-    let race = Race::choose();
+    let race = Race::choose(rng);
 
     let race: Race = match race {
-        Race::Dwarf(_) => race.choose_subrace(),
-        Race::Elf(_) => race.choose_subrace(),
-        Race::Halfling(_) => race.choose_subrace(),
-        Race::Human(_) => race.choose_subrace(),
-        Race::Gnome(_) => race.choose_subrace(),
+        Race::Dwarf(_) => race.choose_subrace(rng),
+        Race::Elf(_) => race.choose_subrace(rng),
+        Race::Halfling(_) => race.choose_subrace(rng),
+        Race::Human(_) => race.choose_subrace(rng),
+        Race::Gnome(_) => race.choose_subrace(rng),
         _ => race,
     };
 
-    let gender = Gender::choose();
+    let gender = Gender::choose(rng);
 
-    let name = choose_name(&race, &gender);
+    let name = choose_name(&race, &gender, rng);
 
-    let mut class = Class::choose();
+    let class = Class::choose(rng);
 
-    let level: u8 = choose_level();
+    let level: u8 = choose_level(&class, 20);
 
-    if level > 2 {
-        class = class.choose_subclass();
-    }
+    let mut classes = vec![ClassLevel { class, level }];
+
+    let background = Background::choose(rng);
 
-    let background = Background::choose();
+    let (stats, stat_method) = choose_stats(rng);
+
+    let racial_increases = race.choose_ability_increases();
+
+    let (stats, racial_increases) = apply_increases(stats, &racial_increases);
+
+    choose_multiclasses(&mut classes, &stats, rng);
+
+    for class_level in classes.iter_mut() {
+        if class_level.level >= class_level.class.subclass_level() {
+            class_level.class = class_level.class.choose_subclass(rng);
+        }
+    }
 
-    let stats: [Stat; 6] = choose_stats();
+    let (skills, expertise) = choose_skills_and_expertise(&classes, &background);
 
     let use_average_dice = choose_average_dice();
 
-    let status = Status::new(&stats, &race, &class, &level, use_average_dice);
+    let status = Status::new(&stats, &race, &classes, use_average_dice, rng);
 
-    let character = Character {
+    let level = classes.iter().map(|c| c.level).sum();
+
+    let mut character = Character {
         name,
         level,
         background,
         race,
-        class,
+        classes,
         stats,
+        stat_method,
+        racial_increases,
+        ability_improvements: [0; 6],
+        skills,
+        expertise,
+        feats: vec![],
+        inventory: Default::default(),
+        purse: Default::default(),
         status,
         gender,
     };
 
-    PlayObject {
+    if character.race == Race::Human(Human::Variant) {
+        pretty_print("As a Variant Human, you start with a feat.", BLUE, true);
+        take_feat(&mut character, rng);
+    }
+
+    let mut play_object = PlayObject {
         character,
         created_at: Utc::now(),
         updated_at: Some(Utc::now()),
         last_played_at: Utc::now(),
-    }
+        ledger: vec![],
+    };
+    play_object.choose_starting_equipment(rng);
+    play_object
 }
 
-fn load_character_or_new(play_object: PlayObject) -> PlayObject {
+fn load_character_or_new(mut play_object: PlayObject, rng: &mut GameRng) -> PlayObject {
     play_object.character.display(true);
 
     if choose_yes_or_no(&play_object.character.name) {
+        let level_up = Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!(
+                "Would you like to level up {}?",
+                play_object.character.name
+            ))
+            .default(false)
+            .wait_for_newline(true)
+            .interact()
+            .unwrap();
+        if level_up {
+            play_object.level_up(rng);
+        }
         play_object
     } else {
-        create_new_character()
+        create_new_character(rng)
     }
 }
 
+fn parse_seed(args: &[String]) -> Result<Option<u64>, String> {
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let value = match arg.strip_prefix("--seed") {
+            Some("") => args.next().map(|v| v.as_str()),
+            Some(rest) => rest.strip_prefix('='),
+            None => continue,
+        };
+        return match value.map(|v| v.parse::<u64>()) {
+            Some(Ok(seed)) => Ok(Some(seed)),
+            _ => Err(String::from(
+                "--seed expects a whole number, e.g. --seed 1234",
+            )),
+        };
+    }
+    Ok(None)
+}
+
 fn main() -> Result<(), serde_yaml::Error> {
+    let args: Vec<String> = std::env::args().collect();
+    let seed = match parse_seed(&args) {
+        Ok(seed) => seed,
+        Err(e) => {
+            pretty_print(&e, RED, true);
+            return Ok(());
+        }
+    };
+    let mut rng = GameRng::new(seed);
+    pretty_print(
+        &format!(
+            "Session seed: {} (pass --seed {} to replay this session)",
+            rng.seed(),
+            rng.seed()
+        ),
+        BLUE,
+        true,
+    );
+
     // let stats = [
     //     Stat::Str(10),
     //     Stat::Dex(18),
@@ -319,7 +640,7 @@ fn main() -> Result<(), serde_yaml::Error> {
     //     last_played_at: Utc::now(),
     // };
 
-    let data = match fs::read_to_string("./output.yaml") {
+    let data = match fs::read_to_string(SAVE_FILE) {
         Ok(s) => s,
         Err(_) => {
             pretty_print("No character data found on file.", RED, true);
@@ -331,8 +652,8 @@ fn main() -> Result<(), serde_yaml::Error> {
 
     // let play_object: PlayObject =
     //     serde_yaml::from_str(&data).expect("Character YAML not properly configured");
-    let play_object: PlayObject = match serde_yaml::from_str(&data) {
-        Ok(play_object) => load_character_or_new(play_object),
+    let play_object: PlayObject = match PlayObject::from_yaml(&data) {
+        Ok(play_object) => load_character_or_new(play_object, &mut rng),
         Err(_err) => {
             // println!("playobject error: {:?}", err);
             pretty_print(
@@ -340,20 +661,20 @@ fn main() -> Result<(), serde_yaml::Error> {
                 BLUE,
                 true,
             );
-            create_new_character()
+            create_new_character(&mut rng)
         }
     };
 
     let s = serde_yaml::to_string(&play_object)?;
     // println!("{:?}", s);
-    fs::write("./output.yaml", &s).expect("Unable to write file");
+    fs::write(SAVE_FILE, &s).expect("Unable to write file");
 
     play_object.character.display(true);
 
-    let mut play_state = PlayState::new(play_object);
+    let mut play_state = PlayState::new(play_object, rng);
 
     while play_state.active {
-        play_state.take_turn()
+        play_state.next_turn()
     }
 
     Ok(())
@@ -362,6 +683,27 @@ fn main() -> Result<(), serde_yaml::Error> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use state::play_object::legacy_save;
+
+    #[test]
+    fn parses_seed_argument() {
+        let args = |s: &str| -> Vec<String> { s.split(' ').map(String::from).collect() };
+        assert_eq!(parse_seed(&args("dnd_player")), Ok(None));
+        assert_eq!(parse_seed(&args("dnd_player --seed 42")), Ok(Some(42)));
+        assert_eq!(parse_seed(&args("dnd_player --seed=7")), Ok(Some(7)));
+        assert!(parse_seed(&args("dnd_player --seed")).is_err());
+        assert!(parse_seed(&args("dnd_player --seed abc")).is_err());
+    }
+
+    #[test]
+    fn seeded_names_are_reproducible() {
+        let race = Race::Elf(Elf::WoodElf);
+        let first =
+            random_name_from_race_gender(&race, &Gender::Female, &mut GameRng::new(Some(3)));
+        let second =
+            random_name_from_race_gender(&race, &Gender::Female, &mut GameRng::new(Some(3)));
+        assert_eq!(first, second);
+    }
 
     #[test]
     fn can_read_character_yaml() -> Result<(), String> {
@@ -371,6 +713,29 @@ mod tests {
         }
     }
 
+    #[test]
+    fn score_increases_record_what_was_applied() {
+        let stats = [
+            Stat::Str(19),
+            Stat::Dex(14),
+            Stat::Con(13),
+            Stat::Int(12),
+            Stat::Wis(10),
+            Stat::Chr(8),
+        ];
+        let (stats, applied) = apply_increases(stats, &[2, 0, 1, 0, 0, 0]);
+        assert_eq!(stats[0].value(), 20);
+        assert_eq!(applied, [1, 0, 1, 0, 0, 0]);
+
+        let mut character = legacy_save().character;
+        for _ in 0..3 {
+            character.improve_ability(1, 2);
+        }
+        // Dexterity 16 only had room for 4 more.
+        assert_eq!(character.stats[1].value(), 20);
+        assert_eq!(character.ability_improvements[1], 4);
+    }
+
     // #[test]
     // fn can_deserialize_character_yaml_as_struct() -> Result<(), serde_yaml::Error> {
     //     let data =
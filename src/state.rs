@@ -1,2 +1,4 @@
+pub mod actions;
+pub mod encounter;
 pub mod play_object;
 pub mod play_state;
@@ -0,0 +1,371 @@
+use std::fmt;
+
+use strum_macros::{Display, EnumIter, EnumString};
+
+use crate::data::character::Character;
+use crate::data::classes::Class;
+use crate::data::damage::DamageType;
+use crate::data::resources::Resource;
+use crate::data::weapons::WeaponProperty;
+
+#[derive(Debug, Clone, Copy, PartialEq, EnumIter, EnumString, Display)]
+#[strum(ascii_case_insensitive, serialize_all = "title_case")]
+pub enum Action {
+    Attack,
+    #[strum(serialize = "Cast a Spell")]
+    CastASpell,
+    Dash,
+    Disengage,
+    Dodge,
+    Help,
+    Hide,
+    Ready,
+    Search,
+    #[strum(serialize = "Use an Object")]
+    UseAnObject,
+}
+
+impl Action {
+    pub fn description(&self) -> &str {
+        match self {
+            Action::Attack => "Make a melee or ranged attack",
+            Action::CastASpell => "Cast a spell with a casting time of 1 action",
+            Action::Dash => "Gain extra movement equal to your speed this turn",
+            Action::Disengage => "Your movement doesn't provoke opportunity attacks this turn",
+            Action::Dodge => {
+                "Attacks against you have disadvantage and you have advantage on Dex saves"
+            }
+            Action::Help => "Give an ally advantage on their next check or attack",
+            Action::Hide => "Make a Dexterity (Stealth) check to hide",
+            Action::Ready => "Prepare an action to take as a reaction to a trigger",
+            Action::Search => "Make a Wisdom (Perception) or Intelligence (Investigation) check",
+            Action::UseAnObject => "Interact with a second object, drink a potion, etc.",
+        }
+    }
+}
+
+/// Bonus actions all come from class features or spells, so which ones are
+/// offered depends on the character.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BonusAction {
+    MartialArts,
+    FlurryOfBlows,
+    /// A Rogue's Dash, Disengage or Hide.
+    CunningAction(Action),
+    Rage,
+    SecondWind,
+    BardicInspiration,
+    OffHandAttack,
+    Spell,
+}
+
+impl BonusAction {
+    /// The class resource the bonus action uses up, if any.
+    pub fn cost(&self) -> Option<Resource> {
+        match self {
+            BonusAction::FlurryOfBlows => Some(Resource::Ki),
+            BonusAction::Rage => Some(Resource::Rage),
+            BonusAction::SecondWind => Some(Resource::SecondWind),
+            BonusAction::BardicInspiration => Some(Resource::BardicInspiration),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for BonusAction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BonusAction::MartialArts => write!(f, "Martial Arts (unarmed strike)"),
+            BonusAction::FlurryOfBlows => write!(f, "Flurry of Blows (1 ki)"),
+            BonusAction::CunningAction(action) => write!(f, "Cunning Action: {}", action),
+            BonusAction::Rage => write!(f, "Rage"),
+            BonusAction::SecondWind => write!(f, "Second Wind"),
+            BonusAction::BardicInspiration => write!(f, "Bardic Inspiration"),
+            BonusAction::OffHandAttack => write!(f, "Off-hand Attack"),
+            BonusAction::Spell => write!(f, "Cast a Bonus Action Spell"),
+        }
+    }
+}
+
+/// What the character has spent on their current turn, and the effects of
+/// their action, which last until the start of their next turn.
+#[derive(Debug, Default)]
+pub struct Turn {
+    pub action: Option<Action>,
+    pub bonus_action: Option<BonusAction>,
+    /// The reaction taken since the start of this turn; it refreshes each round.
+    pub reaction: Option<Reaction>,
+    /// The trigger and action prepared with the Ready action.
+    pub readied: Option<(String, Action)>,
+    /// Whether a spell slot was spent this turn, which rules out a bonus
+    /// action spell.
+    pub leveled_spell: bool,
+    /// Whether Shield was cast as a reaction, adding 5 to AC until the start
+    /// of the next turn.
+    pub shield: bool,
+}
+
+impl Turn {
+    pub fn action_used(&self) -> bool {
+        self.action.is_some()
+    }
+
+    pub fn bonus_action_used(&self) -> bool {
+        self.bonus_action.is_some()
+    }
+
+    pub fn reaction_used(&self) -> bool {
+        self.reaction.is_some()
+    }
+
+    /// Bonus actions the character's classes give them right now, none once
+    /// one has been taken. Martial Arts, Flurry of Blows and off-hand attacks
+    /// need the Attack action first, and an off-hand attack a light melee weapon.
+    pub fn bonus_actions(&self, character: &Character) -> Vec<BonusAction> {
+        if self.bonus_action_used() {
+            return Vec::new();
+        }
+        let attacked = self.action == Some(Action::Attack);
+        let monk = character.class_level(&Class::Monk(None));
+        let mut bonus_actions = Vec::new();
+
+        if monk >= 1 && attacked {
+            bonus_actions.push(BonusAction::MartialArts);
+        }
+        if monk >= 2 && attacked && character.resource_remaining(Resource::Ki) > 0 {
+            bonus_actions.push(BonusAction::FlurryOfBlows);
+        }
+        if character.class_level(&Class::Rogue(None)) >= 2 {
+            for action in [Action::Dash, Action::Disengage, Action::Hide] {
+                bonus_actions.push(BonusAction::CunningAction(action));
+            }
+        }
+        if !character.status.is_raging() && character.resource_remaining(Resource::Rage) > 0 {
+            bonus_actions.push(BonusAction::Rage);
+        }
+        if character.resource_remaining(Resource::SecondWind) > 0 {
+            bonus_actions.push(BonusAction::SecondWind);
+        }
+        if character.resource_remaining(Resource::BardicInspiration) > 0 {
+            bonus_actions.push(BonusAction::BardicInspiration);
+        }
+        let light_melee = character
+            .inventory
+            .weapons()
+            .iter()
+            .any(|w| w.has_property(WeaponProperty::Light) && !w.is_ranged());
+        if attacked && light_melee {
+            bonus_actions.push(BonusAction::OffHandAttack);
+        }
+        if !character.spellcasting_classes().is_empty() && !self.leveled_spell {
+            bonus_actions.push(BonusAction::Spell);
+        }
+        bonus_actions
+    }
+
+    /// Spends the turn's bonus action on `bonus_action`, along with the
+    /// resource it costs, and starts a Rage. Returns `false` if it isn't
+    /// available.
+    pub fn take_bonus_action(
+        &mut self,
+        bonus_action: BonusAction,
+        character: &mut Character,
+    ) -> bool {
+        if !self.bonus_actions(character).contains(&bonus_action) {
+            return false;
+        }
+        if let Some(resource) = bonus_action.cost() {
+            character.spend_resource(resource, 1);
+        }
+        if bonus_action == BonusAction::Rage {
+            character.status.rage_rounds = 10;
+        }
+        self.bonus_action = Some(bonus_action);
+        true
+    }
+
+    /// Whether only a cantrip can be cast as the action, after a bonus
+    /// action spell.
+    pub fn cantrip_only(&self) -> bool {
+        self.bonus_action == Some(BonusAction::Spell)
+    }
+
+    /// Whether `action` was taken this turn, either as the action or through
+    /// Cunning Action, so its effect (e.g. Dodge or Disengage) is still running.
+    pub fn is_active(&self, action: Action) -> bool {
+        self.action == Some(action) || self.bonus_action == Some(BonusAction::CunningAction(action))
+    }
+}
+
+/// What an attack is made as, which decides the weapons it can be made with.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AttackKind {
+    /// One of the attacks of the Attack action, or a readied attack.
+    Action,
+    /// The bonus attack of two-weapon fighting, with a light melee weapon.
+    OffHand,
+    /// A reaction to an enemy leaving reach, which is a melee attack.
+    Opportunity,
+}
+
+/// Something that happens outside the character's turn and may call for a reaction.
+#[derive(Debug, Clone, Copy, PartialEq, EnumIter, Display)]
+pub enum Trigger {
+    #[strum(serialize = "An enemy leaves your reach")]
+    EnemyLeavesReach,
+    #[strum(serialize = "You are hit by an attack")]
+    HitByAttack,
+    #[strum(serialize = "You are hit by a ranged weapon attack")]
+    HitByRangedWeapon,
+    #[strum(serialize = "A creature you can see casts a spell")]
+    SpellCast,
+    #[strum(serialize = "A creature you can see damages you")]
+    DamagedByCreature,
+    #[strum(serialize = "The trigger for your readied action happens")]
+    Readied,
+}
+
+impl Trigger {
+    /// Whether the trigger is an attack hitting the character, whose damage
+    /// is taken after any reaction to it.
+    pub fn is_hit(&self) -> bool {
+        matches!(self, Trigger::HitByAttack | Trigger::HitByRangedWeapon)
+    }
+}
+
+/// An attack that hit the character, which a reaction may turn into a miss
+/// or make deal less damage.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hit {
+    /// The attack roll's total.
+    pub attack: i32,
+    pub damage: i32,
+    pub damage_type: DamageType,
+    pub critical: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Display)]
+#[strum(serialize_all = "title_case")]
+pub enum Reaction {
+    OpportunityAttack,
+    Shield,
+    Counterspell,
+    UncannyDodge,
+    DeflectMissiles,
+    HellishRebuke,
+    ReadiedAction,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::classes::ClassLevel;
+    use crate::data::inventory::Item;
+    use crate::data::weapons::Weapon;
+    use crate::state::play_object::legacy_save;
+
+    fn add_class(character: &mut Character, class: Class, level: u8) {
+        character.classes.push(ClassLevel { class, level });
+        character.level += level;
+    }
+
+    #[test]
+    fn martial_arts_and_off_hand_attacks_follow_the_attack_action() {
+        let mut character = legacy_save().character;
+        let mut turn = Turn::default();
+        assert!(turn.bonus_actions(&character).is_empty());
+
+        turn.action = Some(Action::Attack);
+        assert_eq!(
+            turn.bonus_actions(&character),
+            [BonusAction::MartialArts, BonusAction::FlurryOfBlows]
+        );
+
+        // An off-hand attack needs a light melee weapon.
+        character.inventory.add(Item::from(Weapon::Longbow));
+        assert!(!turn
+            .bonus_actions(&character)
+            .contains(&BonusAction::OffHandAttack));
+        character.inventory.add(Item::from(Weapon::Handaxe));
+        assert!(turn
+            .bonus_actions(&character)
+            .contains(&BonusAction::OffHandAttack));
+    }
+
+    #[test]
+    fn only_one_bonus_action_is_taken_and_it_spends_its_resource() {
+        let mut character = legacy_save().character;
+        let mut turn = Turn {
+            action: Some(Action::Attack),
+            ..Turn::default()
+        };
+        assert_eq!(character.resource_remaining(Resource::Ki), 8);
+
+        assert!(turn.take_bonus_action(BonusAction::FlurryOfBlows, &mut character));
+        assert_eq!(character.resource_remaining(Resource::Ki), 7);
+        assert!(turn.bonus_action_used());
+        assert!(turn.bonus_actions(&character).is_empty());
+        assert!(!turn.take_bonus_action(BonusAction::MartialArts, &mut character));
+        assert_eq!(turn.bonus_action, Some(BonusAction::FlurryOfBlows));
+
+        // Without Ki left, Flurry of Blows isn't offered.
+        let mut turn = Turn {
+            action: Some(Action::Attack),
+            ..Turn::default()
+        };
+        assert!(character.spend_resource(Resource::Ki, 7));
+        assert_eq!(turn.bonus_actions(&character), [BonusAction::MartialArts]);
+        assert!(!turn.take_bonus_action(BonusAction::FlurryOfBlows, &mut character));
+        assert!(!turn.bonus_action_used());
+    }
+
+    #[test]
+    fn class_features_spend_their_uses() {
+        let mut character = legacy_save().character;
+        add_class(&mut character, Class::Fighter(None), 1);
+        add_class(&mut character, Class::Barbarian(None), 1);
+        add_class(&mut character, Class::Bard(None), 1);
+
+        let mut turn = Turn::default();
+        assert!(turn.take_bonus_action(BonusAction::Rage, &mut character));
+        assert!(character.status.is_raging());
+        assert_eq!(character.resource_remaining(Resource::Rage), 1);
+
+        // Already raging, so Rage isn't offered again.
+        let mut turn = Turn::default();
+        assert!(!turn.bonus_actions(&character).contains(&BonusAction::Rage));
+        assert!(turn.take_bonus_action(BonusAction::SecondWind, &mut character));
+        assert_eq!(character.resource_remaining(Resource::SecondWind), 0);
+
+        let mut turn = Turn::default();
+        assert!(!turn
+            .bonus_actions(&character)
+            .contains(&BonusAction::SecondWind));
+        let inspiration = character.resource_remaining(Resource::BardicInspiration);
+        assert!(turn.take_bonus_action(BonusAction::BardicInspiration, &mut character));
+        assert_eq!(
+            character.resource_remaining(Resource::BardicInspiration),
+            inspiration - 1
+        );
+    }
+
+    #[test]
+    fn bonus_action_spells_leave_only_cantrips() {
+        let mut character = legacy_save().character;
+        let mut turn = Turn::default();
+        assert!(!turn.bonus_actions(&character).contains(&BonusAction::Spell));
+
+        add_class(&mut character, Class::Wizard(None), 1);
+        assert!(turn.bonus_actions(&character).contains(&BonusAction::Spell));
+        assert!(!turn.cantrip_only());
+        assert!(turn.take_bonus_action(BonusAction::Spell, &mut character));
+        assert!(turn.cantrip_only());
+
+        // After a leveled spell, no bonus action spell.
+        let turn = Turn {
+            leveled_spell: true,
+            ..Turn::default()
+        };
+        assert!(!turn.bonus_actions(&character).contains(&BonusAction::Spell));
+    }
+}
@@ -0,0 +1,420 @@
+use rand::Rng;
+use term_table::{
+    row::Row,
+    table_cell::{Alignment, TableCell},
+    Table, TableStyle,
+};
+
+use crate::data::damage::{adjust_damage, DamageType};
+use crate::data::dice::{Dice, DiceExpression, RollResult};
+use crate::data::monsters::Monster;
+use crate::data::utils::format_modifier;
+
+/// Anyone taking turns in an encounter, in initiative order.
+#[derive(Debug, Clone)]
+pub struct Combatant {
+    pub name: String,
+    /// The character being played, whose turns are taken with the full turn menu.
+    pub is_player: bool,
+    pub initiative_modifier: i32,
+    /// Dexterity score, used to break ties in initiative.
+    pub dexterity: u8,
+    pub initiative: i32,
+    /// A hidden roll-off for ties that are still tied on modifier and Dexterity.
+    tiebreak: u32,
+    pub turns_taken: u32,
+    /// Holding their turn until after someone later in the order.
+    pub delayed: bool,
+    /// The stat block of a monster or NPC brought in from the bestiary.
+    pub stat_block: Option<Monster>,
+    /// Current and maximum HP, tracked for combatants with a stat block.
+    pub hit_points: Option<(i32, i32)>,
+}
+
+impl Combatant {
+    pub fn new<R: Rng + ?Sized>(
+        name: &str,
+        is_player: bool,
+        initiative_modifier: i32,
+        dexterity: u8,
+        rng: &mut R,
+    ) -> Combatant {
+        Combatant {
+            name: String::from(name),
+            is_player,
+            initiative_modifier,
+            dexterity,
+            initiative: 0,
+            tiebreak: rng.gen(),
+            turns_taken: 0,
+            delayed: false,
+            stat_block: None,
+            hit_points: None,
+        }
+    }
+
+    /// A monster from the bestiary, with its HP rolled or averaged.
+    pub fn from_monster<R: Rng + ?Sized>(
+        name: &str,
+        monster: &Monster,
+        use_average: bool,
+        rng: &mut R,
+    ) -> Combatant {
+        let dexterity = monster.dexterity();
+        let mut combatant = Combatant::new(
+            name,
+            false,
+            dexterity.get_modifier() as i32,
+            dexterity.value(),
+            rng,
+        );
+        let hit_points = monster.roll_hit_points(use_average, rng);
+        combatant.hit_points = Some((hit_points, hit_points));
+        combatant.stat_block = Some(monster.clone());
+        combatant
+    }
+
+    /// Rolls a d20 plus the initiative modifier.
+    pub fn roll_initiative<R: Rng + ?Sized>(&mut self, rng: &mut R) -> RollResult {
+        let roll = DiceExpression::from(Dice::D20)
+            .with_modifier(self.initiative_modifier)
+            .roll(rng);
+        self.initiative = roll.total;
+        roll
+    }
+
+    /// Applies damage through the stat block's resistances, immunities and
+    /// vulnerabilities, returning the HP actually lost.
+    pub fn take_damage(&mut self, amount: i32, damage_type: DamageType) -> i32 {
+        let defenses = match &self.stat_block {
+            Some(monster) => monster.damage_defenses(damage_type),
+            None => vec![],
+        };
+        let amount = adjust_damage(amount, &defenses);
+        match self.hit_points.as_mut() {
+            Some((current, _)) => {
+                let lost = amount.min(*current);
+                *current -= lost;
+                lost
+            }
+            None => 0,
+        }
+    }
+
+    /// Restores HP up to the maximum, returning how much was regained.
+    pub fn heal(&mut self, amount: i32) -> i32 {
+        match self.hit_points.as_mut() {
+            Some((current, maximum)) => {
+                let healed = amount.min(*maximum - *current);
+                *current += healed;
+                healed
+            }
+            None => 0,
+        }
+    }
+
+    /// Whether this combatant acts before `other`: the higher initiative goes
+    /// first, then the higher modifier, then the higher Dexterity score, then
+    /// the roll-off.
+    fn acts_before(&self, other: &Combatant) -> bool {
+        (
+            self.initiative,
+            self.initiative_modifier,
+            self.dexterity,
+            self.tiebreak,
+        ) > (
+            other.initiative,
+            other.initiative_modifier,
+            other.dexterity,
+            other.tiebreak,
+        )
+    }
+}
+
+/// The initiative order, whose turn it is and what has happened so far.
+#[derive(Debug)]
+pub struct Encounter {
+    combatants: Vec<Combatant>,
+    current: usize,
+    pub round: u32,
+    /// Combatants taken out of the fight, with the round they left in.
+    departed: Vec<(Combatant, u32)>,
+}
+
+impl Encounter {
+    /// Starts round 1 with the combatants, who must already have rolled initiative.
+    pub fn new(combatants: Vec<Combatant>) -> Encounter {
+        let mut encounter = Encounter {
+            combatants: vec![],
+            current: 0,
+            round: 1,
+            departed: vec![],
+        };
+        for combatant in combatants {
+            encounter.insert(combatant);
+        }
+        encounter
+    }
+
+    pub fn combatants(&self) -> &[Combatant] {
+        &self.combatants
+    }
+
+    pub fn combatants_mut(&mut self) -> &mut [Combatant] {
+        &mut self.combatants
+    }
+
+    pub fn current_index(&self) -> usize {
+        self.current
+    }
+
+    pub fn current(&self) -> &Combatant {
+        &self.combatants[self.current]
+    }
+
+    fn insert(&mut self, combatant: Combatant) -> usize {
+        let position = self
+            .combatants
+            .iter()
+            .position(|other| combatant.acts_before(other))
+            .unwrap_or(self.combatants.len());
+        self.combatants.insert(position, combatant);
+        position
+    }
+
+    /// Adds a combatant mid-fight. If their initiative has already passed this
+    /// round, their first turn is next round.
+    pub fn add(&mut self, combatant: Combatant) {
+        if self.insert(combatant) <= self.current && self.combatants.len() > 1 {
+            self.current += 1;
+        }
+    }
+
+    /// Takes a combatant out of the fight. Removing whoever's turn it is ends
+    /// their turn.
+    pub fn remove(&mut self, index: usize) -> Combatant {
+        let combatant = self.combatants.remove(index);
+        self.departed.push((combatant.clone(), self.round));
+        if index < self.current {
+            self.current -= 1;
+        }
+        if self.current >= self.combatants.len() {
+            self.current = 0;
+            self.round += 1;
+        }
+        combatant
+    }
+
+    /// Ends the current turn. Returns true if a new round has started.
+    pub fn end_turn(&mut self) -> bool {
+        self.combatants[self.current].turns_taken += 1;
+        self.current += 1;
+        if self.current >= self.combatants.len() {
+            self.current = 0;
+            self.round += 1;
+            return true;
+        }
+        false
+    }
+
+    /// Moves the current combatant to act straight after the combatant at
+    /// `after`, who must come later this round. It's then the next combatant's turn.
+    pub fn delay(&mut self, after: usize) {
+        if after <= self.current || after >= self.combatants.len() {
+            return;
+        }
+        let mut combatant = self.combatants.remove(self.current);
+        combatant.initiative = self.combatants[after - 1].initiative;
+        combatant.delayed = true;
+        self.combatants.insert(after, combatant);
+    }
+
+    /// Clears the delayed flag on the current combatant, returning whether
+    /// they are coming back to a turn they delayed.
+    pub fn resume_delayed(&mut self) -> bool {
+        std::mem::replace(&mut self.combatants[self.current].delayed, false)
+    }
+
+    pub fn display(&self) {
+        let mut table = Table::new();
+        table.style = TableStyle::extended();
+        table.add_row(Row::new(vec![TableCell::new_with_alignment(
+            format!("Initiative (Round {})", self.round),
+            4,
+            Alignment::Center,
+        )]));
+        for (index, combatant) in self.combatants.iter().enumerate() {
+            let marker = match (index == self.current, combatant.delayed) {
+                (true, _) => "▶",
+                (false, true) => "delayed",
+                _ => "",
+            };
+            table.add_row(Row::new(vec![
+                TableCell::new_with_alignment(marker, 1, Alignment::Center),
+                TableCell::new_with_alignment(
+                    format!(
+                        "{} ({})",
+                        combatant.initiative,
+                        format_modifier(combatant.initiative_modifier)
+                    ),
+                    1,
+                    Alignment::Center,
+                ),
+                TableCell::new_with_alignment(&combatant.name, 1, Alignment::Left),
+                TableCell::new_with_alignment(
+                    match (&combatant.stat_block, combatant.hit_points) {
+                        (Some(monster), Some((current, maximum))) => {
+                            format!("AC {}, HP {}/{}", monster.armor_class, current, maximum)
+                        }
+                        _ => String::new(),
+                    },
+                    1,
+                    Alignment::Left,
+                ),
+            ]));
+        }
+        println!("{}", table.render());
+    }
+
+    /// How the fight went, one line per entry.
+    pub fn summary(&self) -> Vec<String> {
+        let mut lines = vec![format!(
+            "The encounter lasted {} round{}.",
+            self.round,
+            if self.round == 1 { "" } else { "s" }
+        )];
+        for combatant in &self.combatants {
+            lines.push(format!(
+                "{} is still standing after {} turn{}.",
+                combatant.name,
+                combatant.turns_taken,
+                if combatant.turns_taken == 1 { "" } else { "s" }
+            ));
+        }
+        for (combatant, round) in &self.departed {
+            lines.push(format!(
+                "{} left the fight in round {} after {} turn{}.",
+                combatant.name,
+                round,
+                combatant.turns_taken,
+                if combatant.turns_taken == 1 { "" } else { "s" }
+            ));
+        }
+        lines
+    }
+}
+
+/// Names for `count` more of the monster called `base`, numbered on from any
+/// already among `present`. A lone monster keeps its plain name.
+pub fn number_monsters(present: &[Combatant], base: &str, count: usize) -> Vec<String> {
+    let is_same_monster = |name: &str| match name.strip_prefix(base) {
+        Some("") => true,
+        Some(rest) => rest
+            .strip_prefix(' ')
+            .filter(|n| !n.is_empty())
+            .is_some_and(|n| n.chars().all(|c| c.is_ascii_digit())),
+        None => false,
+    };
+    let existing = present.iter().filter(|c| is_same_monster(&c.name)).count();
+    (1..=count)
+        .map(|number| match (count, existing) {
+            (1, 0) => String::from(base),
+            _ => format!("{} {}", base, existing + number),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::monsters::load_monsters;
+    use crate::data::rng::GameRng;
+
+    fn combatant(name: &str, initiative: i32, modifier: i32, rng: &mut GameRng) -> Combatant {
+        let mut combatant = Combatant::new(name, false, modifier, 10, rng);
+        combatant.initiative = initiative;
+        combatant
+    }
+
+    fn names(encounter: &Encounter) -> Vec<&str> {
+        encounter
+            .combatants()
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn initiative_ties_go_to_the_higher_modifier() {
+        let mut rng = GameRng::new(Some(7));
+        let encounter = Encounter::new(vec![
+            combatant("Goblin", 12, 2, &mut rng),
+            combatant("Ogre", 15, -1, &mut rng),
+            combatant("Rogue", 12, 4, &mut rng),
+        ]);
+        assert_eq!(names(&encounter), ["Ogre", "Rogue", "Goblin"]);
+    }
+
+    #[test]
+    fn turns_cycle_through_rounds_with_delays_and_changes() {
+        let mut rng = GameRng::new(Some(7));
+        let mut encounter = Encounter::new(vec![
+            combatant("Ogre", 15, -1, &mut rng),
+            combatant("Rogue", 12, 4, &mut rng),
+            combatant("Goblin", 8, 2, &mut rng),
+        ]);
+        assert_eq!(encounter.current().name, "Ogre");
+
+        // The Ogre waits until after the Goblin.
+        encounter.delay(2);
+        assert_eq!(names(&encounter), ["Rogue", "Goblin", "Ogre"]);
+        assert_eq!(encounter.current().name, "Rogue");
+        assert!(!encounter.end_turn());
+
+        // A wolf arrives with a higher initiative, so it waits for next round.
+        encounter.add(combatant("Wolf", 14, 2, &mut rng));
+        assert_eq!(encounter.current().name, "Goblin");
+        encounter.remove(encounter.current_index());
+        assert_eq!(encounter.current().name, "Ogre");
+        assert!(encounter.resume_delayed());
+        assert!(encounter.end_turn());
+
+        assert_eq!(encounter.round, 2);
+        assert_eq!(encounter.current().name, "Wolf");
+        assert_eq!(encounter.summary().len(), 5);
+    }
+
+    #[test]
+    fn monsters_take_damage_through_their_defenses() {
+        let mut rng = GameRng::new(Some(7));
+        let (bestiary, _) = load_monsters("no-such-directory");
+        let skeleton = bestiary.iter().find(|m| m.name == "Skeleton").unwrap();
+        let mut combatant = Combatant::from_monster("Skeleton", skeleton, true, &mut rng);
+        assert_eq!(combatant.hit_points, Some((13, 13)));
+
+        assert_eq!(combatant.take_damage(5, DamageType::Poison), 0);
+        assert_eq!(combatant.take_damage(3, DamageType::Bludgeoning), 6);
+        assert_eq!(combatant.heal(10), 6);
+        assert_eq!(combatant.take_damage(20, DamageType::Slashing), 13);
+        assert_eq!(combatant.hit_points, Some((0, 13)));
+    }
+
+    #[test]
+    fn monsters_are_numbered_by_their_exact_name() {
+        let mut rng = GameRng::new(Some(7));
+        assert_eq!(number_monsters(&[], "Goblin", 1), ["Goblin"]);
+        assert_eq!(number_monsters(&[], "Goblin", 2), ["Goblin 1", "Goblin 2"]);
+
+        let present = vec![
+            combatant("Goblin Boss", 12, 2, &mut rng),
+            combatant("Goblin", 10, 2, &mut rng),
+            combatant("Goblin 2", 9, 2, &mut rng),
+        ];
+        assert_eq!(number_monsters(&present, "Goblin", 1), ["Goblin 3"]);
+        assert_eq!(
+            number_monsters(&present, "Goblin Boss", 1),
+            ["Goblin Boss 2"]
+        );
+        assert_eq!(number_monsters(&present, "Gob", 1), ["Gob"]);
+    }
+}
@@ -1,7 +1,27 @@
+use std::fs;
+use std::str::FromStr;
+
 use chrono::{DateTime, TimeZone, Utc};
+use dialoguer::{theme::ColorfulTheme, Confirm, FuzzySelect, Input, Select};
 use serde::{Deserialize, Deserializer, Serialize};
+use serde_yaml::{Mapping, Value};
+use strum::IntoEnumIterator;
+
+use crate::data::armor::Armor;
+use crate::data::character::{Character, Status};
+use crate::data::classes::{Class, ClassLevel};
+use crate::data::currency::{Purse, Transaction};
+use crate::data::equipment::{background_equipment, class_equipment, starting_gold, Equipment};
+use crate::data::feats::take_feat;
+use crate::data::inventory::{catalogue, Item, ItemKind};
+use crate::data::rng::GameRng;
+use crate::data::skills::choose_skills;
+use crate::data::stats::Stat;
+use crate::data::utils::{choose_value, pretty_print, BLUE, RED};
+use crate::data::weapons::Weapon;
 
-use crate::data::character::Character;
+/// Where the character is saved between sessions.
+pub const SAVE_FILE: &str = "./output.yaml";
 
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct PlayObject {
@@ -12,34 +32,699 @@ pub struct PlayObject {
     pub updated_at: Option<DateTime<Utc>>,
     #[serde(skip_deserializing, default = "empty_datetime")]
     pub last_played_at: DateTime<Utc>,
+    /// Every purchase, sale and other change to the character's coins.
+    #[serde(default)]
+    pub ledger: Vec<Transaction>,
+}
+
+impl PlayObject {
+    /// Parses a saved game. Saves from before multiclassing stored a single
+    /// `class`, which is converted to a one-entry `classes` list first, and
+    /// saves from before the inventory kept a list of `weapons`, which are
+    /// moved into it. Armor saved before it had a type is matched by name.
+    /// HP that overflowed in older saves are recalculated, and AC, initiative
+    /// and speed are re-derived from the character's equipment.
+    pub fn from_yaml(data: &str) -> Result<PlayObject, serde_yaml::Error> {
+        let mut value: Value = serde_yaml::from_str(data)?;
+        let mut weapons: Vec<Weapon> = vec![];
+        if let Some(Value::Mapping(character)) = value.get_mut("character") {
+            if let Some(list) = character.remove(&Value::from("weapons")) {
+                weapons = serde_yaml::from_value(list)?;
+            }
+            if let Some(Value::Mapping(inventory)) = character.get_mut(&Value::from("inventory")) {
+                if let Some(Value::Sequence(items)) = inventory.get_mut(&Value::from("items")) {
+                    items.iter_mut().for_each(upgrade_armor_item);
+                }
+            }
+            if let Some(class) = character.remove(&Value::from("class")) {
+                let level = character
+                    .get(&Value::from("level"))
+                    .cloned()
+                    .unwrap_or_else(|| Value::from(1));
+                let mut class_level = Mapping::new();
+                class_level.insert(Value::from("class"), class);
+                class_level.insert(Value::from("level"), level);
+                character.insert(
+                    Value::from("classes"),
+                    Value::Sequence(vec![Value::Mapping(class_level)]),
+                );
+            }
+        }
+        let mut play_object: PlayObject = serde_yaml::from_value(value)?;
+        for weapon in weapons {
+            play_object.character.inventory.add(Item::from(weapon));
+        }
+        play_object.character.repair_hit_points();
+        play_object.character.recalculate_status();
+        Ok(play_object)
+    }
+
+    /// Writes the game to `SAVE_FILE`, stamping when it was updated.
+    pub fn save(&mut self) -> Result<(), String> {
+        self.updated_at = Some(Utc::now());
+        let data = serde_yaml::to_string(self).map_err(|e| e.to_string())?;
+        fs::write(SAVE_FILE, data).map_err(|e| e.to_string())
+    }
+
+    /// Pays `cost` copper pieces for `item` and puts it in the inventory.
+    pub fn buy(&mut self, item: Item, cost: u32) -> Result<(), String> {
+        let character = &mut self.character;
+        character.purse.pay(cost)?;
+        self.ledger.push(Transaction::new(
+            &format!("Bought {} × {}", item.quantity, item.name),
+            -(cost as i64),
+        ));
+        character.inventory.add(item);
+        character.recalculate_status();
+        Ok(())
+    }
+
+    /// Sells `quantity` of the item at `index` in the inventory for `proceeds`
+    /// copper pieces, returning what was sold.
+    pub fn sell(&mut self, index: usize, quantity: u32, proceeds: u32) -> Option<Item> {
+        let character = &mut self.character;
+        let item = character.inventory.remove(index, quantity)?;
+        character.purse.receive(proceeds);
+        self.ledger.push(Transaction::new(
+            &format!("Sold {} × {}", item.quantity, item.name),
+            proceeds as i64,
+        ));
+        character.recalculate_status();
+        Some(item)
+    }
+
+    /// Picks something from the catalogue and how many to buy, and buys
+    /// them. Returns `false` if the player went back instead.
+    pub fn buy_from_catalogue(&mut self) -> bool {
+        let (mut item, price) = match choose_from_catalogue() {
+            Some(choice) => choice,
+            None => return false,
+        };
+        let bundles: u32 = Input::with_theme(&ColorfulTheme::default())
+            .with_prompt("How many?")
+            .default(1)
+            .validate_with(|input: &u32| -> Result<(), &str> {
+                match *input >= 1 {
+                    true => Ok(()),
+                    false => Err("Please buy at least one"),
+                }
+            })
+            .interact_text()
+            .unwrap();
+        let (quantity, cost) = match (
+            item.quantity.checked_mul(bundles),
+            price.checked_mul(bundles),
+        ) {
+            (Some(quantity), Some(cost)) => (quantity, cost),
+            _ => {
+                pretty_print("No purse could pay for that many.", RED, true);
+                return true;
+            }
+        };
+        item.quantity = quantity;
+        let name = item.name.clone();
+        match self.buy(item, cost) {
+            Ok(()) => pretty_print(
+                &format!("You buy {} for {}.", name, Purse::from_value(cost)),
+                BLUE,
+                true,
+            ),
+            Err(message) => pretty_print(&message, RED, true),
+        }
+        true
+    }
+
+    /// Gives a new character the starting equipment of their first class and
+    /// their background, a choice at a time, or instead the class's starting
+    /// gold to buy their own from the catalogue. Armor, a shield and a weapon
+    /// are then equipped.
+    pub fn choose_starting_equipment(&mut self, rng: &mut GameRng) {
+        let class = &self.character.classes[0].class;
+        let (dice, multiplier) = starting_gold(class);
+        let choices = [
+            String::from("Take the equipment of your class and background"),
+            format!("Take {} × {} gp and buy your own", dice, multiplier),
+        ];
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("How would you like to equip your character?")
+            .default(0)
+            .items(&choices)
+            .interact()
+            .unwrap();
+
+        match selection {
+            0 => {
+                let mut equipment = class_equipment(class);
+                equipment.append(&mut background_equipment(&self.character.background));
+                for choice in equipment {
+                    let option = match choice.len() {
+                        1 => 0,
+                        _ => {
+                            let options: Vec<String> = choice
+                                .iter()
+                                .zip('a'..)
+                                .map(|(option, letter)| {
+                                    let option: Vec<String> =
+                                        option.iter().map(|e| e.describe()).collect();
+                                    format!("({}) {}", letter, option.join(", "))
+                                })
+                                .collect();
+                            Select::with_theme(&ColorfulTheme::default())
+                                .with_prompt("Which would you like?")
+                                .default(0)
+                                .items(&options)
+                                .interact()
+                                .unwrap()
+                        }
+                    };
+                    for equipment in &choice[option] {
+                        self.take_equipment(equipment);
+                    }
+                }
+            }
+            _ => {
+                let roll = dice.roll(rng);
+                let gold = roll.total as u32 * multiplier;
+                pretty_print(&format!("{} → {} gp", roll, gold), BLUE, true);
+                let coins = Purse {
+                    gp: gold,
+                    ..Default::default()
+                };
+                self.gain_coins(&coins, "Starting gold");
+                while self.buy_from_catalogue() {}
+            }
+        }
+
+        let character = &mut self.character;
+        character.inventory.equip_starting_gear();
+        character.recalculate_status();
+        character.display_inventory();
+        pretty_print(
+            &format!("Your AC is {}.", character.status.armor_class()),
+            BLUE,
+            true,
+        );
+    }
+
+    /// Adds a piece of starting equipment to the inventory, first asking which
+    /// one for equipment such as "any simple weapon".
+    fn take_equipment(&mut self, equipment: &Equipment) {
+        if let Equipment::Gold(gp) = equipment {
+            let coins = Purse {
+                gp: *gp,
+                ..Default::default()
+            };
+            let reason = format!("{} background", self.character.background);
+            self.gain_coins(&coins, &reason);
+            return;
+        }
+        let options = equipment.options();
+        let items = match options.is_empty() {
+            true => equipment.items(),
+            false => {
+                let names: Vec<&str> = options.iter().map(|i| i.name.as_str()).collect();
+                let selection = Select::with_theme(&ColorfulTheme::default())
+                    .with_prompt(format!("Choose {}", equipment.describe()))
+                    .default(0)
+                    .items(&names)
+                    .interact()
+                    .unwrap();
+                vec![options[selection].clone()]
+            }
+        };
+        for item in items {
+            self.character.inventory.add(item);
+        }
+    }
+
+    /// Adds coins that were found or given, noting why in the ledger.
+    pub fn gain_coins(&mut self, coins: &Purse, reason: &str) {
+        self.character.purse.deposit(coins);
+        self.ledger
+            .push(Transaction::new(reason, coins.total() as i64));
+    }
+
+    /// Pays `amount` copper pieces for something other than equipment, such
+    /// as lodging or a bribe, noting why in the ledger.
+    pub fn spend_coins(&mut self, amount: u32, reason: &str) -> Result<(), String> {
+        self.character.purse.pay(amount)?;
+        self.ledger.push(Transaction::new(reason, -(amount as i64)));
+        Ok(())
+    }
+
+    /// Advances the character one level in a class of the player's choice,
+    /// adding that level's HP and walking through any subclass, Ability Score
+    /// Improvement or Expertise choices.
+    pub fn level_up(&mut self, rng: &mut GameRng) {
+        let character = &mut self.character;
+        if character.level >= 20 {
+            pretty_print(
+                &format!("{} is already level 20.", character.name),
+                RED,
+                true,
+            );
+            return;
+        }
+
+        let index = choose_class_to_advance(character, rng);
+        let use_average_dice = Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt("Take the average HP increase? ('NO' will roll your hit die)")
+            .default(true)
+            .wait_for_newline(true)
+            .interact()
+            .unwrap();
+        let hp_gain = gain_level(character, index, use_average_dice, rng);
+        let class_level = character.classes[index].level;
+        pretty_print(
+            &format!(
+                "\n{} is now level {} ({})!\n",
+                character.name, character.level, character.classes[index]
+            ),
+            BLUE,
+            true,
+        );
+        pretty_print(
+            &format!("Your maximum HP increases by {}.\n", hp_gain),
+            BLUE,
+            true,
+        );
+
+        let class = &character.classes[index].class;
+        if class_level >= class.subclass_level() && !class.has_subclass() {
+            character.classes[index].class = class.choose_subclass(rng);
+        }
+
+        if character.classes[index]
+            .class
+            .asi_levels()
+            .contains(&class_level)
+        {
+            choose_ability_score_improvement(character, rng);
+        }
+
+        let expertise_count: usize = character
+            .classes
+            .iter()
+            .map(|c| c.class.expertise_count(c.level))
+            .sum();
+        let new_expertise = expertise_count.saturating_sub(character.expertise.len());
+        if new_expertise > 0 {
+            let known = character.expertise.clone();
+            let mut expertise = choose_skills(
+                "Choose a skill to gain Expertise in",
+                new_expertise,
+                &character.skills,
+                &known,
+            );
+            character.expertise.append(&mut expertise);
+        }
+
+        character.recalculate_status();
+        self.updated_at = Some(Utc::now());
+    }
+}
+
+/// Adds a level in `character.classes[index]` along with the maximum HP it
+/// brings, returning the HP gained.
+fn gain_level(
+    character: &mut Character,
+    index: usize,
+    use_average_dice: bool,
+    rng: &mut GameRng,
+) -> i32 {
+    character.level += 1;
+    character.classes[index].level += 1;
+    let con_modifier = character.get_con().get_modifier();
+    let hit_die = character.classes[index].class.hit_die();
+    let hp_gain = Status::level_hp_increase(hit_die, con_modifier, use_average_dice, rng)
+        + character.hp_bonus_per_level();
+    character.status.increase_maximum_hp(hp_gain);
+    hp_gain
+}
+
+/// Asks which class gains the new level, offering any class the character
+/// qualifies to multiclass into. Returns its index in `character.classes`.
+fn choose_class_to_advance(character: &mut Character, rng: &mut GameRng) -> usize {
+    let mut choices: Vec<String> = character
+        .classes
+        .iter()
+        .map(|c| format!("{} {} → {}", c.class, c.level, c.level + 1))
+        .collect();
+    let new_classes = multiclass_options(&character.classes, &character.stats);
+    if !new_classes.is_empty() {
+        choices.push(String::from("A new class (multiclass)"));
+    }
+    if choices.len() == 1 {
+        return 0;
+    }
+
+    let selection = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt("Which class would you like to gain a level in?")
+        .default(0)
+        .items(&choices)
+        .interact()
+        .unwrap();
+    if selection < character.classes.len() {
+        return selection;
+    }
+
+    let class: Class = choose_value("Which class would you like to add?", &new_classes, rng);
+    let (count, options) = class.multiclass_skill_choices();
+    let prompt = format!("Choose a {} skill proficiency", class);
+    let mut skills = choose_skills(&prompt, count, &options, &character.skills);
+    character.skills.append(&mut skills);
+    character.classes.push(ClassLevel { class, level: 0 });
+    character.classes.len() - 1
+}
+
+/// Names of the classes a character could multiclass into: every class they
+/// don't have yet whose prerequisites, and those of their current classes, are met.
+pub fn multiclass_options(classes: &[ClassLevel], stats: &[Stat]) -> Vec<String> {
+    if let Some(current) = classes
+        .iter()
+        .find(|c| !c.class.meets_multiclass_prerequisites(stats))
+    {
+        pretty_print(
+            &format!(
+                "Multiclassing out of {} requires {}.",
+                current.class,
+                current.class.multiclass_prerequisites()
+            ),
+            RED,
+            true,
+        );
+        return vec![];
+    }
+    Class::iter()
+        .filter(|class| !classes.iter().any(|c| c.class.is_same_class(class)))
+        .filter(|class| class.meets_multiclass_prerequisites(stats))
+        .map(|class| class.to_string())
+        .collect()
+}
+
+fn choose_ability_score_improvement(character: &mut Character, rng: &mut GameRng) {
+    let choices = [
+        "Increase one ability score by 2",
+        "Increase two ability scores by 1",
+        "Take a feat",
+    ];
+    let choice = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt("You gain an Ability Score Improvement!")
+        .default(0)
+        .items(&choices)
+        .interact()
+        .unwrap();
+
+    let (picks, amount) = match choice {
+        0 => (1, 2),
+        1 => (2, 1),
+        _ => return take_feat(character, rng),
+    };
+
+    let mut options: Vec<usize> = (0..character.stats.len())
+        .filter(|i| character.stats[*i].value() < 20)
+        .collect();
+    for pick in 1..=picks {
+        if options.is_empty() {
+            pretty_print("All of your ability scores are already 20.", RED, true);
+            break;
+        }
+        let selections: Vec<String> = options
+            .iter()
+            .map(|i| {
+                let stat = &character.stats[*i];
+                format!("{} ({})", stat.show_name(), stat.value())
+            })
+            .collect();
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!(
+                "Choose an ability to increase by {} ({} of {})",
+                amount, pick, picks
+            ))
+            .default(0)
+            .items(&selections)
+            .interact()
+            .unwrap();
+        let ability = options.remove(selection);
+        character.improve_ability(ability, amount);
+        let stat = &character.stats[ability];
+        pretty_print(
+            &format!("\n{} is now {}.\n", stat.show_name(), stat.value()),
+            BLUE,
+            true,
+        );
+    }
 }
 
 fn empty_datetime() -> DateTime<Utc> {
     Utc::now()
 }
 
+/// Older saves stored armor as a plain `kind: Armor`. The type is found from
+/// the item's name, e.g. "Chain Mail" or "Leather Armor"; anything else
+/// becomes unequipped gear.
+fn upgrade_armor_item(item: &mut Value) {
+    if item.get("kind") != Some(&Value::from("Armor")) {
+        return;
+    }
+    let name = item.get("name").and_then(Value::as_str).unwrap_or_default();
+    let armor = Armor::from_str(name).ok().or_else(|| {
+        let lowercase = name.to_lowercase();
+        let stripped = lowercase.strip_suffix(" armor")?;
+        Armor::from_str(stripped).ok()
+    });
+    if let Value::Mapping(item) = item {
+        let kind = match armor {
+            Some(armor) => ItemKind::Armor(armor),
+            None => {
+                item.remove(&Value::from("equipped"));
+                ItemKind::Gear
+            }
+        };
+        if let Ok(kind) = serde_yaml::to_value(kind) {
+            item.insert(Value::from("kind"), kind);
+        }
+    }
+}
+
 fn empty_updated_at() -> Option<DateTime<Utc>> {
     None
 }
 
+/// Reads timestamps written by this program (RFC 3339) as well as the older
+/// `Fri Nov 28 12:00:09 2014` style.
+fn parse_datetime(s: &str) -> Option<DateTime<Utc>> {
+    match DateTime::parse_from_rfc3339(s) {
+        Ok(datetime) => Some(datetime.with_timezone(&Utc)),
+        Err(_) => Utc.datetime_from_str(s, "%a %b %e %T %Y").ok(),
+    }
+}
+
 fn de_created_at<'de, D>(deserializer: D) -> Result<DateTime<Utc>, D::Error>
 where
     D: Deserializer<'de>,
 {
     let s = String::deserialize(deserializer)?;
-    match Utc.datetime_from_str(&s, "%a %b %e %T %Y") {
-        Ok(utc) => Ok(utc),
-        Err(_) => Ok(Utc::now()),
-    }
+    Ok(parse_datetime(&s).unwrap_or_else(Utc::now))
 }
 
 fn de_updated_at<'de, D>(deserializer: D) -> Result<Option<DateTime<Utc>>, D::Error>
 where
     D: Deserializer<'de>,
 {
-    let s = String::deserialize(deserializer)?;
-    match Utc.datetime_from_str(&s, "%a %b %e %T %Y") {
-        Ok(utc) => Ok(Some(utc)),
-        Err(_) => Ok(None),
+    let s = Option::<String>::deserialize(deserializer)?;
+    Ok(s.and_then(|s| parse_datetime(&s)))
+}
+
+/// Picks something to buy from the catalogue, with the price of its bundle.
+fn choose_from_catalogue() -> Option<(Item, u32)> {
+    let catalogue = catalogue();
+    let mut choices: Vec<String> = catalogue
+        .iter()
+        .map(|(item, price)| {
+            let description = match &item.kind {
+                ItemKind::Weapon(weapon) => weapon.describe(),
+                ItemKind::Armor(armor) => armor.describe(),
+                _ if item.quantity > 1 => format!("{} × {}", item.quantity, item.name),
+                _ => item.name.clone(),
+            };
+            format!("{}: {}", description, Purse::from_value(*price))
+        })
+        .collect();
+    choices.push(String::from("Back"));
+    let selection = FuzzySelect::with_theme(&ColorfulTheme::default())
+        .with_prompt("What would you like to buy? (type to search)")
+        .default(0)
+        .items(&choices)
+        .interact()
+        .unwrap();
+    catalogue.get(selection).cloned()
+}
+
+/// The bundled save of a level 8 Way of Mercy Monk, from before
+/// multiclassing, for tests to start from.
+#[cfg(test)]
+pub fn legacy_save_data() -> String {
+    fs::read_to_string("./test_yaml/legacy_save.yaml").expect("Unable to read file")
+}
+
+#[cfg(test)]
+pub fn legacy_save() -> PlayObject {
+    PlayObject::from_yaml(&legacy_save_data()).expect("Legacy save should still load")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::classes::MonkSubclass;
+    use crate::data::inventory::Slot;
+    use crate::data::races::{Dwarf, Race};
+
+    #[test]
+    fn levels_add_hit_points_for_the_advancing_class() {
+        let mut rng = GameRng::new(Some(7));
+        let mut play_object = legacy_save();
+        let character = &mut play_object.character;
+
+        // A Monk's d8 averages 5, plus 1 for Con 12.
+        assert_eq!(gain_level(character, 0, true, &mut rng), 6);
+        assert_eq!(character.level, 9);
+        assert_eq!(character.classes[0].level, 9);
+        assert_eq!(character.status.effective_maximum_hp(), 57);
+        assert_eq!(character.status.current_hp(), 57);
+
+        // A new class rolls its own hit die, and a Hill Dwarf gains 1 more.
+        character.race = Race::Dwarf(Dwarf::HillDwarf);
+        character.classes.push(ClassLevel {
+            class: Class::Barbarian(None),
+            level: 0,
+        });
+        let gain = gain_level(character, 1, false, &mut rng);
+        assert!((2..=14).contains(&gain));
+        assert_eq!(character.level, 10);
+        assert_eq!(character.classes[1].level, 1);
+        assert_eq!(character.status.effective_maximum_hp(), 57 + gain);
+    }
+
+    #[test]
+    fn level_20_characters_stay_put() {
+        let mut rng = GameRng::new(Some(7));
+        let mut play_object = legacy_save();
+        play_object.character.level = 20;
+        play_object.character.classes[0].level = 20;
+        play_object.level_up(&mut rng);
+        assert_eq!(play_object.character.level, 20);
+        assert_eq!(play_object.character.classes[0].level, 20);
+        assert_eq!(play_object.updated_at, legacy_save().updated_at);
+    }
+
+    #[test]
+    fn can_load_legacy_save() {
+        let play_object = legacy_save();
+        assert!(play_object.character.status.conditions.is_empty());
+        assert_eq!(
+            play_object.character.classes,
+            vec![ClassLevel {
+                class: Class::Monk(Some(MonkSubclass::Mercy)),
+                level: 8,
+            }]
+        );
+    }
+
+    #[test]
+    fn reads_saved_timestamps() {
+        let play_object = legacy_save();
+        assert_eq!(
+            play_object.created_at.to_rfc3339(),
+            "2022-08-10T15:21:13.122930+00:00"
+        );
+        assert_eq!(play_object.updated_at, None);
+    }
+
+    #[test]
+    fn weapons_move_into_the_inventory() {
+        let data = legacy_save_data().replace(
+            "  gender: Male",
+            "  weapons:\n    - Quarterstaff\n    - Dart\n    - Dart\n  gender: Male",
+        );
+        let character = PlayObject::from_yaml(&data).unwrap().character;
+        let items: Vec<(&ItemKind, u32)> = character
+            .inventory
+            .items
+            .iter()
+            .map(|item| (&item.kind, item.quantity))
+            .collect();
+        assert_eq!(
+            items,
+            [
+                (&ItemKind::Weapon(Weapon::Quarterstaff), 1),
+                (&ItemKind::Weapon(Weapon::Dart), 2)
+            ]
+        );
+    }
+
+    #[test]
+    fn armor_from_older_saves_keeps_its_type() {
+        let data = legacy_save_data().replace(
+            "  gender: Male",
+            "  inventory:
+    items:
+      - name: Chain Mail
+        kind: Armor
+        quantity: 1
+        weight: 55.0
+        equipped: Armor
+      - name: Leather armor
+        kind: Armor
+        quantity: 1
+        weight: 10.0
+      - name: Mithral coat
+        kind: Armor
+        quantity: 1
+        weight: 20.0
+        equipped: Armor
+  gender: Male",
+        );
+        let character = PlayObject::from_yaml(&data).unwrap().character;
+        let items = &character.inventory.items;
+        assert_eq!(items[0].kind, ItemKind::Armor(Armor::ChainMail));
+        assert_eq!(items[0].equipped, Some(Slot::Armor));
+        assert_eq!(items[1].kind, ItemKind::Armor(Armor::Leather));
+        assert_eq!(items[2].kind, ItemKind::Gear);
+        assert_eq!(items[2].equipped, None);
+        assert_eq!(character.inventory.armor(), Some(Armor::ChainMail));
+    }
+
+    #[test]
+    fn purchases_go_in_the_ledger() {
+        let mut play_object = legacy_save();
+        assert_eq!(play_object.character.purse.total(), 0);
+        assert!(play_object
+            .buy(Item::from(Weapon::Longsword), 1_500)
+            .is_err());
+
+        play_object.gain_coins(&Purse::from_str("2 pp, 1 ep").unwrap(), "Dragon hoard");
+        play_object
+            .buy(Item::from(Weapon::Longsword), Weapon::Longsword.price())
+            .unwrap();
+        assert_eq!(play_object.character.purse.to_string(), "5 gp, 5 sp");
+        assert_eq!(
+            play_object.character.inventory.weapons(),
+            [Weapon::Longsword]
+        );
+
+        let sold = play_object.sell(0, 1, 750).unwrap();
+        assert_eq!(sold.name, "Longsword");
+        assert!(play_object.character.inventory.items.is_empty());
+        play_object.spend_coins(5, "A night at the inn").unwrap();
+        assert_eq!(play_object.character.purse.total(), 1_295);
+
+        let amounts: Vec<i64> = play_object.ledger.iter().map(|t| t.amount).collect();
+        assert_eq!(amounts, [2_050, -1_500, 750, -5]);
+        let saved = serde_yaml::to_string(&play_object).unwrap();
+        assert_eq!(
+            PlayObject::from_yaml(&saved).unwrap().ledger,
+            play_object.ledger
+        );
     }
 }
@@ -10,68 +10,3114 @@ We should be able to...
 
 use std::str::FromStr;
 
-use dialoguer::{theme::ColorfulTheme, Select};
+use dialoguer::{theme::ColorfulTheme, Confirm, FuzzySelect, Input, Select};
+use strum::IntoEnumIterator;
 use strum_macros::{Display, EnumIter, EnumString};
 
-use crate::data::utils::{pretty_print, StringJoin, BLUE};
+use crate::data::armor::{Armor, SHIELD_BONUS};
+use crate::data::classes::Class;
+use crate::data::conditions::{ActiveCondition, Condition, RollKind, SaveAgainst, MAX_EXHAUSTION};
+use crate::data::currency::Purse;
+use crate::data::damage::{adjust_damage, DamageDefense, DamageType, DeathSaveOutcome, Defense};
+use crate::data::dice::{Dice, DiceExpression, RollMode, RollResult};
+use crate::data::inventory::{Encumbrance, Item, ItemKind};
+use crate::data::monsters::{load_monsters, Monster, MonsterAction, MONSTER_DIR};
+use crate::data::resources::Resource;
+use crate::data::rng::GameRng;
+use crate::data::skills::Skill;
+use crate::data::spellcasting::ordinal;
+use crate::data::stats::Stat;
+use crate::data::utils::{format_modifier, pretty_print, StringJoin, BLUE, RED};
+use crate::data::weapons::{Grip, Weapon, WeaponProperty};
 
-use super::play_object::PlayObject;
+use super::actions::{Action, AttackKind, BonusAction, Hit, Reaction, Trigger, Turn};
+use super::encounter::{number_monsters, Combatant, Encounter};
+use super::play_object::{PlayObject, SAVE_FILE};
 
-#[derive(EnumIter, EnumString, Display)]
+#[derive(Debug, Clone, Copy, PartialEq, EnumIter, EnumString, Display)]
 #[strum(ascii_case_insensitive, serialize_all = "title_case")]
 enum TurnOption {
     Move,
     Action,
     BonusAction,
+    AbilityCheck,
+    SavingThrow,
+    AttackRoll,
+    RollDice,
+    Conditions,
+    HitPoints,
+    Inventory,
+    Shop,
+    Rest,
+    Encounter,
+    TurnLog,
+    EndTurn,
     Quit,
 }
 
+/// Which spell slot, if any, a spell is cast with.
+#[derive(Debug, Clone, Copy)]
+enum SpellSlot {
+    Cantrip,
+    Level(usize),
+    Pact,
+}
+
 pub struct PlayState {
     play_object: PlayObject,
+    rng: GameRng,
+    turn: Turn,
+    round: u32,
+    /// The fight underway, if any, which decides whose turn is next.
+    encounter: Option<Encounter>,
+    /// Monsters that can be added to an encounter.
+    bestiary: Vec<Monster>,
+    /// What the character did each round, including reactions.
+    log: Vec<String>,
+    /// The Stealth total of a successful Hide, until the character attacks or is found.
+    hidden: Option<i32>,
     pub active: bool,
 }
 
 impl PlayState {
-    pub fn new(play_object: PlayObject) -> PlayState {
+    pub fn new(play_object: PlayObject, rng: GameRng) -> PlayState {
+        let (bestiary, warnings) = load_monsters(MONSTER_DIR);
+        for warning in warnings {
+            pretty_print(&warning, RED, true);
+        }
         PlayState {
             play_object,
+            rng,
+            turn: Turn::default(),
+            round: 0,
+            encounter: None,
+            bestiary,
+            log: vec![],
+            hidden: None,
             active: true,
         }
     }
 
-    pub fn take_turn(&mut self) {
-        let turn_selection = Select::with_theme(&ColorfulTheme::default())
-            .with_prompt("Your turn begins. What would you like to do?")
+    /// Runs whoever's turn is next: the character's own, or another
+    /// combatant's when an encounter is underway.
+    pub fn next_turn(&mut self) {
+        let finished = match &self.encounter {
+            Some(encounter) if !encounter.current().is_player => self.other_turn(),
+            _ => self.take_turn(),
+        };
+        if let (true, Some(encounter)) = (finished, &mut self.encounter) {
+            if encounter.end_turn() {
+                pretty_print(&format!("\nRound {} begins.", encounter.round), BLUE, true);
+                encounter.display();
+            }
+        }
+    }
+
+    /// The character's turn. Returns false if the turn was cut short by
+    /// delaying, starting or ending an encounter, or quitting.
+    fn take_turn(&mut self) -> bool {
+        let resuming = self
+            .encounter
+            .as_mut()
+            .is_some_and(|encounter| encounter.resume_delayed());
+        match resuming {
+            true => pretty_print("You take your delayed turn.", BLUE, true),
+            false => {
+                self.start_turn();
+                if self.play_object.character.status.is_dying() {
+                    self.death_saving_throw();
+                }
+            }
+        }
+        loop {
+            // Death ends the character's play, whether it came before the turn
+            // or during it; all that's left is to quit.
+            if self.play_object.character.status.dead {
+                pretty_print("You are dead.", RED, true);
+            }
+            let options = self.available_options();
+            let option_strings: Vec<String> = options.iter().map(|o| o.to_string()).collect();
+
+            let turn_selection = Select::with_theme(&ColorfulTheme::default())
+                .with_prompt("What would you like to do?")
+                .default(0)
+                .items(&option_strings)
+                .interact()
+                .unwrap();
+
+            let turn = options[turn_selection];
+
+            pretty_print(&format!("\nYou chose to {}.\n", turn), BLUE, true);
+
+            match turn {
+                TurnOption::Move => self.get_movement(),
+                TurnOption::Action => self.get_actions(),
+                TurnOption::BonusAction => self.get_bonus_actions(),
+                TurnOption::AbilityCheck => self.ability_check(),
+                TurnOption::SavingThrow => self.saving_throw(),
+                TurnOption::AttackRoll => self.attack_roll(),
+                TurnOption::RollDice => self.roll_dice(),
+                TurnOption::Conditions => self.manage_conditions(),
+                TurnOption::HitPoints => self.manage_hit_points(),
+                TurnOption::Inventory => self.manage_inventory(),
+                TurnOption::Shop => self.shop(),
+                TurnOption::Rest => self.rest(),
+                TurnOption::Encounter => {
+                    if self.manage_encounter() {
+                        return false;
+                    }
+                }
+                TurnOption::TurnLog => self.show_log(),
+                TurnOption::EndTurn => {
+                    // In an encounter, reactions come up during the other combatants' turns.
+                    if self.encounter.is_none() {
+                        self.between_turns();
+                    }
+                    return true;
+                }
+                TurnOption::Quit => {
+                    self.quit();
+                    return false;
+                }
+            }
+
+            self.play_object.character.display(false);
+            self.show_turn_effects();
+        }
+    }
+
+    fn start_turn(&mut self) {
+        self.round = match &self.encounter {
+            Some(encounter) => encounter.round,
+            None => self.round + 1,
+        };
+        pretty_print(
+            &format!("Round {}: your turn begins.", self.round),
+            BLUE,
+            true,
+        );
+        if let Some(action) = self.turn.action {
+            if matches!(action, Action::Dodge | Action::Disengage | Action::Ready) {
+                pretty_print(&format!("Your {} ends.", action), BLUE, true);
+            }
+        }
+        self.turn = Turn::default();
+        if self.play_object.character.status.tick_rage() {
+            pretty_print("Your Rage ends.", BLUE, true);
+        }
+        for expired in self.play_object.character.status.tick_conditions() {
+            pretty_print(
+                &format!("You are no longer {}.", expired.condition),
+                BLUE,
+                true,
+            );
+        }
+        let status = &mut self.play_object.character.status;
+        status.start_movement();
+        if status.is_incapacitated() {
+            pretty_print(
+                "You are incapacitated and can't take actions this turn.",
+                RED,
+                true,
+            );
+        }
+    }
+
+    /// Rolled at the start of each turn spent dying at 0 HP.
+    fn death_saving_throw(&mut self) {
+        let roll = match self.roll_d20(RollKind::DeathSave, 0, "Death saving throw", vec![], vec![])
+        {
+            Some(roll) => roll,
+            None => return,
+        };
+        let status = &mut self.play_object.character.status;
+        let (message, color) = match status.death_saving_throw(&roll) {
+            DeathSaveOutcome::Success => (format!("A success ({}).", status.death_saves), BLUE),
+            DeathSaveOutcome::Failure(_) => (format!("A failure ({}).", status.death_saves), RED),
+            DeathSaveOutcome::Stable => (
+                String::from("Three successes: you are stable, though still unconscious."),
+                BLUE,
+            ),
+            DeathSaveOutcome::RegainedHp => (
+                String::from("You regain 1 hit point and consciousness."),
+                BLUE,
+            ),
+            DeathSaveOutcome::Dead => (String::from("Three failures: you die."), RED),
+        };
+        pretty_print(&format!("{}\n", message), color, true);
+        self.record(format!("Death saving throw: {}", message));
+        // Keep the tally even if the game isn't quit cleanly.
+        self.autosave();
+    }
+
+    /// Stops the character's death saves with help from an ally.
+    fn get_stabilized(&mut self) {
+        if !self.play_object.character.status.is_dying() {
+            pretty_print("You aren't dying.\n", BLUE, true);
+            return;
+        }
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("How are you stabilized?")
+            .default(0)
+            .items(&[
+                "An ally's DC 10 Wisdom (Medicine) check",
+                "Spare the Dying",
+                "Back",
+            ])
+            .interact()
+            .unwrap();
+        let source = match selection {
+            0 => {
+                let modifier: i32 = Input::with_theme(&ColorfulTheme::default())
+                    .with_prompt("What is their Medicine modifier?")
+                    .default(0)
+                    .interact_text()
+                    .unwrap();
+                let roll = DiceExpression::from(Dice::D20)
+                    .with_modifier(modifier)
+                    .roll(&mut self.rng);
+                pretty_print(&format!("\nMedicine check: {}", roll), BLUE, true);
+                if roll.total < 10 {
+                    pretty_print("It fails, and you are still dying.\n", RED, true);
+                    return;
+                }
+                "a Medicine check"
+            }
+            1 => "Spare the Dying",
+            _ => return,
+        };
+        self.play_object.character.status.stabilize();
+        pretty_print("You are stable, though still unconscious.\n", BLUE, true);
+        self.record(format!("You were stabilized by {}", source));
+    }
+
+    fn available_options(&self) -> Vec<TurnOption> {
+        let status = &self.play_object.character.status;
+        if status.dead {
+            return vec![TurnOption::Quit];
+        }
+        TurnOption::iter()
+            .filter(|option| match option {
+                TurnOption::Move => status.movement > 0 && status.effective_speed() > 0,
+                TurnOption::Action => !status.is_incapacitated() && !self.turn.action_used(),
+                TurnOption::BonusAction => {
+                    !status.is_incapacitated()
+                        && !self
+                            .turn
+                            .bonus_actions(&self.play_object.character)
+                            .is_empty()
+                }
+                TurnOption::Rest | TurnOption::Shop => self.encounter.is_none(),
+                _ => true,
+            })
+            .collect()
+    }
+
+    /// Rolls a d20 for the character, applying advantage, disadvantage and
+    /// automatic failure from their conditions along with any `advantages` and
+    /// `disadvantages` from the situation. Returns `None` on an automatic failure.
+    fn roll_d20(
+        &mut self,
+        kind: RollKind,
+        modifier: i32,
+        label: &str,
+        advantages: Vec<String>,
+        disadvantages: Vec<String>,
+    ) -> Option<RollResult> {
+        let status = &self.play_object.character.status;
+        let mut effects = status.roll_effects(kind);
+        effects.advantage.extend(advantages);
+        effects.disadvantage.extend(disadvantages);
+        if status.is_raging()
+            && matches!(kind, RollKind::AbilityCheck(0) | RollKind::SavingThrow(0))
+        {
+            effects.advantage.push(String::from("Rage"));
+        }
+        if self.play_object.character.encumbrance() == Encumbrance::HeavilyEncumbered
+            && matches!(
+                kind,
+                RollKind::Attack | RollKind::AbilityCheck(0..=2) | RollKind::SavingThrow(0..=2)
+            )
+        {
+            effects
+                .disadvantage
+                .push(String::from("Heavily encumbered"));
+        }
+
+        if !effects.auto_fail.is_empty() {
+            pretty_print(
+                &format!(
+                    "\nYour {} automatically fails ({}).\n",
+                    label,
+                    effects.auto_fail.join(", ")
+                ),
+                RED,
+                true,
+            );
+            return None;
+        }
+        if !effects.advantage.is_empty() {
+            pretty_print(
+                &format!("Advantage from {}", effects.advantage.join(", ")),
+                BLUE,
+                true,
+            );
+        }
+        if !effects.disadvantage.is_empty() {
+            pretty_print(
+                &format!("Disadvantage from {}", effects.disadvantage.join(", ")),
+                RED,
+                true,
+            );
+        }
+
+        let roll = DiceExpression::from(Dice::D20)
+            .with_modifier(modifier)
+            .with_mode(effects.mode())
+            .roll(&mut self.rng);
+        pretty_print(&format!("\n{}: {}", label, roll), BLUE, true);
+        if roll.is_critical() {
+            pretty_print("Natural 20!", BLUE, true);
+        } else if roll.is_fumble() {
+            pretty_print("Natural 1...", RED, true);
+        }
+        println!();
+        Some(roll)
+    }
+
+    /// Spends part of the turn's movement, so movement can be split around actions.
+    pub fn get_movement(&mut self) {
+        let status = &mut self.play_object.character.status;
+        let prone = status
+            .conditions
+            .iter()
+            .any(|c| c.condition == Condition::Prone);
+        let mut choices = vec![
+            String::from("Move"),
+            String::from("Move through difficult terrain (double cost)"),
+        ];
+        if prone {
+            choices.push(format!("Stand up ({} feet)", status.stand_up_cost()));
+        }
+        choices.push(String::from("Back"));
+        let choice = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!(
+                "You have {} feet of movement left.",
+                status.movement
+            ))
+            .default(0)
+            .items(&choices)
+            .interact()
+            .unwrap();
+
+        match choice {
+            0 | 1 => {
+                let difficult_terrain = choice == 1;
+                let most = status.movement / status.movement_multiplier(difficult_terrain);
+                let feet: String = Input::with_theme(&ColorfulTheme::default())
+                    .with_prompt(format!("How many feet? (up to {})", most))
+                    .default(most.to_string())
+                    .validate_with(|input: &String| -> Result<(), String> {
+                        match input.parse::<u16>() {
+                            Ok(feet) if feet <= most => Ok(()),
+                            _ => Err(format!("You can move at most {} feet", most)),
+                        }
+                    })
+                    .interact_text()
+                    .unwrap();
+                if prone {
+                    pretty_print(
+                        "Crawling while prone costs an extra foot per foot.",
+                        BLUE,
+                        true,
+                    );
+                }
+                if let Some(cost) = status.spend_movement(feet.parse().unwrap(), difficult_terrain)
+                {
+                    pretty_print(
+                        &format!(
+                            "You spend {} feet of movement ({} left).\n",
+                            cost, status.movement
+                        ),
+                        BLUE,
+                        true,
+                    );
+                }
+            }
+            2 if prone => match status.stand_up() {
+                true => pretty_print(
+                    &format!("You stand up ({} feet left).\n", status.movement),
+                    BLUE,
+                    true,
+                ),
+                false => pretty_print("You don't have enough movement to stand up.\n", RED, true),
+            },
+            _ => {}
+        }
+    }
+
+    /// Effects of this turn's action that carry on until the next turn starts.
+    fn show_turn_effects(&self) {
+        let mut effects: Vec<String> =
+            [Action::Dash, Action::Disengage, Action::Dodge, Action::Help]
+                .iter()
+                .filter(|action| self.turn.is_active(**action))
+                .map(|action| action.to_string())
+                .collect();
+        if let Some((trigger, action)) = &self.turn.readied {
+            effects.push(format!("Readied {} ({})", action, trigger));
+        }
+        if let Some(stealth) = self.hidden {
+            effects.push(format!("Hidden (Stealth {})", stealth));
+        }
+        if self.turn.shield {
+            effects.push(format!("Shield (AC {})", self.armor_class()));
+        }
+        if !effects.is_empty() {
+            pretty_print(
+                &format!("Until your next turn: {}\n", effects.join(", ")),
+                BLUE,
+                true,
+            );
+        }
+    }
+
+    /// Offers the standard actions. The action is only spent once it has
+    /// actually been taken, so backing out leaves it available.
+    pub fn get_actions(&mut self) {
+        let can_cast = !self.play_object.character.spellcasting_classes().is_empty();
+        let actions: Vec<Action> = Action::iter()
+            .filter(|action| *action != Action::CastASpell || can_cast)
+            .collect();
+        let mut selections: Vec<String> = actions
+            .iter()
+            .map(|action| format!("{}: {}", action, action.description()))
+            .collect();
+        selections.push(String::from("Back"));
+
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Which action will you take?")
+            .default(0)
+            .max_length(10)
+            .items(&selections)
+            .interact()
+            .unwrap();
+        let action = match actions.get(selection) {
+            Some(action) => *action,
+            None => return,
+        };
+
+        let taken = match action {
+            Action::Attack => self.attack(),
+            Action::CastASpell => {
+                // After a bonus action spell, only a cantrip can be cast as the action.
+                self.cast_a_spell(self.turn.cantrip_only())
+            }
+            Action::Dash => {
+                self.dash();
+                true
+            }
+            Action::Disengage | Action::Dodge => {
+                pretty_print(&format!("{}.\n", action.description()), BLUE, true);
+                true
+            }
+            Action::Help => {
+                let ally: String = Input::with_theme(&ColorfulTheme::default())
+                    .with_prompt("Who are you helping?")
+                    .default(String::from("your ally"))
+                    .interact_text()
+                    .unwrap();
+                pretty_print(
+                    &format!(
+                        "{} has advantage on their next ability check or attack before your next turn.\n",
+                        ally
+                    ),
+                    BLUE,
+                    true,
+                );
+                true
+            }
+            Action::Hide => {
+                self.hide();
+                true
+            }
+            Action::Ready => self.ready(),
+            Action::Search => {
+                self.search();
+                true
+            }
+            Action::UseAnObject => {
+                self.use_an_object();
+                true
+            }
+        };
+        if taken {
+            self.turn.action = Some(action);
+            self.record(format!("You took the {} action", action));
+        }
+    }
+
+    fn dash(&mut self) {
+        let status = &mut self.play_object.character.status;
+        status.dash();
+        pretty_print(
+            &format!(
+                "You can move an extra {} feet this turn ({} feet left).\n",
+                status.effective_speed(),
+                status.movement
+            ),
+            BLUE,
+            true,
+        );
+    }
+
+    /// The Attack action: one attack, or more with Extra Attack. Returns
+    /// `false` if the player backs out before attacking.
+    fn attack(&mut self) -> bool {
+        let attacks = self.play_object.character.attacks_per_action();
+        for attack in 1..=attacks {
+            if attacks > 1 {
+                pretty_print(&format!("Attack {} of {}", attack, attacks), BLUE, true);
+            }
+            if !self.attack_action(AttackKind::Action) {
+                return attack > 1;
+            }
+        }
+        true
+    }
+
+    /// A single attack with a weapon or an unarmed strike. An off-hand attack
+    /// needs a light melee weapon, and an opportunity attack is made in melee.
+    /// Returns `false` if the player backs out.
+    fn attack_action(&mut self, kind: AttackKind) -> bool {
+        let character = &self.play_object.character;
+        let weapons: Vec<Weapon> = character
+            .inventory
+            .weapons()
+            .into_iter()
+            .filter(|w| match kind {
+                AttackKind::Action => true,
+                AttackKind::OffHand => w.has_property(WeaponProperty::Light) && !w.is_ranged(),
+                AttackKind::Opportunity => !w.is_ranged(),
+            })
+            .collect();
+        if kind == AttackKind::OffHand && weapons.is_empty() {
+            pretty_print(
+                "You need a light melee weapon for an off-hand attack.\n",
+                RED,
+                true,
+            );
+            return false;
+        }
+        let mut choices: Vec<String> = weapons
+            .iter()
+            .map(|weapon| {
+                let (attack_bonus, _) = character.weapon_attack(*weapon, Grip::OneHanded);
+                format!("{} {}", weapon.describe(), format_modifier(attack_bonus))
+            })
+            .collect();
+        let others = match kind {
+            AttackKind::Action => vec!["Unarmed strike", "Another weapon"],
+            AttackKind::OffHand => vec![],
+            AttackKind::Opportunity => vec!["Unarmed strike"],
+        };
+        choices.extend(others.iter().map(|other| other.to_string()));
+        choices.push(String::from("Back"));
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("What do you attack with?")
+            .default(0)
+            .items(&choices)
+            .interact()
+            .unwrap();
+        let other = selection
+            .checked_sub(weapons.len())
+            .and_then(|index| others.get(index));
+        let weapon = match (weapons.get(selection), other) {
+            (Some(weapon), _) => *weapon,
+            (None, Some(&"Unarmed strike")) => {
+                self.unarmed_strike();
+                return true;
+            }
+            (None, Some(&"Another weapon")) => match self.pick_up_weapon() {
+                Some(weapon) => weapon,
+                None => return false,
+            },
+            _ => return false,
+        };
+
+        let grip = if kind == AttackKind::OffHand {
+            Grip::OffHand
+        } else if weapon.versatile_die().is_some()
+            && Confirm::with_theme(&ColorfulTheme::default())
+                .with_prompt(format!("Wield the {} with two hands?", weapon))
+                .default(false)
+                .interact()
+                .unwrap()
+        {
+            Grip::TwoHanded
+        } else {
+            Grip::OneHanded
+        };
+        let (attack_bonus, damage) = self.play_object.character.weapon_attack(weapon, grip);
+        let label = match kind {
+            AttackKind::Action => format!("{} attack", weapon),
+            AttackKind::OffHand => format!("Off-hand {} attack", weapon),
+            AttackKind::Opportunity => format!("{} opportunity attack", weapon),
+        };
+        self.resolve_attack(attack_bonus, &label, damage.map(|d| d.to_string()));
+        true
+    }
+
+    /// Picks a weapon from the catalogue to attack with, offering to keep it.
+    fn pick_up_weapon(&mut self) -> Option<Weapon> {
+        let weapon = choose_weapon()?;
+        let character = &mut self.play_object.character;
+        if !character.is_proficient_with(weapon) {
+            pretty_print(
+                &format!(
+                    "You aren't proficient with the {}, so you don't add your proficiency bonus.",
+                    weapon
+                ),
+                RED,
+                true,
+            );
+        }
+        if Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!("Add the {} to your inventory?", weapon))
+            .default(true)
+            .interact()
+            .unwrap()
+        {
+            character.inventory.add(Item::from(weapon));
+            character.recalculate_status();
+        }
+        Some(weapon)
+    }
+
+    /// Rolls an attack against the target's AC and, on a hit, its damage,
+    /// doubling the damage dice on a critical hit. Attacking ends being hidden,
+    /// after granting advantage.
+    fn resolve_attack(&mut self, attack_bonus: i32, label: &str, damage: Option<String>) {
+        let target_ac = choose_target_number("What is the target's AC?", 10);
+        let mut advantages = vec![];
+        if self.hidden.take().is_some() {
+            advantages.push(String::from("Hidden"));
+            pretty_print("Attacking gives away your position.", BLUE, true);
+        }
+
+        let roll = match self.roll_d20(RollKind::Attack, attack_bonus, label, advantages, vec![]) {
+            Some(roll) => roll,
+            None => return,
+        };
+        let critical = roll.is_critical();
+        if !critical && (roll.is_fumble() || roll.total < target_ac) {
+            pretty_print(
+                &format!("Miss. ({} vs AC {})\n", roll.total, target_ac),
+                RED,
+                true,
+            );
+            return;
+        }
+        match critical {
+            true => pretty_print("Critical hit! Damage dice are doubled.", BLUE, true),
+            false => pretty_print(
+                &format!("Hit! ({} vs AC {})", roll.total, target_ac),
+                BLUE,
+                true,
+            ),
+        }
+
+        if let Some(expression) = choose_dice("What damage does it deal?", damage) {
+            let expression = match critical {
+                true => expression.critical(),
+                false => expression,
+            };
+            self.roll_expression(&expression, "Damage");
+        }
+    }
+
+    /// Picks the casting class and spell slot, expends the slot and resolves
+    /// the spell as an attack, a saving throw or a plain roll. Returns `false`
+    /// if the player backs out. With `cantrip_only`, as after a bonus action
+    /// spell, no slot can be used.
+    fn cast_a_spell(&mut self, cantrip_only: bool) -> bool {
+        let character = &self.play_object.character;
+        let casters: Vec<(String, usize)> = character
+            .spellcasting_classes()
+            .iter()
+            .map(|(class, ability)| (class.to_string(), *ability))
+            .collect();
+        let ability = match casters.len() {
+            1 => casters[0].1,
+            _ => {
+                let names: Vec<&String> = casters.iter().map(|(name, _)| name).collect();
+                let selection = Select::with_theme(&ColorfulTheme::default())
+                    .with_prompt("Which class's spell are you casting?")
+                    .default(0)
+                    .items(&names)
+                    .interact()
+                    .unwrap();
+                casters[selection].1
+            }
+        };
+
+        let mut slots = vec![(String::from("Cantrip (no slot)"), SpellSlot::Cantrip)];
+        if cantrip_only {
+            pretty_print(
+                "Having cast a bonus action spell, you can only cast a cantrip this turn.",
+                BLUE,
+                true,
+            );
+        }
+        for (i, remaining) in character.remaining_spell_slots().iter().enumerate() {
+            if *remaining > 0 && !cantrip_only {
+                slots.push((
+                    format!("{} level ({} left)", ordinal(i as u8 + 1), remaining),
+                    SpellSlot::Level(i),
+                ));
+            }
+        }
+        if let Some((remaining, level)) = character.remaining_pact_slots() {
+            if remaining > 0 && !cantrip_only {
+                slots.push((
+                    format!("Pact Magic, {} level ({} left)", ordinal(level), remaining),
+                    SpellSlot::Pact,
+                ));
+            }
+        }
+        let mut selections: Vec<&String> = slots.iter().map(|(label, _)| label).collect();
+        let back = String::from("Back");
+        selections.push(&back);
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Which spell slot will you use?")
+            .default(0)
+            .items(&selections)
+            .interact()
+            .unwrap();
+        let slot = match slots.get(selection) {
+            Some((_, slot)) => *slot,
+            None => return false,
+        };
+
+        let attack_bonus = character.spell_attack_bonus(ability) as i32;
+        let save_dc = character.spell_save_dc(ability);
+        let status = &mut self.play_object.character.status;
+        match slot {
+            SpellSlot::Cantrip => {}
+            SpellSlot::Level(i) => status.spell_slots_used[i] += 1,
+            SpellSlot::Pact => status.pact_slots_used += 1,
+        }
+        if !matches!(slot, SpellSlot::Cantrip) {
+            self.turn.leveled_spell = true;
+        }
+
+        let choices = [
+            format!("Spell attack {}", format_modifier(attack_bonus)),
+            format!("Saving throw (DC {})", save_dc),
+            String::from("No attack or save"),
+        ];
+        let choice = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("How is the spell resolved?")
+            .default(0)
+            .items(&choices)
+            .interact()
+            .unwrap();
+        match choice {
+            0 => self.resolve_attack(attack_bonus, "Spell attack", None),
+            1 => {
+                pretty_print(
+                    &format!(
+                        "Your targets make their saving throw against DC {}.",
+                        save_dc
+                    ),
+                    BLUE,
+                    true,
+                );
+                if let Some(expression) = choose_dice(
+                    "What damage does it deal on a failed save? (press ENTER to skip)",
+                    None,
+                ) {
+                    let roll = self.roll_expression(&expression, "Damage");
+                    pretty_print(
+                        &format!("Half damage on a successful save: {}\n", roll.total / 2),
+                        BLUE,
+                        true,
+                    );
+                }
+            }
+            _ => {
+                if let Some(expression) = choose_dice(
+                    "Roll any healing or effect dice (press ENTER to skip)",
+                    None,
+                ) {
+                    self.roll_expression(&expression, "Spell");
+                }
+            }
+        }
+        true
+    }
+
+    fn hide(&mut self) {
+        let modifier = self.play_object.character.skill_modifier(Skill::Stealth) as i32;
+        let disadvantages = self.skill_disadvantages(Skill::Stealth);
+        self.hidden = self
+            .roll_d20(
+                RollKind::AbilityCheck(1),
+                modifier,
+                "Stealth check",
+                vec![],
+                disadvantages,
+            )
+            .map(|roll| roll.total);
+        if let Some(stealth) = self.hidden {
+            pretty_print(
+                &format!(
+                    "You hide. Creatures need a Perception of {} or more to find you.\n",
+                    stealth
+                ),
+                BLUE,
+                true,
+            );
+        }
+    }
+
+    /// Disadvantage on a skill check from what the character is wearing.
+    fn skill_disadvantages(&self, skill: Skill) -> Vec<String> {
+        match self.play_object.character.inventory.armor() {
+            Some(armor) if skill == Skill::Stealth && armor.stealth_disadvantage() => {
+                vec![armor.to_string()]
+            }
+            _ => vec![],
+        }
+    }
+
+    /// Prepares another action to take as a reaction. Returns `false` if the
+    /// player backs out.
+    fn ready(&mut self) -> bool {
+        let actions: Vec<Action> = Action::iter().filter(|a| *a != Action::Ready).collect();
+        let mut selections: Vec<String> = actions.iter().map(|a| a.to_string()).collect();
+        selections.push(String::from("Back"));
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Which action are you readying?")
+            .default(0)
+            .items(&selections)
+            .interact()
+            .unwrap();
+        let action = match actions.get(selection) {
+            Some(action) => *action,
+            None => return false,
+        };
+        let trigger: String = Input::with_theme(&ColorfulTheme::default())
+            .with_prompt("What is the trigger?")
+            .interact_text()
+            .unwrap();
+        pretty_print(
+            &format!(
+                "You will {} when: {}. It takes your reaction.\n",
+                action, trigger
+            ),
+            BLUE,
+            true,
+        );
+        self.turn.readied = Some((trigger, action));
+        true
+    }
+
+    fn search(&mut self) {
+        let character = &self.play_object.character;
+        let skills = [Skill::Perception, Skill::Investigation];
+        let selections: Vec<String> = skills
+            .iter()
+            .map(|skill| {
+                format!(
+                    "{} {}",
+                    skill,
+                    format_modifier(character.skill_modifier(*skill) as i32)
+                )
+            })
+            .collect();
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("How are you searching?")
+            .default(0)
+            .items(&selections)
+            .interact()
+            .unwrap();
+        let skill = skills[selection];
+        let modifier = character.skill_modifier(skill) as i32;
+        self.roll_d20(
+            RollKind::AbilityCheck(skill.ability()),
+            modifier,
+            &format!("{} check", skill),
+            vec![],
+            vec![],
+        );
+    }
+
+    fn use_an_object(&mut self) {
+        let object: String = Input::with_theme(&ColorfulTheme::default())
+            .with_prompt("What object are you using?")
+            .interact_text()
+            .unwrap();
+        if let Some(expression) = choose_dice(
+            "Roll any dice for it, e.g. 2d4+2 for a potion of healing (press ENTER to skip)",
+            None,
+        ) {
+            self.roll_expression(&expression, &object);
+        }
+    }
+
+    /// Whether the character is in any state to take a reaction.
+    fn can_react(&self) -> bool {
+        let status = &self.play_object.character.status;
+        !status.dead && !status.is_incapacitated()
+    }
+
+    /// Lets the game interrupt before the character's next turn with anything
+    /// that could call for a reaction.
+    fn between_turns(&mut self) {
+        if !self.can_react() {
+            return;
+        }
+        loop {
+            let triggers: Vec<Trigger> = Trigger::iter()
+                .filter(|t| *t != Trigger::Readied || self.turn.readied.is_some())
+                .collect();
+            let mut selections: Vec<String> = triggers.iter().map(|t| t.to_string()).collect();
+            selections.push(String::from("Nothing else happens"));
+            let selection = Select::with_theme(&ColorfulTheme::default())
+                .with_prompt("Before your next turn, does anything happen?")
+                .default(selections.len() - 1)
+                .items(&selections)
+                .interact()
+                .unwrap();
+            match triggers.get(selection) {
+                Some(trigger) => {
+                    let hit = trigger.is_hit().then(choose_hit);
+                    self.react(*trigger, hit);
+                }
+                None => break,
+            }
+        }
+    }
+
+    /// Reactions the character could take in response to `trigger`.
+    fn reactions_for(&self, trigger: Trigger) -> Vec<Reaction> {
+        let character = &self.play_object.character;
+        let casts =
+            |classes: &[Class]| classes.iter().any(|class| character.class_level(class) > 0);
+        let has_slot = |min_level: u8, pact: bool| {
+            character.remaining_spell_slots()[min_level as usize - 1..]
+                .iter()
+                .any(|left| *left > 0)
+                || (pact
+                    && matches!(
+                        character.remaining_pact_slots(),
+                        Some((left, level)) if left > 0 && level >= min_level
+                    ))
+        };
+
+        let mut reactions = Vec::new();
+        match trigger {
+            Trigger::EnemyLeavesReach => reactions.push(Reaction::OpportunityAttack),
+            Trigger::HitByAttack | Trigger::HitByRangedWeapon => {
+                if casts(&[Class::Sorcerer(None), Class::Wizard(None)]) && has_slot(1, false) {
+                    reactions.push(Reaction::Shield);
+                }
+                if character.class_level(&Class::Rogue(None)) >= 5 {
+                    reactions.push(Reaction::UncannyDodge);
+                }
+                if trigger == Trigger::HitByRangedWeapon
+                    && character.class_level(&Class::Monk(None)) >= 3
+                {
+                    reactions.push(Reaction::DeflectMissiles);
+                }
+            }
+            Trigger::SpellCast => {
+                let counterspellers = [
+                    Class::Sorcerer(None),
+                    Class::Warlock(None),
+                    Class::Wizard(None),
+                ];
+                if casts(&counterspellers) && has_slot(3, true) {
+                    reactions.push(Reaction::Counterspell);
+                }
+            }
+            Trigger::DamagedByCreature => {
+                if character.resource_remaining(Resource::HellishRebuke) > 0
+                    || (casts(&[Class::Warlock(None)]) && has_slot(1, true))
+                {
+                    reactions.push(Reaction::HellishRebuke);
+                }
+            }
+            Trigger::Readied => reactions.push(Reaction::ReadiedAction),
+        }
+        reactions
+    }
+
+    /// Whether there is a reaction the character could take to `trigger`
+    /// right now, so that it is worth asking.
+    fn could_react(&self, trigger: Trigger) -> bool {
+        self.can_react() && !self.turn.reaction_used() && !self.reactions_for(trigger).is_empty()
+    }
+
+    /// Offers the reactions for `trigger`, if the reaction hasn't been used
+    /// this round, and records what happened in the turn log. The reaction is
+    /// only spent once it has actually been taken. The damage of a `hit` is
+    /// then taken, unless the reaction made it miss. Returns whether it hit.
+    fn react(&mut self, trigger: Trigger, mut hit: Option<Hit>) -> bool {
+        if let Some(reaction) = self.choose_reaction(trigger) {
+            match self.resolve_reaction(reaction, &mut hit) {
+                true => {
+                    self.turn.reaction = Some(reaction);
+                    self.record(format!("{}; you reacted with {}", trigger, reaction));
+                }
+                false => self.record(trigger.to_string()),
+            }
+        }
+        match hit {
+            Some(hit) => {
+                self.take_damage(hit.damage, hit.damage_type, hit.critical);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Asks which reaction to take to `trigger`, or explains why none can be.
+    fn choose_reaction(&mut self, trigger: Trigger) -> Option<Reaction> {
+        if !self.can_react() {
+            pretty_print("You can't take reactions while incapacitated.\n", RED, true);
+            self.record(format!("{}; you couldn't react", trigger));
+            return None;
+        }
+        if self.turn.reaction_used() {
+            pretty_print("You already used your reaction this round.\n", RED, true);
+            self.record(format!("{}; no reaction left", trigger));
+            return None;
+        }
+        let reactions = self.reactions_for(trigger);
+        let mut selections: Vec<String> = reactions.iter().map(|r| r.to_string()).collect();
+        selections.push(String::from("Don't react"));
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!("{}. Will you use your reaction?", trigger))
+            .default(0)
+            .items(&selections)
+            .interact()
+            .unwrap();
+        let reaction = reactions.get(selection).copied();
+        if reaction.is_none() {
+            self.record(trigger.to_string());
+        }
+        reaction
+    }
+
+    /// Takes `reaction`, which may change or stop the `hit` being reacted to.
+    /// Returns `false` if it couldn't be taken after all.
+    fn resolve_reaction(&mut self, reaction: Reaction, hit: &mut Option<Hit>) -> bool {
+        match reaction {
+            Reaction::OpportunityAttack => self.attack_action(AttackKind::Opportunity),
+            Reaction::Shield => self.shield(hit),
+            Reaction::Counterspell => self.counterspell(),
+            Reaction::UncannyDodge => {
+                if let Some(hit) = hit {
+                    hit.damage /= 2;
+                    pretty_print(
+                        &format!("Uncanny Dodge halves the damage to {}.", hit.damage),
+                        BLUE,
+                        true,
+                    );
+                }
+                true
+            }
+            Reaction::DeflectMissiles => {
+                self.deflect_missiles(hit);
+                true
+            }
+            Reaction::HellishRebuke => self.hellish_rebuke(),
+            Reaction::ReadiedAction => self.readied_action(),
+        }
+    }
+
+    /// Takes the action prepared with Ready. Returns `false` if nothing was
+    /// readied or the player backs out, leaving it readied.
+    fn readied_action(&mut self) -> bool {
+        let action = match &self.turn.readied {
+            Some((_, action)) => *action,
+            None => return false,
+        };
+        let taken = match action {
+            Action::Attack => self.attack_action(AttackKind::Action),
+            Action::CastASpell => self.cast_a_spell(false),
+            Action::Hide => {
+                self.hide();
+                true
+            }
+            Action::Search => {
+                self.search();
+                true
+            }
+            Action::UseAnObject => {
+                self.use_an_object();
+                true
+            }
+            _ => {
+                pretty_print(&format!("{}.\n", action.description()), BLUE, true);
+                true
+            }
+        };
+        if taken {
+            self.turn.readied = None;
+        }
+        taken
+    }
+
+    /// The character's AC, including Shield if it was cast since their turn.
+    fn armor_class(&self) -> i32 {
+        let armor_class = self.play_object.character.status.armor_class();
+        match self.turn.shield {
+            true => armor_class + 5,
+            false => armor_class,
+        }
+    }
+
+    /// +5 AC until the start of the character's next turn, which may turn the
+    /// triggering hit into a miss. Returns `false` if no slot was spent.
+    fn shield(&mut self, hit: &mut Option<Hit>) -> bool {
+        if self
+            .play_object
+            .character
+            .expend_spell_slot(1, false)
+            .is_none()
+        {
+            return false;
+        }
+        self.turn.shield = true;
+        let armor_class = self.armor_class();
+        let attack = match hit {
+            Some(hit) => hit.attack,
+            None => choose_target_number("What did the attack roll total?", 10),
+        };
+        match attack < armor_class {
+            true => {
+                pretty_print(
+                    &format!(
+                        "Shield raises your AC to {}: the attack misses!\n",
+                        armor_class
+                    ),
+                    BLUE,
+                    true,
+                );
+                *hit = None;
+            }
+            false => pretty_print(
+                &format!(
+                    "Shield raises your AC to {}, but the attack still hits.\n",
+                    armor_class
+                ),
+                RED,
+                true,
+            ),
+        }
+        true
+    }
+
+    /// Stops a spell outright if the slot is high enough, or with a
+    /// spellcasting ability check against DC 10 + the spell's level. Returns
+    /// `false` if no slot was spent.
+    fn counterspell(&mut self) -> bool {
+        let spell_level: String = Input::with_theme(&ColorfulTheme::default())
+            .with_prompt("What level is the spell being cast? (0 for a cantrip)")
+            .default(String::from("3"))
+            .validate_with(|input: &String| -> Result<(), &str> {
+                match input.parse::<u8>() {
+                    Ok(0..=9) => Ok(()),
+                    _ => Err("Please enter a spell level between 0 and 9"),
+                }
+            })
+            .interact_text()
+            .unwrap();
+        let spell_level: u8 = spell_level.parse().unwrap();
+
+        let character = &mut self.play_object.character;
+        let ability = character
+            .spellcasting_classes()
+            .iter()
+            .find(|(class, _)| {
+                matches!(
+                    class,
+                    Class::Sorcerer(_) | Class::Warlock(_) | Class::Wizard(_)
+                )
+            })
+            .map(|(_, ability)| *ability)
+            .unwrap_or(3);
+        let slot = match character.expend_spell_slot(3, true) {
+            Some(slot) => slot,
+            None => return false,
+        };
+        pretty_print(
+            &format!("You cast Counterspell at {} level.", ordinal(slot)),
+            BLUE,
+            true,
+        );
+        if spell_level <= slot {
+            pretty_print("The spell fails and has no effect.\n", BLUE, true);
+            return true;
+        }
+
+        let dc = 10 + spell_level as i32;
+        let modifier = character.ability_check_modifier(ability) as i32;
+        match self.roll_d20(
+            RollKind::AbilityCheck(ability),
+            modifier,
+            "Counterspell check",
+            vec![],
+            vec![],
+        ) {
+            Some(roll) if roll.total >= dc => pretty_print(
+                &format!("The spell is countered! ({} vs DC {})\n", roll.total, dc),
+                BLUE,
+                true,
+            ),
+            _ => pretty_print(
+                &format!("The spell goes off anyway. (DC {})\n", dc),
+                RED,
+                true,
+            ),
+        }
+        true
+    }
+
+    /// Reduces ranged weapon damage by 1d10 + Dexterity modifier + Monk level.
+    fn deflect_missiles(&mut self, hit: &mut Option<Hit>) {
+        let character = &self.play_object.character;
+        let reduction = Dice::D10.expression(1).with_modifier(
+            character.get_dex().get_modifier() as i32
+                + character.class_level(&Class::Monk(None)) as i32,
+        );
+        let roll = self.roll_expression(&reduction, "Deflect Missiles");
+        if let Some(deflected) = hit {
+            deflected.damage = (deflected.damage - roll.total).max(0);
+            if deflected.damage == 0 {
+                pretty_print(
+                    "You catch the missile, and can spend 1 ki to throw it back as part of the reaction.\n",
+                    BLUE,
+                    true,
+                );
+                *hit = None;
+            }
+        }
+    }
+
+    /// 2d10 fire damage to the creature that damaged the character, halved on
+    /// a successful Dexterity save. A Tiefling's free casting is used first.
+    /// Returns `false` if no slot was spent.
+    fn hellish_rebuke(&mut self) -> bool {
+        let character = &mut self.play_object.character;
+        // Infernal Legacy casts it as a 2nd-level spell; otherwise it takes
+        // the slot spent, dealing another 1d10 for each level above 1st.
+        let level = match character.spend_resource(Resource::HellishRebuke, 1) {
+            true => 2,
+            false => match character.expend_spell_slot(1, true) {
+                Some(level) => level,
+                None => return false,
+            },
+        };
+        // Both Infernal Legacy and Warlock spells are cast with Charisma.
+        let dc = character.spell_save_dc(5);
+        pretty_print(
+            &format!(
+                "The creature makes a Dexterity saving throw against DC {}.",
+                dc
+            ),
+            BLUE,
+            true,
+        );
+        let roll = self.roll_expression(
+            &Dice::D10.expression(1 + level as u16),
+            &format!("Hellish Rebuke at {} level (fire)", ordinal(level)),
+        );
+        pretty_print(
+            &format!("Half damage on a successful save: {}\n", roll.total / 2),
+            BLUE,
+            true,
+        );
+        true
+    }
+
+    /// Rolls initiative for the character and everyone else in the fight.
+    fn start_encounter(&mut self) {
+        let character = &self.play_object.character;
+        let modifier = character.status.initiative();
+        let mut player = Combatant::new(
+            &character.name,
+            true,
+            modifier,
+            character.get_dex().value(),
+            &mut self.rng,
+        );
+        player.initiative = self
+            .roll_d20(
+                RollKind::AbilityCheck(1),
+                modifier,
+                "Initiative",
+                vec![],
+                vec![],
+            )
+            .map_or(1 + modifier, |roll| roll.total);
+
+        let mut combatants = vec![player];
+        while let Some(mut more) = self.choose_new_combatants(&combatants) {
+            combatants.append(&mut more);
+        }
+        let encounter = Encounter::new(combatants);
+        encounter.display();
+        self.encounter = Some(encounter);
+        self.record(String::from("The encounter began"));
+    }
+
+    /// Asks for more combatants to join those `present` and rolls their
+    /// initiative, or `None` once everyone has been added.
+    fn choose_new_combatants(&mut self, present: &[Combatant]) -> Option<Vec<Combatant>> {
+        let choices = ["Monsters from the bestiary", "Someone else", "No one else"];
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Who else is in the fight?")
+            .default(0)
+            .items(&choices)
+            .interact()
+            .unwrap();
+        let combatants = match selection {
+            0 => self.choose_monsters(present),
+            1 => vec![self.choose_other_combatant()],
+            _ => return None,
+        };
+        for combatant in &combatants {
+            pretty_print(
+                &format!(
+                    "{} rolls initiative: {}",
+                    combatant.name, combatant.initiative
+                ),
+                BLUE,
+                true,
+            );
+        }
+        Some(combatants)
+    }
+
+    /// Any number of one monster, each with its own HP and initiative.
+    fn choose_monsters(&mut self, present: &[Combatant]) -> Vec<Combatant> {
+        let names: Vec<&str> = self.bestiary.iter().map(|m| m.name.as_str()).collect();
+        let selection = FuzzySelect::with_theme(&ColorfulTheme::default())
+            .with_prompt("Which monster? (type to search)")
+            .default(0)
+            .items(&names)
+            .interact()
+            .unwrap();
+        let monster = self.bestiary[selection].clone();
+
+        let count: String = Input::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!("How many {}s?", monster.name))
+            .default(String::from("1"))
+            .validate_with(|input: &String| -> Result<(), &str> {
+                match input.parse::<u8>() {
+                    Ok(1..=20) => Ok(()),
+                    _ => Err("Please enter a number between 1 and 20"),
+                }
+            })
+            .interact_text()
+            .unwrap();
+        let count: usize = count.parse().unwrap();
+
+        let use_average = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!("Hit points ({})", monster.hit_points))
+            .default(0)
+            .items(&["Roll them", "Use the average"])
+            .interact()
+            .unwrap()
+            == 1;
+
+        // Number them on from any of the same monster already in the fight.
+        number_monsters(present, &monster.name, count)
+            .into_iter()
+            .map(|name| {
+                let mut combatant =
+                    Combatant::from_monster(&name, &monster, use_average, &mut self.rng);
+                combatant.roll_initiative(&mut self.rng);
+                combatant
+            })
+            .collect()
+    }
+
+    /// A combatant without a stat block, such as another player's character.
+    fn choose_other_combatant(&mut self) -> Combatant {
+        let name: String = Input::with_theme(&ColorfulTheme::default())
+            .with_prompt("Name")
+            .interact_text()
+            .unwrap();
+        let modifier: String = Input::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!("{}'s initiative modifier", name.trim()))
+            .default(String::from("0"))
+            .validate_with(|input: &String| -> Result<(), &str> {
+                match input.parse::<i32>() {
+                    Ok(-10..=20) => Ok(()),
+                    _ => Err("Please enter a modifier between -10 and 20"),
+                }
+            })
+            .interact_text()
+            .unwrap();
+        let modifier: i32 = modifier.parse().unwrap();
+
+        // Without a stat block, assume the Dexterity score the modifier implies.
+        let dexterity = (10 + 2 * modifier).clamp(1, 30) as u8;
+        let mut combatant = Combatant::new(name.trim(), false, modifier, dexterity, &mut self.rng);
+        combatant.roll_initiative(&mut self.rng);
+        combatant
+    }
+
+    /// Rolls one of a monster's attacks or damaging effects against the character.
+    fn monster_attack(&mut self, name: &str, monster: &Monster) {
+        let actions: Vec<&MonsterAction> = monster
+            .actions
+            .iter()
+            .chain(&monster.legendary_actions)
+            .filter(|action| action.attack_bonus.is_some() || action.damage.is_some())
+            .collect();
+        let mut choices: Vec<String> = actions.iter().map(|a| a.name.clone()).collect();
+        choices.push(String::from("Back"));
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!("What does {} use?", name))
+            .default(0)
+            .items(&choices)
+            .interact()
+            .unwrap();
+        let action = match actions.get(selection) {
+            Some(action) => *action,
+            None => return,
+        };
+
+        let mut attack = None;
+        let mut critical = false;
+        if let Some(bonus) = action.attack_bonus {
+            let mode = match self.turn.is_active(Action::Dodge) {
+                true => {
+                    pretty_print("Disadvantage from your Dodge", BLUE, true);
+                    RollMode::Disadvantage
+                }
+                false => RollMode::Normal,
+            };
+            let roll = DiceExpression::from(Dice::D20)
+                .with_modifier(bonus as i32)
+                .with_mode(mode)
+                .roll(&mut self.rng);
+            pretty_print(&format!("\n{}: {}", action.name, roll), BLUE, true);
+            let armor_class = self.armor_class();
+            critical = roll.is_critical();
+            if roll.is_fumble() || (!critical && roll.total < armor_class) {
+                pretty_print(
+                    &format!("It misses you (AC {}).\n", armor_class),
+                    BLUE,
+                    true,
+                );
+                return;
+            }
+            pretty_print(
+                &format!(
+                    "{} you (AC {}).",
+                    if critical {
+                        "A critical hit on"
+                    } else {
+                        "It hits"
+                    },
+                    armor_class
+                ),
+                RED,
+                true,
+            );
+            attack = Some(roll.total);
+        } else {
+            pretty_print(&action.description, BLUE, true);
+        }
+        if let Some(damage) = action.damage() {
+            let damage = match critical {
+                true => damage.critical(),
+                false => damage,
+            };
+            let damage_type = damage.label.as_deref().and_then(DamageType::from_label);
+            let roll = self.roll_expression(&damage, &format!("{} damage", action.name));
+            let amount = match attack {
+                Some(_) => roll.total,
+                None => {
+                    let selection = Select::with_theme(&ColorfulTheme::default())
+                        .with_prompt("How much of it do you take?")
+                        .default(0)
+                        .items(&["All of it", "Half (a successful save)", "None of it"])
+                        .interact()
+                        .unwrap();
+                    match selection {
+                        0 => roll.total,
+                        1 => roll.total / 2,
+                        _ => return,
+                    }
+                }
+            };
+            let damage_type = damage_type.unwrap_or_else(choose_damage_type);
+            let damaged = match attack {
+                // The hit itself can be reacted to before its damage lands.
+                Some(attack) => {
+                    let hit = Hit {
+                        attack,
+                        damage: amount,
+                        damage_type,
+                        critical,
+                    };
+                    let trigger = match action.is_ranged_weapon_attack() {
+                        true => Trigger::HitByRangedWeapon,
+                        false => Trigger::HitByAttack,
+                    };
+                    match self.could_react(trigger) {
+                        true => self.react(trigger, Some(hit)),
+                        false => {
+                            self.take_damage(amount, damage_type, critical);
+                            true
+                        }
+                    }
+                }
+                None => {
+                    self.take_damage(amount, damage_type, critical);
+                    true
+                }
+            };
+            if damaged && amount > 0 && self.could_react(Trigger::DamagedByCreature) {
+                self.react(Trigger::DamagedByCreature, None);
+            }
+        }
+    }
+
+    /// Damage, healing, temporary HP and the character's resistances.
+    fn manage_hit_points(&mut self) {
+        let choices = [
+            "Take damage",
+            "Regain hit points",
+            "Gain temporary hit points",
+            "Add a resistance, immunity or vulnerability",
+            "Remove a resistance, immunity or vulnerability",
+            "Get stabilized",
+            "Back",
+        ];
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("What happens to your hit points?")
+            .default(0)
+            .items(&choices)
+            .interact()
+            .unwrap();
+
+        match selection {
+            0 => {
+                let amount = choose_amount("How much damage?");
+                let damage_type = choose_damage_type();
+                self.take_damage(amount, damage_type, false);
+            }
+            1 => {
+                let amount = choose_amount("How many hit points?");
+                let status = &mut self.play_object.character.status;
+                let healed = status.heal(amount);
+                pretty_print(
+                    &format!(
+                        "You regain {} HP ({}/{} HP).\n",
+                        healed,
+                        status.current_hp(),
+                        status.effective_maximum_hp()
+                    ),
+                    BLUE,
+                    true,
+                );
+                if healed > 0 && status.current_hp() == healed {
+                    pretty_print("You regain consciousness.\n", BLUE, true);
+                }
+                self.record(format!("You regained {} HP", healed));
+            }
+            2 => {
+                let amount = choose_amount("How many temporary hit points?");
+                let status = &mut self.play_object.character.status;
+                match status.gain_temporary_hp(amount) {
+                    true => pretty_print(
+                        &format!("You have {} temporary HP.\n", status.temporary_hp),
+                        BLUE,
+                        true,
+                    ),
+                    false => pretty_print(
+                        &format!(
+                            "Temporary HP don't stack, so you keep your {}.\n",
+                            status.temporary_hp
+                        ),
+                        BLUE,
+                        true,
+                    ),
+                }
+            }
+            3 => self.add_damage_defense(),
+            4 => self.remove_damage_defense(),
+            5 => self.get_stabilized(),
+            _ => {}
+        }
+    }
+
+    /// A short rest, spending hit dice, or a long rest.
+    fn rest(&mut self) {
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("How long do you rest?")
+            .default(0)
+            .items(&["Short rest (1 hour)", "Long rest (8 hours)", "Back"])
+            .interact()
+            .unwrap();
+        match selection {
+            0 => {
+                self.spend_hit_dice();
+                self.play_object.character.short_rest();
+                pretty_print(
+                    "You finish a short rest. Features that recharge on a short rest are restored.\n",
+                    BLUE,
+                    true,
+                );
+                self.record(String::from("You took a short rest"));
+                self.autosave();
+            }
+            1 => {
+                let character = &mut self.play_object.character;
+                match character.long_rest() {
+                    Some(recovered) => {
+                        pretty_print(
+                            &format!(
+                                "You finish a long rest with {} HP, your features and spell slots restored and {} hit {} recovered.\n",
+                                character.status.current_hp(),
+                                recovered,
+                                if recovered == 1 { "die" } else { "dice" }
+                            ),
+                            BLUE,
+                            true,
+                        );
+                        self.record(String::from("You took a long rest"));
+                        self.autosave();
+                    }
+                    None => pretty_print(
+                        "You need at least 1 hit point to benefit from a long rest.\n",
+                        RED,
+                        true,
+                    ),
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Spends hit dice one at a time until the player is done or runs out.
+    fn spend_hit_dice(&mut self) {
+        loop {
+            let character = &self.play_object.character;
+            let remaining = character.remaining_hit_dice();
+            if remaining.is_empty() {
+                pretty_print("You have no hit dice left to spend.", BLUE, true);
+                return;
+            }
+            let mut choices: Vec<String> = remaining
+                .iter()
+                .map(|(die, count)| format!("Spend a {} ({} left)", die, count))
+                .collect();
+            choices.push(String::from("Done"));
+            let selection = Select::with_theme(&ColorfulTheme::default())
+                .with_prompt(format!(
+                    "You have {}/{} HP. Spend a hit die?",
+                    character.status.current_hp(),
+                    character.status.effective_maximum_hp()
+                ))
+                .default(0)
+                .items(&choices)
+                .interact()
+                .unwrap();
+            let die = match remaining.get(selection) {
+                Some((die, _)) => *die,
+                None => return,
+            };
+            let character = &mut self.play_object.character;
+            if let Some((roll, healed)) = character.spend_hit_die(die, &mut self.rng) {
+                pretty_print(
+                    &format!("\nHit die: {}\nYou regain {} HP.\n", roll, healed),
+                    BLUE,
+                    true,
+                );
+            }
+        }
+    }
+
+    /// Applies damage of one type to the character, showing how their
+    /// defenses and temporary HP changed it. A `critical` hit at 0 HP costs
+    /// two death saves.
+    fn take_damage(&mut self, amount: i32, damage_type: DamageType, critical: bool) {
+        let character = &mut self.play_object.character;
+        let defenses = character.damage_defenses(damage_type);
+        let kinds: Vec<Defense> = defenses.iter().map(|d| d.defense).collect();
+        let adjusted = adjust_damage(amount, &kinds);
+        if adjusted != amount {
+            pretty_print(
+                &format!(
+                    "{} turns {} {} damage into {}.",
+                    defenses
+                        .iter()
+                        .map(|d| d.to_string())
+                        .collect::<Vec<String>>()
+                        .join(", "),
+                    amount,
+                    damage_type,
+                    adjusted
+                ),
+                BLUE,
+                true,
+            );
+        }
+
+        let status = &mut character.status;
+        let taken = status.take_damage(adjusted, critical);
+        if taken.temporary > 0 {
+            pretty_print(
+                &format!("Your temporary HP absorb {}.", taken.temporary),
+                BLUE,
+                true,
+            );
+        }
+        pretty_print(
+            &format!(
+                "You lose {} HP ({}/{} HP).\n",
+                taken.lost,
+                status.current_hp(),
+                status.effective_maximum_hp()
+            ),
+            RED,
+            true,
+        );
+        if taken.instant_death {
+            pretty_print(
+                "The damage left over at 0 HP is at least your hit point maximum. You die instantly.\n",
+                RED,
+                true,
+            );
+        } else if taken.death_save_failures > 0 {
+            pretty_print(
+                &format!(
+                    "Taking damage at 0 HP counts as {} death save failure{} ({}).\n",
+                    taken.death_save_failures,
+                    if taken.death_save_failures == 1 {
+                        ""
+                    } else {
+                        "s"
+                    },
+                    status.death_saves
+                ),
+                RED,
+                true,
+            );
+            if status.dead {
+                pretty_print("That is your third failure. You die.\n", RED, true);
+            }
+        } else if taken.lost > 0 && status.current_hp() == 0 {
+            pretty_print(
+                "You drop to 0 hit points and fall unconscious. You start making death saving throws.\n",
+                RED,
+                true,
+            );
+        }
+        self.record(format!("You took {} {} damage", adjusted, damage_type));
+    }
+
+    /// A resistance, immunity or vulnerability from an item, spell or other effect.
+    fn add_damage_defense(&mut self) {
+        let defenses: Vec<Defense> = Defense::iter().collect();
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("What do you gain?")
+            .default(0)
+            .items(&defenses)
+            .interact()
+            .unwrap();
+        let defense = defenses[selection];
+        let damage_type = choose_damage_type();
+        let source: String = Input::with_theme(&ColorfulTheme::default())
+            .with_prompt("Where does it come from?")
+            .default(String::from("an item"))
+            .interact_text()
+            .unwrap();
+        let defense = DamageDefense {
+            defense,
+            damage_type,
+            source,
+        };
+        pretty_print(&format!("You gain {}.\n", defense), BLUE, true);
+        self.play_object
+            .character
+            .status
+            .damage_defenses
+            .push(defense);
+    }
+
+    fn remove_damage_defense(&mut self) {
+        let status = &mut self.play_object.character.status;
+        if status.damage_defenses.is_empty() {
+            pretty_print(
+                "You have no resistances, immunities or vulnerabilities from items or effects.\n",
+                BLUE,
+                true,
+            );
+            return;
+        }
+        let mut choices: Vec<String> = status
+            .damage_defenses
+            .iter()
+            .map(|d| d.to_string())
+            .collect();
+        choices.push(String::from("Back"));
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Which one ends?")
+            .default(0)
+            .items(&choices)
+            .interact()
+            .unwrap();
+        if selection < status.damage_defenses.len() {
+            let defense = status.damage_defenses.remove(selection);
+            pretty_print(&format!("You lose {}.\n", defense), BLUE, true);
+        }
+    }
+
+    /// Damages or heals a combatant whose HP are tracked from their stat block.
+    fn change_combatant_hp(&mut self) {
+        let encounter = match &mut self.encounter {
+            Some(encounter) => encounter,
+            None => return,
+        };
+        let tracked: Vec<usize> = (0..encounter.combatants().len())
+            .filter(|index| encounter.combatants()[*index].hit_points.is_some())
+            .collect();
+        let mut choices: Vec<String> = tracked
+            .iter()
+            .map(|index| encounter.combatants()[*index].name.clone())
+            .collect();
+        choices.push(String::from("Back"));
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Whose hit points change?")
+            .default(0)
+            .items(&choices)
+            .interact()
+            .unwrap();
+        let index = match tracked.get(selection) {
+            Some(index) => *index,
+            None => return,
+        };
+        let heal = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Do they take damage or regain hit points?")
+            .default(0)
+            .items(&["Take damage", "Regain hit points"])
+            .interact()
+            .unwrap()
+            == 1;
+        let amount = choose_amount("How much?");
+        let combatant = &mut encounter.combatants_mut()[index];
+        let change = match heal {
+            true => format!("regains {} HP", combatant.heal(amount)),
+            false => {
+                let damage_type = choose_damage_type();
+                format!("loses {} HP", combatant.take_damage(amount, damage_type))
+            }
+        };
+        let name = combatant.name.clone();
+        let (current, maximum) = combatant.hit_points.unwrap_or_default();
+        pretty_print(
+            &format!("{} {} ({}/{} HP).\n", name, change, current, maximum),
+            BLUE,
+            true,
+        );
+        if current == 0
+            && Confirm::with_theme(&ColorfulTheme::default())
+                .with_prompt(format!("{} is down. Remove them from the fight?", name))
+                .default(true)
+                .interact()
+                .unwrap()
+        {
+            encounter.remove(index);
+            self.record(format!("{} was defeated", name));
+        }
+    }
+
+    /// Changes the encounter from the character's turn. Returns true if the
+    /// character's turn ends because of it.
+    fn manage_encounter(&mut self) -> bool {
+        let encounter = match &self.encounter {
+            Some(encounter) => encounter,
+            None => {
+                self.start_encounter();
+                return true;
+            }
+        };
+        let can_delay = !self.turn.action_used()
+            && !self.turn.bonus_action_used()
+            && encounter.current_index() + 1 < encounter.combatants().len();
+        let mut choices = vec![
+            "Show the initiative order",
+            "Add a combatant",
+            "Remove a combatant",
+        ];
+        if encounter
+            .combatants()
+            .iter()
+            .any(|c| c.hit_points.is_some())
+        {
+            choices.push("Damage or heal a combatant");
+        }
+        if can_delay {
+            choices.push("Delay your turn");
+        }
+        choices.extend(["End the encounter", "Back"]);
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!("Round {} of the encounter.", encounter.round))
+            .default(0)
+            .items(&choices)
+            .interact()
+            .unwrap();
+
+        match choices[selection] {
+            "Show the initiative order" => encounter.display(),
+            "Add a combatant" => {
+                let present = encounter.combatants().to_vec();
+                for combatant in self.choose_new_combatants(&present).unwrap_or_default() {
+                    self.record(format!("{} joined the fight", combatant.name));
+                    if let Some(encounter) = &mut self.encounter {
+                        encounter.add(combatant);
+                    }
+                }
+                if let Some(encounter) = &self.encounter {
+                    encounter.display();
+                }
+            }
+            "Remove a combatant" => self.remove_combatant(),
+            "Damage or heal a combatant" => self.change_combatant_hp(),
+            "Delay your turn" => return self.delay_turn(),
+            "End the encounter" => {
+                self.end_encounter();
+                return true;
+            }
+            _ => {}
+        }
+        false
+    }
+
+    /// Takes someone other than the character out of the fight.
+    fn remove_combatant(&mut self) {
+        let encounter = match &mut self.encounter {
+            Some(encounter) => encounter,
+            None => return,
+        };
+        let others: Vec<usize> = (0..encounter.combatants().len())
+            .filter(|index| !encounter.combatants()[*index].is_player)
+            .collect();
+        let mut choices: Vec<String> = others
+            .iter()
+            .map(|index| encounter.combatants()[*index].name.clone())
+            .collect();
+        choices.push(String::from("Back"));
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Who leaves the fight?")
+            .default(0)
+            .items(&choices)
+            .interact()
+            .unwrap();
+        if let Some(index) = others.get(selection) {
+            let combatant = encounter.remove(*index);
+            self.record(format!("{} left the fight", combatant.name));
+        }
+    }
+
+    /// Holds the current combatant's turn until after someone later in the
+    /// order. Returns true if the turn was delayed.
+    fn delay_turn(&mut self) -> bool {
+        let encounter = match &mut self.encounter {
+            Some(encounter) => encounter,
+            None => return false,
+        };
+        let later: Vec<usize> =
+            (encounter.current_index() + 1..encounter.combatants().len()).collect();
+        let mut choices: Vec<String> = later
+            .iter()
+            .map(|index| format!("After {}", encounter.combatants()[*index].name))
+            .collect();
+        choices.push(String::from("Back"));
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("When will the turn be taken instead?")
+            .default(0)
+            .items(&choices)
+            .interact()
+            .unwrap();
+        let after = match later.get(selection) {
+            Some(after) => *after,
+            None => return false,
+        };
+        let name = encounter.current().name.clone();
+        encounter.delay(after);
+        self.record(format!("{} delayed their turn", name));
+        true
+    }
+
+    /// Another combatant's turn, which the DM runs. The character can react to
+    /// what happens during it.
+    fn other_turn(&mut self) -> bool {
+        let encounter = match &mut self.encounter {
+            Some(encounter) => encounter,
+            None => return true,
+        };
+        let resuming = encounter.resume_delayed();
+        let name = encounter.current().name.clone();
+        let stat_block = encounter.current().stat_block.clone();
+        self.round = encounter.round;
+        pretty_print(
+            &format!(
+                "\nRound {}: {}'s {}turn.",
+                self.round,
+                name,
+                if resuming { "delayed " } else { "" }
+            ),
+            BLUE,
+            true,
+        );
+
+        loop {
+            let encounter = match &self.encounter {
+                Some(encounter) => encounter,
+                None => return false,
+            };
+            let mut choices = vec!["End their turn"];
+            if stat_block.is_some() {
+                choices.extend(["Roll one of their attacks", "Show their stat block"]);
+            }
+            if self.can_react() {
+                choices.push("React to something they do");
+            }
+            if encounter.current_index() + 1 < encounter.combatants().len() {
+                choices.push("Delay their turn");
+            }
+            choices.extend([
+                "Remove them from the fight",
+                "Show the initiative order",
+                "End the encounter",
+                "Quit",
+            ]);
+            let selection = Select::with_theme(&ColorfulTheme::default())
+                .with_prompt(format!("It's {}'s turn.", name))
+                .default(0)
+                .items(&choices)
+                .interact()
+                .unwrap();
+
+            match choices[selection] {
+                "End their turn" => return true,
+                "Roll one of their attacks" => {
+                    if let Some(monster) = &stat_block {
+                        self.monster_attack(&name, monster);
+                    }
+                }
+                "Show their stat block" => {
+                    if let Some(monster) = &stat_block {
+                        monster.display();
+                    }
+                }
+                "React to something they do" => self.between_turns(),
+                "Delay their turn" => {
+                    if self.delay_turn() {
+                        return false;
+                    }
+                }
+                "Remove them from the fight" => {
+                    if let Some(encounter) = &mut self.encounter {
+                        encounter.remove(encounter.current_index());
+                    }
+                    self.record(format!("{} left the fight", name));
+                    return false;
+                }
+                "Show the initiative order" => encounter.display(),
+                "End the encounter" => {
+                    self.end_encounter();
+                    return false;
+                }
+                _ => {
+                    self.quit();
+                    return false;
+                }
+            }
+        }
+    }
+
+    fn end_encounter(&mut self) {
+        let encounter = match self.encounter.take() {
+            Some(encounter) => encounter,
+            None => return,
+        };
+        pretty_print("\nThe encounter is over.", BLUE, true);
+        for line in encounter.summary() {
+            pretty_print(&line, BLUE, true);
+        }
+        let status = &self.play_object.character.status;
+        pretty_print(
+            &format!(
+                "You finish the fight with {}/{} HP.\n",
+                status.current_hp(),
+                status.effective_maximum_hp()
+            ),
+            BLUE,
+            true,
+        );
+        self.record(String::from("The encounter ended"));
+    }
+
+    fn record(&mut self, entry: String) {
+        self.log.push(format!("Round {}: {}", self.round, entry));
+    }
+
+    pub fn show_log(&self) {
+        if self.log.is_empty() {
+            pretty_print("Nothing has happened yet.\n", BLUE, true);
+            return;
+        }
+        for entry in &self.log {
+            pretty_print(entry, BLUE, true);
+        }
+        println!();
+    }
+
+    /// Rolls a dice expression and prints the result.
+    fn roll_expression(&mut self, expression: &DiceExpression, label: &str) -> RollResult {
+        let roll = expression.roll(&mut self.rng);
+        pretty_print(&format!("\n{}: {}\n", label, roll), BLUE, true);
+        roll
+    }
+
+    /// Offers the bonus actions available this turn and resolves the chosen
+    /// one. Only one can be taken per turn.
+    pub fn get_bonus_actions(&mut self) {
+        let bonus_actions = self.turn.bonus_actions(&self.play_object.character);
+        let mut selections: Vec<String> = bonus_actions.iter().map(|b| b.to_string()).collect();
+        selections.push(String::from("Back"));
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Which bonus action will you take?")
+            .default(0)
+            .max_length(10)
+            .items(&selections)
+            .interact()
+            .unwrap();
+        let bonus_action = match bonus_actions.get(selection) {
+            Some(bonus_action) => *bonus_action,
+            None => return,
+        };
+        if !self
+            .turn
+            .take_bonus_action(bonus_action, &mut self.play_object.character)
+        {
+            return;
+        }
+
+        let taken = match bonus_action {
+            BonusAction::MartialArts => {
+                self.unarmed_strike();
+                true
+            }
+            BonusAction::FlurryOfBlows => {
+                self.unarmed_strike();
+                self.unarmed_strike();
+                true
+            }
+            BonusAction::CunningAction(action) => {
+                match action {
+                    Action::Dash => self.dash(),
+                    Action::Hide => self.hide(),
+                    _ => pretty_print(&format!("{}.\n", action.description()), BLUE, true),
+                }
+                true
+            }
+            BonusAction::Rage => {
+                pretty_print(
+                    &format!(
+                        "You rage! Advantage on Strength checks and saves, +{} damage on Strength melee attacks.\n",
+                        self.play_object.character.rage_damage()
+                    ),
+                    BLUE,
+                    true,
+                );
+                true
+            }
+            BonusAction::SecondWind => {
+                let fighter = self
+                    .play_object
+                    .character
+                    .class_level(&Class::Fighter(None));
+                let expression = Dice::D10.expression(1).with_modifier(fighter as i32);
+                let roll = self.roll_expression(&expression, "Second Wind");
+                let character = &mut self.play_object.character;
+                let healed = character.status.heal(roll.total);
+                pretty_print(&format!("You regain {} HP.\n", healed), BLUE, true);
+                true
+            }
+            BonusAction::BardicInspiration => {
+                let ally: String = Input::with_theme(&ColorfulTheme::default())
+                    .with_prompt("Who are you inspiring?")
+                    .default(String::from("your ally"))
+                    .interact_text()
+                    .unwrap();
+                if let Some(die) = self.play_object.character.bardic_inspiration_die() {
+                    pretty_print(
+                        &format!(
+                            "{} can add a {} to one check, attack or save in the next 10 minutes.\n",
+                            ally,
+                            die.expression(1)
+                        ),
+                        BLUE,
+                        true,
+                    );
+                }
+                true
+            }
+            BonusAction::OffHandAttack => self.attack_action(AttackKind::OffHand),
+            BonusAction::Spell => self.cast_a_spell(false),
+        };
+        if taken {
+            self.record(format!("You used {} as a bonus action", bonus_action));
+        } else {
+            // Only the off-hand attack and spells can be backed out of, and
+            // neither costs a resource, so the bonus action is simply freed.
+            self.turn.bonus_action = None;
+        }
+    }
+
+    /// An unarmed strike: 1 + Strength modifier, or a Monk's Martial Arts
+    /// die with the better of Strength and Dexterity.
+    fn unarmed_strike(&mut self) {
+        let character = &self.play_object.character;
+        let strength = character.get_str().get_modifier() as i32;
+        let (modifier, damage) = match character.martial_arts_die() {
+            Some(die) => {
+                let modifier = strength.max(character.get_dex().get_modifier() as i32);
+                (modifier, die.expression(1).with_modifier(modifier))
+            }
+            None => (
+                strength,
+                "1".parse::<DiceExpression>()
+                    .unwrap()
+                    .with_modifier(strength + character.rage_damage()),
+            ),
+        };
+        let attack_bonus = modifier + character.proficiency_bonus() as i32;
+        self.resolve_attack(
+            attack_bonus,
+            "Unarmed strike",
+            Some(format!("{} bludgeoning", damage)),
+        );
+    }
+
+    pub fn ability_check(&mut self) {
+        let character = &self.play_object.character;
+        let skills: Vec<Skill> = Skill::iter().collect();
+        let names = Stat::collect_names();
+        let mut selections: Vec<String> = skills
+            .iter()
+            .map(|skill| {
+                format!(
+                    "{} {}",
+                    skill,
+                    format_modifier(character.skill_modifier(*skill) as i32)
+                )
+            })
+            .collect();
+        selections.extend(names.iter().enumerate().map(|(i, name)| {
+            format!(
+                "{} (no skill) {}",
+                name,
+                format_modifier(character.ability_check_modifier(i) as i32)
+            )
+        }));
+
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Which skill or ability are you checking?")
+            .default(0)
+            .max_length(7)
+            .items(&selections)
+            .interact()
+            .unwrap();
+
+        let (ability, modifier, label, disadvantages) = match skills.get(selection) {
+            Some(skill) => (
+                skill.ability(),
+                character.skill_modifier(*skill),
+                format!("{} check", skill),
+                self.skill_disadvantages(*skill),
+            ),
+            None => {
+                let ability = selection - skills.len();
+                (
+                    ability,
+                    character.ability_check_modifier(ability),
+                    format!("{} check", names[ability]),
+                    vec![],
+                )
+            }
+        };
+        self.roll_d20(
+            RollKind::AbilityCheck(ability),
+            modifier as i32,
+            &label,
+            vec![],
+            disadvantages,
+        );
+    }
+
+    pub fn saving_throw(&mut self) {
+        let character = &self.play_object.character;
+        let names = Stat::collect_names();
+        let selections: Vec<String> = names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| {
+                format!(
+                    "{} {}",
+                    name,
+                    format_modifier(character.save_modifier(i) as i32)
+                )
+            })
+            .collect();
+        let ability = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Which saving throw are you making?")
             .default(0)
-            .items(&TurnOption::collect_string())
+            .items(&selections)
             .interact()
             .unwrap();
 
-        let turn_string = &TurnOption::collect_string()[turn_selection];
+        let threats = SaveAgainst::collect_string();
+        let against = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("What are you saving against?")
+            .default(0)
+            .items(&threats)
+            .interact()
+            .unwrap();
+        let against = SaveAgainst::from_str(&threats[against]).unwrap();
+
+        let dc = choose_target_number("What is the DC?", 10);
+
+        let modifier = character.save_modifier(ability) as i32;
+        let mut advantages = character.save_advantages(ability, against);
+        let status = &character.status;
+        if self.turn.is_active(Action::Dodge)
+            && ability == 1
+            && !status.is_incapacitated()
+            && status.effective_speed() > 0
+        {
+            advantages.push(String::from("Dodge"));
+        }
+        let label = format!("{} saving throw", names[ability]);
+
+        match self.roll_d20(
+            RollKind::SavingThrow(ability),
+            modifier,
+            &label,
+            advantages,
+            vec![],
+        ) {
+            Some(roll) if roll.total >= dc => pretty_print(
+                &format!("Success! ({} vs DC {})\n", roll.total, dc),
+                BLUE,
+                true,
+            ),
+            Some(roll) => pretty_print(
+                &format!("Failure. ({} vs DC {})\n", roll.total, dc),
+                RED,
+                true,
+            ),
+            None => pretty_print(&format!("Failure. (DC {})\n", dc), RED, true),
+        }
+    }
 
-        pretty_print(&format!("\nYou chose to {}.\n", turn_string), BLUE, true);
+    pub fn attack_roll(&mut self) {
+        let bonus: String = Input::with_theme(&ColorfulTheme::default())
+            .with_prompt("What is your attack bonus?")
+            .default(String::from("0"))
+            .validate_with(|input: &String| -> Result<(), &str> {
+                match input.parse::<i32>() {
+                    Ok(_) => Ok(()),
+                    Err(_) => Err("Please enter a number, e.g. 5 or -1"),
+                }
+            })
+            .interact_text()
+            .unwrap();
+        self.roll_d20(
+            RollKind::Attack,
+            bonus.parse().unwrap(),
+            "Attack roll",
+            vec![],
+            vec![],
+        );
+    }
 
-        let turn = TurnOption::from_str(turn_string).unwrap();
+    pub fn roll_dice(&mut self) {
+        let expression: String = Input::with_theme(&ColorfulTheme::default())
+            .with_prompt("What would you like to roll? (e.g. 2d6+3, 1d20+5 adv, 4d6kh3)")
+            .validate_with(|input: &String| -> Result<(), String> {
+                match DiceExpression::from_str(input) {
+                    Ok(_) => Ok(()),
+                    Err(e) => Err(e.to_string()),
+                }
+            })
+            .interact_text()
+            .unwrap();
 
-        match turn {
-            TurnOption::Move => self.get_movement(),
-            TurnOption::Action => self.get_actions(),
-            TurnOption::BonusAction => self.get_bonus_actions(),
-            TurnOption::Quit => self.quit(),
+        let roll = DiceExpression::from_str(&expression)
+            .unwrap()
+            .roll(&mut self.rng);
+        pretty_print(&format!("\nYou rolled {}", roll), BLUE, true);
+        if roll.is_critical() {
+            pretty_print("Natural 20!", BLUE, true);
+        } else if roll.is_fumble() {
+            pretty_print("Natural 1...", RED, true);
         }
+        println!();
+    }
+
+    pub fn manage_conditions(&mut self) {
+        let status = &mut self.play_object.character.status;
+        let choices = [
+            "Add a condition",
+            "Remove a condition",
+            "Set exhaustion level",
+            "Start or end Mage Armor",
+            "Back",
+        ];
+        let choice = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Conditions")
+            .default(0)
+            .items(&choices)
+            .interact()
+            .unwrap();
+
+        match choice {
+            0 => {
+                let conditions = Condition::collect_string();
+                let selection = Select::with_theme(&ColorfulTheme::default())
+                    .with_prompt("Which condition?")
+                    .default(0)
+                    .max_length(7)
+                    .items(&conditions)
+                    .interact()
+                    .unwrap();
+                let condition = Condition::from_str(&conditions[selection]).unwrap();
+                pretty_print(condition.description(), BLUE, true);
 
-        self.play_object.character.display(false)
+                let source: String = Input::with_theme(&ColorfulTheme::default())
+                    .with_prompt("What caused it? (press ENTER to skip)")
+                    .allow_empty(true)
+                    .interact_text()
+                    .unwrap();
+                let rounds: String = Input::with_theme(&ColorfulTheme::default())
+                    .with_prompt("How many rounds does it last? (press ENTER if until removed)")
+                    .allow_empty(true)
+                    .validate_with(|input: &String| -> Result<(), &str> {
+                        match input.trim().is_empty() || input.trim().parse::<u8>().is_ok() {
+                            true => Ok(()),
+                            false => Err("Please enter a number of rounds"),
+                        }
+                    })
+                    .interact_text()
+                    .unwrap();
+
+                status.add_condition(ActiveCondition {
+                    condition,
+                    source: match source.trim() {
+                        "" => None,
+                        s => Some(s.to_owned()),
+                    },
+                    rounds_remaining: rounds.trim().parse::<u8>().ok(),
+                });
+            }
+            1 => {
+                if status.conditions.is_empty() {
+                    pretty_print("You have no conditions to remove.", BLUE, true);
+                    return;
+                }
+                let active: Vec<String> = status.conditions.iter().map(|c| c.to_string()).collect();
+                let selection = Select::with_theme(&ColorfulTheme::default())
+                    .with_prompt("Which condition has ended?")
+                    .default(0)
+                    .items(&active)
+                    .interact()
+                    .unwrap();
+                let condition = status.conditions[selection].condition;
+                status.remove_condition(condition);
+            }
+            2 => {
+                let level: String = Input::with_theme(&ColorfulTheme::default())
+                    .with_prompt(format!("Exhaustion level (0-{})", MAX_EXHAUSTION))
+                    .default(status.exhaustion.to_string())
+                    .validate_with(|input: &String| -> Result<(), &str> {
+                        match input.parse::<u8>() {
+                            Ok(v) if v <= MAX_EXHAUSTION => Ok(()),
+                            _ => Err("That is not a valid exhaustion level"),
+                        }
+                    })
+                    .interact_text()
+                    .unwrap();
+                status.set_exhaustion(level.parse().unwrap());
+                if status.exhaustion == MAX_EXHAUSTION {
+                    pretty_print("Exhaustion level 6: your character dies.", RED, true);
+                }
+            }
+            3 => {
+                status.mage_armor = !status.mage_armor;
+                let character = &mut self.play_object.character;
+                character.recalculate_status();
+                pretty_print(
+                    &format!(
+                        "Mage Armor has {}. Your AC is {}.",
+                        match character.status.mage_armor {
+                            true => "started",
+                            false => "ended",
+                        },
+                        character.status.armor_class()
+                    ),
+                    BLUE,
+                    true,
+                );
+            }
+            _ => {}
+        }
     }
 
-    pub fn get_movement(&self) {}
+    /// Viewing the inventory, adding and removing items and equipping them.
+    fn manage_inventory(&mut self) {
+        let choices = [
+            "Show inventory",
+            "Add an item",
+            "Add a weapon",
+            "Add armor or a shield",
+            "Remove an item",
+            "Equip an item",
+            "Unequip an item",
+            "Turn variant encumbrance on or off",
+            "Back",
+        ];
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Inventory")
+            .default(0)
+            .items(&choices)
+            .interact()
+            .unwrap();
+        let character = &mut self.play_object.character;
+        let armor_class = character.status.armor_class();
+        let inventory = &mut character.inventory;
+        match selection {
+            0 => {}
+            1 => inventory.add(choose_item()),
+            2 => {
+                if let Some(weapon) = choose_weapon() {
+                    inventory.add(Item::from(weapon));
+                }
+            }
+            3 => {
+                if let Some(item) = choose_armor() {
+                    inventory.add(item);
+                }
+            }
+            4 => {
+                let index = match choose_inventory_item(&inventory.items, "Remove which item?") {
+                    Some(index) => index,
+                    None => return,
+                };
+                let quantity = match inventory.items[index].quantity {
+                    1 => 1,
+                    _ => choose_amount("How many?") as u32,
+                };
+                if let Some(item) = inventory.remove(index, quantity) {
+                    pretty_print(
+                        &format!("You remove {} × {}.", item.quantity, item.name),
+                        BLUE,
+                        true,
+                    );
+                }
+            }
+            5 => {
+                let equippable: Vec<usize> = (0..inventory.items.len())
+                    .filter(|i| !inventory.items[*i].slots().is_empty())
+                    .collect();
+                let items: Vec<Item> = equippable
+                    .iter()
+                    .map(|i| inventory.items[*i].clone())
+                    .collect();
+                let index = match choose_inventory_item(&items, "Equip which item?") {
+                    Some(index) => equippable[index],
+                    None => return,
+                };
+                let slots = inventory.items[index].slots();
+                let slot = match slots.len() {
+                    1 => slots[0],
+                    _ => {
+                        let selection = Select::with_theme(&ColorfulTheme::default())
+                            .with_prompt("Where?")
+                            .default(0)
+                            .items(&slots)
+                            .interact()
+                            .unwrap();
+                        slots[selection]
+                    }
+                };
+                match inventory.equip(index, slot) {
+                    Ok(unequipped) => {
+                        for name in unequipped {
+                            pretty_print(&format!("You unequip your {}.", name), BLUE, true);
+                        }
+                    }
+                    Err(message) => pretty_print(&message, RED, true),
+                }
+            }
+            6 => {
+                let equipped: Vec<usize> = (0..inventory.items.len())
+                    .filter(|i| inventory.items[*i].equipped.is_some())
+                    .collect();
+                let items: Vec<Item> = equipped
+                    .iter()
+                    .map(|i| inventory.items[*i].clone())
+                    .collect();
+                if let Some(index) = choose_inventory_item(&items, "Unequip which item?") {
+                    inventory.unequip(equipped[index]);
+                }
+            }
+            7 => {
+                inventory.variant_encumbrance = !inventory.variant_encumbrance;
+                pretty_print(
+                    &format!(
+                        "Variant encumbrance is {}.",
+                        match inventory.variant_encumbrance {
+                            true => "on",
+                            false => "off",
+                        }
+                    ),
+                    BLUE,
+                    true,
+                );
+            }
+            _ => return,
+        }
+        character.recalculate_status();
+        if character.status.armor_class() != armor_class {
+            pretty_print(
+                &format!("Your AC is now {}.", character.status.armor_class()),
+                BLUE,
+                true,
+            );
+        }
+        character.display_inventory();
+    }
 
-    pub fn get_actions(&self) {}
+    /// Buying and selling equipment, and keeping track of other coins gained
+    /// and spent, all of which go in the ledger.
+    fn shop(&mut self) {
+        pretty_print(
+            &format!("You have {}.", self.play_object.character.purse),
+            BLUE,
+            true,
+        );
+        let choices = [
+            "Buy equipment",
+            "Sell an item",
+            "Gain coins",
+            "Spend coins",
+            "Show the ledger",
+            "Back",
+        ];
+        let selection = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt("Shop")
+            .default(0)
+            .items(&choices)
+            .interact()
+            .unwrap();
+        match selection {
+            0 => {
+                if !self.play_object.buy_from_catalogue() {
+                    return;
+                }
+            }
+            1 => {
+                let items = &self.play_object.character.inventory.items;
+                let index = match choose_inventory_item(items, "Sell which item?") {
+                    Some(index) => index,
+                    None => return,
+                };
+                let quantity = match items[index].quantity {
+                    1 => 1,
+                    held => (choose_amount("How many?") as u32).clamp(1, held),
+                };
+                // Equipment normally sells for half its price.
+                let offer = items[index].price().unwrap_or(0) as u64 * quantity as u64 / 2;
+                let offer = u32::try_from(offer).ok();
+                let proceeds = choose_coins("How much do you get for it?", offer);
+                // Parsing keeps any amount entered within a u32.
+                let amount = proceeds.total() as u32;
+                if let Some(item) = self.play_object.sell(index, quantity, amount) {
+                    pretty_print(
+                        &format!(
+                            "You sell {} × {} for {}.",
+                            item.quantity, item.name, proceeds
+                        ),
+                        BLUE,
+                        true,
+                    );
+                }
+            }
+            2 => {
+                let coins = choose_coins("How many coins?", None);
+                let reason = choose_reason("Where are they from?");
+                self.play_object.gain_coins(&coins, &reason);
+            }
+            3 => {
+                let amount = choose_coins("How much?", None).total() as u32;
+                let reason = choose_reason("What for?");
+                if let Err(message) = self.play_object.spend_coins(amount, &reason) {
+                    pretty_print(&message, RED, true);
+                }
+            }
+            4 => {
+                if self.play_object.ledger.is_empty() {
+                    pretty_print("Nothing has been bought or sold yet.", BLUE, true);
+                }
+                for transaction in &self.play_object.ledger {
+                    println!("{}", transaction);
+                }
+                println!();
+            }
+            _ => return,
+        }
+        pretty_print(
+            &format!("You have {}.", self.play_object.character.purse),
+            BLUE,
+            true,
+        );
+    }
 
-    pub fn get_bonus_actions(&self) {}
+    /// Saves mid-session, only speaking up if it fails.
+    fn autosave(&mut self) {
+        if let Err(e) = self.play_object.save() {
+            pretty_print(&format!("Unable to save: {}", e), RED, true);
+        }
+    }
 
+    /// Ends play, saving the character so that what happened to them carries
+    /// over to the next session.
     pub fn quit(&mut self) {
         self.active = false;
+        match self.play_object.save() {
+            Ok(()) => pretty_print(&format!("Saved to {}.", SAVE_FILE), BLUE, true),
+            Err(e) => pretty_print(&format!("Unable to save: {}", e), RED, true),
+        }
+    }
+}
+
+/// Asks for a piece of gear's name, quantity, weight and notes.
+fn choose_item() -> Item {
+    let name: String = Input::with_theme(&ColorfulTheme::default())
+        .with_prompt("What is it?")
+        .interact_text()
+        .unwrap();
+    let quantity = choose_amount("How many?").max(1) as u32;
+    let weight: f32 = Input::with_theme(&ColorfulTheme::default())
+        .with_prompt("How many pounds does each weigh?")
+        .default(0.0)
+        .validate_with(|input: &f32| -> Result<(), &str> {
+            match *input >= 0.0 {
+                true => Ok(()),
+                false => Err("Please enter a weight of 0 or more"),
+            }
+        })
+        .interact_text()
+        .unwrap();
+    let notes: String = Input::with_theme(&ColorfulTheme::default())
+        .with_prompt("Any notes? (press ENTER to skip)")
+        .allow_empty(true)
+        .interact_text()
+        .unwrap();
+    let mut item = Item::new(name.trim(), ItemKind::Gear, quantity, weight);
+    item.notes = notes;
+    item
+}
+
+/// Picks a suit of armor from the catalogue, or a shield.
+fn choose_armor() -> Option<Item> {
+    let armor: Vec<Armor> = Armor::iter().collect();
+    let mut choices: Vec<String> = armor.iter().map(|a| a.describe()).collect();
+    choices.push(format!("Shield (+{} AC)", SHIELD_BONUS));
+    choices.push(String::from("Back"));
+    let selection = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt("Which armor?")
+        .default(0)
+        .items(&choices)
+        .interact()
+        .unwrap();
+    match armor.get(selection) {
+        Some(armor) => Some(Item::from(*armor)),
+        None => (selection == armor.len()).then(Item::shield),
+    }
+}
+
+/// Picks a weapon from the catalogue.
+fn choose_weapon() -> Option<Weapon> {
+    let weapons: Vec<Weapon> = Weapon::iter().collect();
+    let mut choices: Vec<String> = weapons.iter().map(|w| w.describe()).collect();
+    choices.push(String::from("Back"));
+    let selection = FuzzySelect::with_theme(&ColorfulTheme::default())
+        .with_prompt("Which weapon? (type to search)")
+        .default(0)
+        .items(&choices)
+        .interact()
+        .unwrap();
+    weapons.get(selection).copied()
+}
+
+/// Asks for an amount of coins like "12 gp, 5 sp".
+fn choose_coins(prompt: &str, default: Option<u32>) -> Purse {
+    let theme = ColorfulTheme::default();
+    let mut input = Input::with_theme(&theme);
+    input.with_prompt(prompt);
+    if let Some(amount) = default {
+        input.default(Purse::from_value(amount).to_string());
+    }
+    let coins: String = input
+        .validate_with(|input: &String| -> Result<(), String> {
+            Purse::from_str(input).map(|_| ())
+        })
+        .interact_text()
+        .unwrap();
+    Purse::from_str(&coins).unwrap()
+}
+
+fn choose_reason(prompt: &str) -> String {
+    Input::with_theme(&ColorfulTheme::default())
+        .with_prompt(prompt)
+        .interact_text()
+        .unwrap()
+}
+
+/// Picks one of `items`, returning its index, or `None` for Back.
+fn choose_inventory_item(items: &[Item], prompt: &str) -> Option<usize> {
+    if items.is_empty() {
+        pretty_print("There is nothing to choose.", BLUE, true);
+        return None;
+    }
+    let mut choices: Vec<String> = items
+        .iter()
+        .map(|item| {
+            let mut choice = format!("{} × {}", item.quantity, item.name);
+            if let Some(slot) = item.equipped {
+                choice.push_str(&format!(" ({})", slot));
+            }
+            choice
+        })
+        .collect();
+    choices.push(String::from("Back"));
+    let selection = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt(prompt)
+        .default(0)
+        .items(&choices)
+        .interact()
+        .unwrap();
+    (selection < items.len()).then_some(selection)
+}
+
+/// Asks for a DC or AC between 1 and 30.
+fn choose_target_number(prompt: &str, default: i32) -> i32 {
+    let number: String = Input::with_theme(&ColorfulTheme::default())
+        .with_prompt(prompt)
+        .default(default.to_string())
+        .validate_with(|input: &String| -> Result<(), &str> {
+            match input.parse::<u8>() {
+                Ok(1..=30) => Ok(()),
+                _ => Err("Please enter a number between 1 and 30"),
+            }
+        })
+        .interact_text()
+        .unwrap();
+    number.parse().unwrap()
+}
+
+/// Asks for a dice expression. With no `default`, pressing ENTER skips the roll.
+fn choose_dice(prompt: &str, default: Option<String>) -> Option<DiceExpression> {
+    let theme = ColorfulTheme::default();
+    let mut input = Input::<String>::with_theme(&theme);
+    input.with_prompt(prompt).allow_empty(default.is_none());
+    if let Some(default) = default {
+        input.default(default);
+    }
+    let expression = input
+        .validate_with(|input: &String| -> Result<(), String> {
+            match input.trim().is_empty() {
+                true => Ok(()),
+                false => DiceExpression::from_str(input)
+                    .map(|_| ())
+                    .map_err(|e| e.to_string()),
+            }
+        })
+        .interact_text()
+        .unwrap();
+    DiceExpression::from_str(&expression).ok()
+}
+
+/// Asks for an amount of damage or healing.
+fn choose_amount(prompt: &str) -> i32 {
+    let amount: String = Input::with_theme(&ColorfulTheme::default())
+        .with_prompt(prompt)
+        .validate_with(|input: &String| -> Result<(), &str> {
+            match input.parse::<u16>() {
+                Ok(_) => Ok(()),
+                Err(_) => Err("Please enter a whole number"),
+            }
+        })
+        .interact_text()
+        .unwrap();
+    amount.parse().unwrap()
+}
+
+/// The attack roll, damage, damage type and whether it was a critical hit,
+/// for an attack the character is reacting to.
+fn choose_hit() -> Hit {
+    let attack = choose_target_number("What did the attack roll total?", 10);
+    let damage = choose_amount("How much damage does the attack deal?");
+    let damage_type = choose_damage_type();
+    let critical = Confirm::with_theme(&ColorfulTheme::default())
+        .with_prompt("Was it a critical hit?")
+        .default(false)
+        .interact()
+        .unwrap();
+    Hit {
+        attack,
+        damage,
+        damage_type,
+        critical,
+    }
+}
+
+fn choose_damage_type() -> DamageType {
+    let damage_types: Vec<DamageType> = DamageType::iter().collect();
+    let selection = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt("What type of damage?")
+        .default(0)
+        .items(&damage_types)
+        .interact()
+        .unwrap();
+    damage_types[selection]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::classes::ClassLevel;
+    use crate::data::races::Race;
+    use crate::state::play_object::legacy_save;
+
+    fn play_state() -> PlayState {
+        PlayState::new(legacy_save(), GameRng::new(Some(7)))
+    }
+
+    fn add_class(play_state: &mut PlayState, class: Class, level: u8) {
+        let character = &mut play_state.play_object.character;
+        character.classes.push(ClassLevel { class, level });
+        character.level += level;
+    }
+
+    #[test]
+    fn reactions_depend_on_class_level_and_slots() {
+        let mut play_state = play_state();
+        // A level 8 Monk can only deflect missiles.
+        assert!(play_state.reactions_for(Trigger::HitByAttack).is_empty());
+        assert_eq!(
+            play_state.reactions_for(Trigger::HitByRangedWeapon),
+            [Reaction::DeflectMissiles]
+        );
+        assert!(play_state.reactions_for(Trigger::SpellCast).is_empty());
+        assert!(play_state
+            .reactions_for(Trigger::DamagedByCreature)
+            .is_empty());
+
+        // Rogue 4 isn't enough for Uncanny Dodge.
+        add_class(&mut play_state, Class::Rogue(None), 4);
+        assert!(play_state.reactions_for(Trigger::HitByAttack).is_empty());
+        play_state.play_object.character.classes[1].level = 5;
+        assert_eq!(
+            play_state.reactions_for(Trigger::HitByAttack),
+            [Reaction::UncannyDodge]
+        );
+
+        // Wizard 5 has 3rd-level slots for Counterspell.
+        add_class(&mut play_state, Class::Wizard(None), 5);
+        assert_eq!(
+            play_state.reactions_for(Trigger::HitByAttack),
+            [Reaction::Shield, Reaction::UncannyDodge]
+        );
+        assert_eq!(
+            play_state.reactions_for(Trigger::SpellCast),
+            [Reaction::Counterspell]
+        );
+        let character = &mut play_state.play_object.character;
+        for _ in 0..2 {
+            assert_eq!(character.expend_spell_slot(3, false), Some(3));
+        }
+        assert!(play_state.reactions_for(Trigger::SpellCast).is_empty());
+        let character = &mut play_state.play_object.character;
+        while character.expend_spell_slot(1, false).is_some() {}
+        assert_eq!(
+            play_state.reactions_for(Trigger::HitByAttack),
+            [Reaction::UncannyDodge]
+        );
     }
 
-    // pub fn get_reactions(&self) {}
+    #[test]
+    fn hellish_rebuke_comes_from_pact_slots_or_infernal_legacy() {
+        let mut play_state = play_state();
+        play_state.play_object.character.race = Race::Tiefling;
+        assert_eq!(
+            play_state.reactions_for(Trigger::DamagedByCreature),
+            [Reaction::HellishRebuke]
+        );
+        let character = &mut play_state.play_object.character;
+        character.spend_resource(Resource::HellishRebuke, 1);
+        assert!(play_state
+            .reactions_for(Trigger::DamagedByCreature)
+            .is_empty());
+
+        add_class(&mut play_state, Class::Warlock(None), 1);
+        assert_eq!(
+            play_state.reactions_for(Trigger::DamagedByCreature),
+            [Reaction::HellishRebuke]
+        );
+        let character = &mut play_state.play_object.character;
+        assert_eq!(character.expend_spell_slot(1, true), Some(1));
+        assert!(play_state
+            .reactions_for(Trigger::DamagedByCreature)
+            .is_empty());
+    }
+
+    #[test]
+    fn reactions_refresh_at_the_start_of_the_turn() {
+        let mut play_state = play_state();
+        let armor_class = play_state.armor_class();
+        play_state.turn.reaction = Some(Reaction::Shield);
+        play_state.turn.shield = true;
+        assert!(play_state.turn.reaction_used());
+        assert_eq!(play_state.armor_class(), armor_class + 5);
+
+        play_state.start_turn();
+        assert!(!play_state.turn.reaction_used());
+        assert_eq!(play_state.armor_class(), armor_class);
+
+        // Nor can an unconscious character react.
+        assert!(play_state.can_react());
+        play_state
+            .play_object
+            .character
+            .status
+            .take_damage(100, false);
+        assert!(!play_state.can_react());
+    }
 }